@@ -8,9 +8,12 @@ use ddnet_account_client::{
     errors::{FsLikeError, HttpLikeError},
     interface::Io,
 };
+use ddnet_accounts_shared::account_server::health::{HealthReport, HealthStatus};
 use serde::{Deserialize, Serialize};
 
-use crate::{fs::Fs, http::Http};
+use crate::{fs::Fs, http::Http, key_store::KeyStore};
+
+pub(crate) const SESSION_KEY_FILE: &str = "account.key";
 
 #[derive(Debug, Serialize, Deserialize)]
 struct FastestHttp {
@@ -30,6 +33,8 @@ pub struct ClientHttpTokioFs {
     pub http: Vec<Arc<dyn Http>>,
     pub cur_http: AtomicUsize,
     pub fs: Fs,
+    /// Stores the session key pair.
+    pub key_store: Arc<dyn KeyStore>,
 }
 
 impl ClientHttpTokioFs {
@@ -161,16 +166,24 @@ impl ClientHttpTokioFs {
         }
     }
 
-    async fn evalulate_fastest_http(http: &[Arc<dyn Http>]) -> usize {
+    /// Measures the response time of all servers for the given url,
+    /// only counting responses that pass the `is_ok` check.
+    async fn evalulate_fastest_http_by(
+        http: &[Arc<dyn Http>],
+        url: &'static str,
+        is_ok: fn(&[u8]) -> bool,
+    ) -> Option<usize> {
         let mut handles: Vec<_> = Default::default();
         for (i, http) in http.iter().enumerate() {
             let http = http.clone();
             handles.push((
                 tokio::spawn(async move {
                     let i = std::time::Instant::now();
-                    match Self::get_json_http(&http, "/ping").await {
-                        Ok(_) => Some(std::time::Instant::now().saturating_duration_since(i)),
-                        Err(_) => None,
+                    match Self::get_json_http(&http, url).await {
+                        Ok(res) if is_ok(&res) => {
+                            Some(std::time::Instant::now().saturating_duration_since(i))
+                        }
+                        _ => None,
                     }
                 }),
                 i,
@@ -186,7 +199,22 @@ impl ClientHttpTokioFs {
             .into_iter()
             .min_by_key(|(time, _)| *time)
             .map(|(_, index)| index)
-            .unwrap_or_default()
+    }
+
+    async fn evalulate_fastest_http(http: &[Arc<dyn Http>]) -> usize {
+        // prefer servers that are ready, degraded servers are skipped.
+        // older servers don't have a health endpoint, so fall back to a ping
+        match Self::evalulate_fastest_http_by(http, "/health/ready", |res| {
+            serde_json::from_slice::<HealthReport>(res)
+                .is_ok_and(|report| report.status == HealthStatus::Ok)
+        })
+        .await
+        {
+            Some(index) => index,
+            None => Self::evalulate_fastest_http_by(http, "/ping", |_| true)
+                .await
+                .unwrap_or_default(),
+        }
     }
 
     pub async fn get_fastest_http(fs: &Fs, http: &[Arc<dyn Http>]) -> usize {
@@ -317,6 +345,21 @@ impl Io for ClientHttpTokioFs {
     async fn request_account_info(&self, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpLikeError> {
         self.post_json("/account-info", data).await
     }
+    async fn request_sessions(&self, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpLikeError> {
+        self.post_json("/sessions", data).await
+    }
+    async fn request_session_revoke(
+        &self,
+        data: Vec<u8>,
+    ) -> anyhow::Result<Vec<u8>, HttpLikeError> {
+        self.post_json("/session-revoke", data).await
+    }
+    async fn request_account_events(
+        &self,
+        data: Vec<u8>,
+    ) -> anyhow::Result<Vec<u8>, HttpLikeError> {
+        self.post_json("/account-events", data).await
+    }
     async fn download_account_server_certificates(&self) -> anyhow::Result<Vec<u8>, HttpLikeError> {
         self.get_json("/certs").await
     }
@@ -324,15 +367,13 @@ impl Io for ClientHttpTokioFs {
         &self,
         file: Vec<u8>,
     ) -> anyhow::Result<(), FsLikeError> {
-        self.fs
-            .write("".as_ref(), "account.key".as_ref(), file)
-            .await
+        self.key_store.write(&self.fs, SESSION_KEY_FILE, file).await
     }
     async fn read_serialized_session_key_pair(&self) -> anyhow::Result<Vec<u8>, FsLikeError> {
-        self.fs.read("account.key".as_ref()).await
+        self.key_store.read(&self.fs, SESSION_KEY_FILE).await
     }
     async fn remove_serialized_session_key_pair(&self) -> anyhow::Result<(), FsLikeError> {
-        self.fs.remove("account.key".as_ref()).await
+        self.key_store.remove(&self.fs, SESSION_KEY_FILE).await
     }
 }
 
@@ -0,0 +1,344 @@
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use ddnet_account_client::errors::FsLikeError;
+
+use crate::fs::Fs;
+
+/// Storage for the secret keys of the client,
+/// e.g. the session key pair of an account.
+///
+/// Keys are stored per [`Fs`], so every profile has its own keys.
+#[async_trait]
+pub trait KeyStore: Debug + Sync + Send {
+    async fn write(&self, fs: &Fs, name: &str, key: Vec<u8>) -> anyhow::Result<(), FsLikeError>;
+    async fn read(&self, fs: &Fs, name: &str) -> anyhow::Result<Vec<u8>, FsLikeError>;
+    async fn remove(&self, fs: &Fs, name: &str) -> anyhow::Result<(), FsLikeError>;
+
+    /// Moves a key that an older client wrote unencrypted into this key store.
+    ///
+    /// Only meant for a one-time migration, [`KeyStore::read`] never accepts
+    /// unencrypted keys. Returns `false` if there is no unencrypted key.
+    async fn migrate_plain(&self, _fs: &Fs, _name: &str) -> anyhow::Result<bool, FsLikeError> {
+        Ok(false)
+    }
+}
+
+/// Reads a file, `None` if it does not exist.
+async fn read_existing(fs: &Fs, name: &str) -> anyhow::Result<Option<Vec<u8>>, FsLikeError> {
+    match fs.read(name.as_ref()).await {
+        Ok(file) => Ok(Some(file)),
+        Err(FsLikeError::Fs(err)) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Stores the keys unencrypted in the secure directory.
+///
+/// Everyone with access to the directory can use the keys,
+/// prefer [`EncryptedFileKeyStore`].
+#[derive(Debug, Default)]
+pub struct PlainKeyStore;
+
+#[async_trait]
+impl KeyStore for PlainKeyStore {
+    async fn write(&self, fs: &Fs, name: &str, key: Vec<u8>) -> anyhow::Result<(), FsLikeError> {
+        fs.write("".as_ref(), name.as_ref(), key).await
+    }
+    async fn read(&self, fs: &Fs, name: &str) -> anyhow::Result<Vec<u8>, FsLikeError> {
+        fs.read(name.as_ref()).await
+    }
+    async fn remove(&self, fs: &Fs, name: &str) -> anyhow::Result<(), FsLikeError> {
+        fs.remove(name.as_ref()).await
+    }
+}
+
+/// The input of the key derivation of the [`EncryptedFileKeyStore`].
+#[derive(Clone)]
+pub enum KeyStoreSecret {
+    /// A passphrase chosen by the user.
+    Passphrase(String),
+    /// The unique id of this machine,
+    /// see [`ddnet_accounts_shared::client::machine_id::machine_uid`].
+    /// Binds the keys to this machine,
+    /// on systems without machine id this is not better than [`PlainKeyStore`].
+    MachineId,
+}
+
+impl Debug for KeyStoreSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Passphrase(_) => f.write_str("Passphrase(..)"),
+            Self::MachineId => f.write_str("MachineId"),
+        }
+    }
+}
+
+/// Identifies the files written by the [`EncryptedFileKeyStore`] & the version of their format.
+const ENCRYPTED_MAGIC: &[u8; 8] = b"ddnetks1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// Stores the keys in the secure directory,
+/// encrypted with XChaCha20-Poly1305.
+/// The encryption key is derived using argon2 from a [`KeyStoreSecret`],
+/// so a copy of the directory is useless without it.
+///
+/// Files written by the [`PlainKeyStore`] are only read by [`KeyStore::migrate_plain`].
+pub struct EncryptedFileKeyStore {
+    secret: KeyStoreSecret,
+    /// The derived encryption keys by their salt, since argon2 is slow on purpose.
+    /// New files reuse the salt of a derived key,
+    /// so usually a single key is derived per store.
+    derived_keys: parking_lot::Mutex<HashMap<[u8; SALT_LEN], [u8; 32]>>,
+}
+
+impl Debug for EncryptedFileKeyStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedFileKeyStore")
+            .field("secret", &self.secret)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EncryptedFileKeyStore {
+    pub fn new(secret: KeyStoreSecret) -> Self {
+        Self {
+            secret,
+            derived_keys: Default::default(),
+        }
+    }
+
+    /// The salt for new files.
+    fn salt(&self) -> [u8; SALT_LEN] {
+        self.derived_keys
+            .lock()
+            .keys()
+            .next()
+            .copied()
+            .unwrap_or_else(|| {
+                let mut salt = [0; SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                salt
+            })
+    }
+
+    async fn derive_key(&self, salt: [u8; SALT_LEN]) -> anyhow::Result<[u8; 32], FsLikeError> {
+        if let Some(key) = self.derived_keys.lock().get(&salt) {
+            return Ok(*key);
+        }
+        let secret = self.secret.clone();
+        let key = tokio::task::spawn_blocking(move || {
+            let secret = match secret {
+                KeyStoreSecret::Passphrase(passphrase) => passphrase.into_bytes(),
+                KeyStoreSecret::MachineId => {
+                    ddnet_accounts_shared::client::machine_id::machine_uid()?.to_vec()
+                }
+            };
+            let mut key = [0; 32];
+            argon2::Argon2::default()
+                .hash_password_into(&secret, &salt, &mut key)
+                .map_err(|err| anyhow!("failed to derive key: {err}"))?;
+            anyhow::Ok(key)
+        })
+        .await
+        .map_err(|err| FsLikeError::Other(err.into()))?
+        .map_err(FsLikeError::Other)?;
+        self.derived_keys.lock().insert(salt, key);
+        Ok(key)
+    }
+
+    /// Encrypts the data, the name is authenticated too,
+    /// so the result can't be used under another name.
+    pub(crate) async fn encrypt(
+        &self,
+        name: &str,
+        key: Vec<u8>,
+    ) -> anyhow::Result<Vec<u8>, FsLikeError> {
+        let salt = self.salt();
+        let cipher = XChaCha20Poly1305::new(&self.derive_key(salt).await?.into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let encrypted = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &key,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|err| FsLikeError::Other(anyhow!("failed to encrypt key: {err}")))?;
+
+        let mut file =
+            Vec::with_capacity(ENCRYPTED_MAGIC.len() + SALT_LEN + NONCE_LEN + encrypted.len());
+        file.extend_from_slice(ENCRYPTED_MAGIC);
+        file.extend_from_slice(&salt);
+        file.extend_from_slice(&nonce);
+        file.extend(encrypted);
+        Ok(file)
+    }
+
+    pub(crate) async fn decrypt(
+        &self,
+        name: &str,
+        file: &[u8],
+    ) -> anyhow::Result<Vec<u8>, FsLikeError> {
+        let invalid = || FsLikeError::Other(anyhow!("the encrypted key file is corrupted"));
+        let file = file
+            .strip_prefix(ENCRYPTED_MAGIC.as_slice())
+            .ok_or_else(invalid)?;
+        let salt: [u8; SALT_LEN] = file
+            .get(..SALT_LEN)
+            .and_then(|salt| salt.try_into().ok())
+            .ok_or_else(invalid)?;
+        let nonce = file
+            .get(SALT_LEN..SALT_LEN + NONCE_LEN)
+            .map(XNonce::from_slice)
+            .ok_or_else(invalid)?;
+        let cipher = XChaCha20Poly1305::new(&self.derive_key(salt).await?.into());
+        cipher
+            .decrypt(
+                nonce,
+                Payload {
+                    msg: &file[SALT_LEN + NONCE_LEN..],
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                FsLikeError::Other(anyhow!(
+                    "failed to decrypt key, wrong secret or the file was copied from another machine"
+                ))
+            })
+    }
+}
+
+impl Default for EncryptedFileKeyStore {
+    fn default() -> Self {
+        Self::new(KeyStoreSecret::MachineId)
+    }
+}
+
+#[async_trait]
+impl KeyStore for EncryptedFileKeyStore {
+    async fn write(&self, fs: &Fs, name: &str, key: Vec<u8>) -> anyhow::Result<(), FsLikeError> {
+        let file = self.encrypt(name, key).await?;
+        fs.write("".as_ref(), name.as_ref(), file).await
+    }
+    async fn read(&self, fs: &Fs, name: &str) -> anyhow::Result<Vec<u8>, FsLikeError> {
+        let file = fs.read(name.as_ref()).await?;
+        self.decrypt(name, &file).await
+    }
+    async fn remove(&self, fs: &Fs, name: &str) -> anyhow::Result<(), FsLikeError> {
+        fs.remove(name.as_ref()).await
+    }
+    async fn migrate_plain(&self, fs: &Fs, name: &str) -> anyhow::Result<bool, FsLikeError> {
+        match read_existing(fs, name).await? {
+            Some(file) if !file.starts_with(ENCRYPTED_MAGIC) => {
+                self.write(fs, name, file).await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[cfg(feature = "keyring")]
+const KEYRING_MARKER: &[u8] = b"keyring";
+
+/// Stores the keys in the keyring of the operating system
+/// (Secret Service/keyutils on Linux, Keychain on macOS, Credential Manager on Windows).
+/// Only a file marking the existence of a key is written to the secure directory.
+#[cfg(feature = "keyring")]
+#[derive(Debug)]
+pub struct KeyringKeyStore {
+    service: String,
+}
+
+#[cfg(feature = "keyring")]
+impl KeyringKeyStore {
+    pub fn new(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+        }
+    }
+
+    async fn with_entry<T: Send + 'static>(
+        &self,
+        fs: &Fs,
+        name: &str,
+        op: impl FnOnce(keyring::Entry) -> keyring::Result<T> + Send + 'static,
+    ) -> anyhow::Result<T, FsLikeError> {
+        // the secure directory identifies the profile
+        let user = fs.secure_path.join(name).to_string_lossy().to_string();
+        let service = self.service.clone();
+        tokio::task::spawn_blocking(move || keyring::Entry::new(&service, &user).and_then(op))
+            .await
+            .map_err(|err| FsLikeError::Other(err.into()))?
+            .map_err(|err| match err {
+                keyring::Error::NoEntry => FsLikeError::Fs(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "no key in the keyring",
+                )),
+                err => FsLikeError::Other(err.into()),
+            })
+    }
+}
+
+#[cfg(feature = "keyring")]
+impl Default for KeyringKeyStore {
+    fn default() -> Self {
+        Self::new("ddnet-accounts")
+    }
+}
+
+#[cfg(feature = "keyring")]
+#[async_trait]
+impl KeyStore for KeyringKeyStore {
+    async fn write(&self, fs: &Fs, name: &str, key: Vec<u8>) -> anyhow::Result<(), FsLikeError> {
+        self.with_entry(fs, name, move |entry| entry.set_secret(&key))
+            .await?;
+        // the marker is removed together with the profile's directory,
+        // so the keyring entry is not used for a new profile in the same directory.
+        fs.write("".as_ref(), name.as_ref(), KEYRING_MARKER.to_vec())
+            .await
+    }
+    async fn read(&self, fs: &Fs, name: &str) -> anyhow::Result<Vec<u8>, FsLikeError> {
+        let file = fs.read(name.as_ref()).await?;
+        if file == KEYRING_MARKER {
+            self.with_entry(fs, name, |entry| entry.get_secret()).await
+        } else {
+            Err(FsLikeError::Other(anyhow!(
+                "the key is not stored in the keyring"
+            )))
+        }
+    }
+    async fn remove(&self, fs: &Fs, name: &str) -> anyhow::Result<(), FsLikeError> {
+        let _ = self
+            .with_entry(fs, name, |entry| entry.delete_credential())
+            .await;
+        fs.remove(name.as_ref()).await
+    }
+    async fn migrate_plain(&self, fs: &Fs, name: &str) -> anyhow::Result<bool, FsLikeError> {
+        match read_existing(fs, name).await? {
+            None => Ok(false),
+            Some(file) if file == KEYRING_MARKER => Ok(false),
+            // only the secret of the encrypted key store can decrypt it
+            Some(file) if file.starts_with(ENCRYPTED_MAGIC) => Err(FsLikeError::Other(anyhow!(
+                "the key is encrypted by another key store, it can't be moved to the keyring"
+            ))),
+            Some(file) => {
+                self.write(fs, name, file).await?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// The key store used if the client does not choose one.
+pub fn default_key_store() -> Arc<dyn KeyStore> {
+    Arc::new(EncryptedFileKeyStore::default())
+}
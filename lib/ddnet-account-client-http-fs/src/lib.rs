@@ -2,4 +2,6 @@ pub mod cert_downloader;
 pub mod client;
 pub mod fs;
 pub mod http;
+pub mod key_store;
 pub mod profiles;
+pub mod profiles_export;
@@ -3,7 +3,7 @@ use std::{
     fmt::Debug,
     future::Future,
     ops::Deref,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
     sync::Arc,
     time::{Duration, SystemTime},
@@ -15,7 +15,11 @@ pub use ddnet_account_client::{
 };
 use ddnet_account_client::{interface::Io, sign::SignResult};
 use ddnet_accounts_shared::{
-    account_server::account_info::AccountInfoResponse,
+    account_server::{
+        account_events::AccountEventsResponse,
+        account_info::AccountInfoResponse,
+        sessions::{SessionFingerprint, SessionsResponse},
+    },
     cert::generate_self_signed,
     client::{
         account_data::{key_pair, AccountDataForClient},
@@ -32,7 +36,12 @@ use x509_cert::der::Decode;
 
 pub use x509_cert::Certificate;
 
-use crate::{client::DeleteAccountExt, fs::Fs};
+use crate::{
+    client::{DeleteAccountExt, SESSION_KEY_FILE},
+    fs::Fs,
+    key_store::KeyStore,
+    profiles_export::{ExportedProfile, PendingImport, ProfilesArchive, ProfilesExport},
+};
 
 #[serde_as]
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -44,10 +53,19 @@ pub struct ProfileData {
     pub user: HashMap<String, serde_json::Value>,
 }
 
+#[serde_as]
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 struct ProfilesState {
     pub profiles: HashMap<String, ProfileData>,
     pub cur_profile: String,
+    /// Imported profiles that were not logged in yet.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "DefaultOnError")]
+    pub imported: HashMap<String, ExportedProfile>,
+    /// The keys of older clients were moved into the key store,
+    /// see [`KeyStore::migrate_plain`].
+    #[serde(default)]
+    pub keys_migrated: bool,
 }
 
 impl ProfilesState {
@@ -98,6 +116,7 @@ pub struct ActiveProfile<C: Io + DeleteAccountExt + Debug> {
 pub struct ActiveProfiles<C: Io + DeleteAccountExt + Debug> {
     profiles: HashMap<String, ActiveProfile<C>>,
     cur_profile: String,
+    imported: HashMap<String, ExportedProfile>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -118,6 +137,7 @@ pub struct Profiles<
     F: Deref<
             Target = dyn Fn(
                 PathBuf,
+                Arc<dyn KeyStore>,
             )
                 -> Pin<Box<dyn Future<Output = anyhow::Result<C>> + Sync + Send>>,
         > + Debug
@@ -128,6 +148,7 @@ pub struct Profiles<
     factory: Arc<F>,
     secure_base_path: Arc<PathBuf>,
     fs: Fs,
+    key_store: Arc<dyn KeyStore>,
 }
 
 impl<
@@ -135,6 +156,7 @@ impl<
         F: Deref<
                 Target = dyn Fn(
                     PathBuf,
+                    Arc<dyn KeyStore>,
                 )
                     -> Pin<Box<dyn Future<Output = anyhow::Result<C>> + Sync + Send>>,
             > + Debug
@@ -152,6 +174,7 @@ impl<
                 .map(|(key, val)| (key.clone(), val.profile_data.clone())),
         );
         res.cur_profile.clone_from(&profiles.cur_profile);
+        res.imported.clone_from(&profiles.imported);
 
         res
     }
@@ -166,6 +189,7 @@ impl<
             factory: loading.factory,
             secure_base_path: Arc::new(loading.secure_base_path),
             fs: loading.fs,
+            key_store: loading.key_store,
         }
     }
 
@@ -178,7 +202,7 @@ impl<
     ) -> anyhow::Result<(), CredentialAuthTokenResult> {
         let path = self.secure_base_path.join("acc_prepare");
         let account_client = Arc::new(
-            (self.factory)(path)
+            (self.factory)(path, self.key_store.clone())
                 .await
                 .map_err(CredentialAuthTokenResult::Other)?,
         );
@@ -203,7 +227,7 @@ impl<
     ) -> anyhow::Result<String, CredentialAuthTokenResult> {
         let path = self.secure_base_path.join("acc_prepare");
         let account_client = Arc::new(
-            (self.factory)(path)
+            (self.factory)(path, self.key_store.clone())
                 .await
                 .map_err(CredentialAuthTokenResult::Other)?,
         );
@@ -226,7 +250,7 @@ impl<
     ) -> anyhow::Result<(), AccountTokenResult> {
         let path = self.secure_base_path.join("acc_prepare");
         let account_client = Arc::new(
-            (self.factory)(path)
+            (self.factory)(path, self.key_store.clone())
                 .await
                 .map_err(AccountTokenResult::Other)?,
         );
@@ -251,7 +275,7 @@ impl<
     ) -> anyhow::Result<String, AccountTokenResult> {
         let path = self.secure_base_path.join("acc_prepare");
         let account_client = Arc::new(
-            (self.factory)(path)
+            (self.factory)(path, self.key_store.clone())
                 .await
                 .map_err(AccountTokenResult::Other)?,
         );
@@ -265,8 +289,12 @@ impl<
         .await
     }
 
-    async fn read_accountless_keys(fs: &Fs) -> anyhow::Result<AccountlessKeysAndValidy> {
-        fs.read(ACCOUNTLESS_KEYS_FILE.as_ref())
+    async fn read_accountless_keys(
+        fs: &Fs,
+        key_store: &dyn KeyStore,
+    ) -> anyhow::Result<AccountlessKeysAndValidy> {
+        key_store
+            .read(fs, ACCOUNTLESS_KEYS_FILE)
             .await
             .map_err(|err| anyhow!(err))
             .and_then(|file| {
@@ -287,9 +315,11 @@ impl<
     }
 
     async fn take_accountless_keys(&self) -> anyhow::Result<AccountDataForClient> {
-        let account_data = Self::read_accountless_keys(&self.fs).await?;
+        let account_data = Self::read_accountless_keys(&self.fs, self.key_store.as_ref()).await?;
 
-        self.fs.remove(ACCOUNTLESS_KEYS_FILE.as_ref()).await?;
+        self.key_store
+            .remove(&self.fs, ACCOUNTLESS_KEYS_FILE)
+            .await?;
 
         Ok(account_data.account_data)
     }
@@ -301,7 +331,7 @@ impl<
         credential_auth_token_hex: String,
     ) -> anyhow::Result<String> {
         let path = self.secure_base_path.join("acc_prepare");
-        let account_client = Arc::new((self.factory)(path).await?);
+        let account_client = Arc::new((self.factory)(path, self.key_store.clone()).await?);
 
         // first try to "upgrade" the accountless keys to a real account.
         let (account_id, login_data_writer) = if let Ok(account_data) =
@@ -320,22 +350,27 @@ impl<
 
         let profile_name = Self::account_id_to_path(account_id);
         let path = self.secure_base_path.join(&profile_name);
-        let account_client = Arc::new((self.factory)(path).await?);
+        let account_client = Arc::new((self.factory)(path, self.key_store.clone()).await?);
 
         login_data_writer.write(&*account_client).await?;
 
-        let profile = ActiveProfile {
-            client: account_client,
-            cur_cert: Default::default(),
-            profile_data: ProfileData {
-                name: display_name.to_string(),
-                user: Default::default(),
-            },
-        };
-
         let profiles_state;
         {
             let mut profiles = self.profiles.lock();
+            // an imported profile keeps its data
+            let profile_data = profiles
+                .imported
+                .remove(&profile_name)
+                .map(|imported| imported.profile_data)
+                .unwrap_or_else(|| ProfileData {
+                    name: display_name.to_string(),
+                    user: Default::default(),
+                });
+            let profile = ActiveProfile {
+                client: account_client,
+                cur_cert: Default::default(),
+                profile_data,
+            };
             profiles.profiles.insert(profile_name.to_string(), profile);
             profiles.cur_profile = profile_name.to_string();
             profiles_state = Self::to_profile_states(&profiles);
@@ -409,21 +444,21 @@ impl<
     /// If no account was found, fall back to key-pair that
     /// is not account based, but could be upgraded
     async fn account_less_cert_and_key_pair(
-        fs_or_account_data: Either<&Fs, AccountDataForClient>,
+        fs_or_account_data: Either<(&Fs, &dyn KeyStore), AccountDataForClient>,
         err: Option<anyhow::Error>,
     ) -> (AccountDataForClient, Certificate, Option<anyhow::Error>) {
         match fs_or_account_data {
-            Either::Left(fs) => {
+            Either::Left((fs, key_store)) => {
                 let (account_data, cert) = if let Ok((account_data, cert)) =
-                    Self::read_accountless_keys(fs)
-                        .await
-                        .and_then(|accountless_keys_and_validy| {
+                    Self::read_accountless_keys(fs, key_store).await.and_then(
+                        |accountless_keys_and_validy| {
                             generate_self_signed(
                                 &accountless_keys_and_validy.account_data.private_key,
                             )
                             .map_err(|err| anyhow!(err))
                             .map(|cert| (accountless_keys_and_validy.account_data, cert))
-                        }) {
+                        },
+                    ) {
                     (account_data, cert)
                 } else {
                     let (private_key, public_key) = key_pair();
@@ -442,9 +477,7 @@ impl<
 
                     // ignore errors, can't recover anyway
                     if let Ok(file) = serde_json::to_vec(&accountless_keys_and_cert) {
-                        let _ = fs
-                            .write("".as_ref(), ACCOUNTLESS_KEYS_FILE.as_ref(), file)
-                            .await;
+                        let _ = key_store.write(fs, ACCOUNTLESS_KEYS_FILE, file).await;
                     }
 
                     (accountless_keys_and_cert.account_data, cert)
@@ -550,6 +583,7 @@ impl<
 
             let should_wait = if let Some((notifier, should_wait)) = try_fetch {
                 let fs = self.fs.clone();
+                let key_store = self.key_store.clone();
                 let profiles = self.profiles.clone();
                 let cur_cert = cur_cert.clone();
                 let res = tokio::spawn(async move {
@@ -571,7 +605,7 @@ impl<
                                 (sign_data.session_key_pair, cert, None)
                             } else {
                                 Self::account_less_cert_and_key_pair(
-                                    Either::Left(&fs),
+                                    Either::Left((&fs, key_store.as_ref())),
                                     Some(anyhow!(
                                         "account server did not return a valid certificate, \
                                         please contact a developer."
@@ -589,7 +623,7 @@ impl<
                                     // try to remove that profile
                                     let _ = Self::remove_profile(profiles, &fs, &cur_profile).await;
                                     Self::account_less_cert_and_key_pair(
-                                        Either::Left(&fs),
+                                        Either::Left((&fs, key_store.as_ref())),
                                         Some(err.into()),
                                     )
                                     .await
@@ -597,6 +631,9 @@ impl<
                                 SignResult::HttpLikeError {
                                     ref account_data, ..
                                 }
+                                | SignResult::Banned {
+                                    ref account_data, ..
+                                }
                                 | SignResult::Other {
                                     ref account_data, ..
                                 } => {
@@ -634,7 +671,7 @@ impl<
                 | ProfileCert::CertAndKeysAndFetch { cert_and_keys, .. }) = cur_cert.lock().clone()
                 else {
                     return Self::account_less_cert_and_key_pair(
-                        Either::Left(&self.fs),
+                        Either::Left((&self.fs, self.key_store.as_ref())),
                         Some(anyhow!("no cert or key found.")),
                     )
                     .await;
@@ -644,7 +681,11 @@ impl<
                 (key_pair, cert, None)
             }
         } else {
-            Self::account_less_cert_and_key_pair(Either::Left(&self.fs), None).await
+            Self::account_less_cert_and_key_pair(
+                Either::Left((&self.fs, self.key_store.as_ref())),
+                None,
+            )
+            .await
         }
     }
 
@@ -793,6 +834,74 @@ impl<
         Ok(ddnet_account_client::account_info::account_info(&*account_client).await?)
     }
 
+    /// Tries to fetch all sessions of the account for the given profile
+    pub async fn sessions(&self, profile_name: &str) -> anyhow::Result<SessionsResponse> {
+        let mut account_client = None;
+        {
+            let profiles = self.profiles.lock();
+            if let Some(profile) = profiles.profiles.get(profile_name) {
+                account_client = Some(profile.client.clone());
+            }
+            drop(profiles);
+        }
+        let Some(account_client) = account_client else {
+            return Err(anyhow::anyhow!(
+                "Profile with name {} not found",
+                profile_name
+            ));
+        };
+        Ok(ddnet_account_client::sessions::sessions(&*account_client).await?)
+    }
+
+    /// Tries to fetch the recent events of the account for the given profile
+    pub async fn account_events(
+        &self,
+        profile_name: &str,
+    ) -> anyhow::Result<AccountEventsResponse> {
+        let mut account_client = None;
+        {
+            let profiles = self.profiles.lock();
+            if let Some(profile) = profiles.profiles.get(profile_name) {
+                account_client = Some(profile.client.clone());
+            }
+            drop(profiles);
+        }
+        let Some(account_client) = account_client else {
+            return Err(anyhow::anyhow!(
+                "Profile with name {} not found",
+                profile_name
+            ));
+        };
+        Ok(ddnet_account_client::account_events::account_events(&*account_client).await?)
+    }
+
+    /// Revokes the session with the given fingerprint
+    /// of the account for the given profile.
+    pub async fn revoke_session(
+        &self,
+        fingerprint: SessionFingerprint,
+        profile_name: &str,
+    ) -> anyhow::Result<()> {
+        let mut account_client = None;
+        {
+            let profiles = self.profiles.lock();
+            if let Some(profile) = profiles.profiles.get(profile_name) {
+                account_client = Some(profile.client.clone());
+            }
+            drop(profiles);
+        }
+        let Some(account_client) = account_client else {
+            return Err(anyhow::anyhow!(
+                "Profile with name {} not found",
+                profile_name
+            ));
+        };
+        Ok(
+            ddnet_account_client::session_revoke::revoke_session(fingerprint, &*account_client)
+                .await?,
+        )
+    }
+
     /// Currently loaded profiles
     pub fn profiles(&self) -> (HashMap<String, ProfileData>, String) {
         let profiles = self.profiles.lock();
@@ -841,6 +950,130 @@ impl<
         let _ = profiles_state.save(&self.fs).await;
     }
 
+    /// Exports all profiles into an archive encrypted with the passphrase,
+    /// which can be imported on another machine using [`Self::import_profiles`].
+    ///
+    /// The archive contains the profile data & the credentials of the accounts,
+    /// but no session keys. The credentials are fetched from the account server,
+    /// if that fails the profile is still exported without them & the error is
+    /// part of the result.
+    pub async fn export_profiles(&self, passphrase: String) -> anyhow::Result<ProfilesExport> {
+        let (clients, mut archive) = {
+            let profiles = self.profiles.lock();
+            let clients: Vec<_> = profiles
+                .profiles
+                .iter()
+                .map(|(profile_name, profile)| {
+                    (
+                        profile_name.clone(),
+                        profile.client.clone(),
+                        profile.profile_data.clone(),
+                    )
+                })
+                .collect();
+            // not yet logged in imports are exported again
+            let archive = ProfilesArchive {
+                profiles: profiles.imported.clone(),
+                cur_profile: profiles.cur_profile.clone(),
+            };
+            (clients, archive)
+        };
+
+        let account_infos = futures::future::join_all(clients.iter().map(|(_, client, _)| {
+            ddnet_account_client::account_info::account_info(client.as_ref())
+        }))
+        .await;
+        let mut failed = HashMap::default();
+        for ((profile_name, _, profile_data), account_info) in
+            clients.into_iter().zip(account_infos)
+        {
+            let credentials = match account_info {
+                Ok(account_info) => account_info.credentials,
+                Err(err) => {
+                    failed.insert(profile_name.clone(), err);
+                    Vec::new()
+                }
+            };
+            archive.profiles.insert(
+                profile_name,
+                ExportedProfile {
+                    profile_data,
+                    credentials,
+                },
+            );
+        }
+
+        Ok(ProfilesExport {
+            archive: archive.encrypt(passphrase).await?,
+            failed,
+        })
+    }
+
+    /// Imports the profiles of an archive created by [`Self::export_profiles`].
+    ///
+    /// The profiles have to be logged in again through the usual email or steam
+    /// login, which then restores their profile data.
+    /// Profiles that are already logged in are skipped.
+    ///
+    /// Returns all imported profiles that still require a login,
+    /// together with the logins that restore them.
+    pub async fn import_profiles(
+        &self,
+        archive: &[u8],
+        passphrase: String,
+    ) -> anyhow::Result<Vec<PendingImport>> {
+        let archive = ProfilesArchive::decrypt(archive, passphrase).await?;
+
+        let profiles_state;
+        let imported;
+        {
+            let mut profiles = self.profiles.lock();
+            for (profile_name, profile) in archive.profiles {
+                if !profiles.profiles.contains_key(&profile_name) {
+                    profiles.imported.insert(profile_name, profile);
+                }
+            }
+            imported = Self::pending_imports(&profiles.imported);
+            profiles_state = Self::to_profile_states(&profiles);
+            drop(profiles);
+        }
+
+        profiles_state.save(&self.fs).await?;
+
+        Ok(imported)
+    }
+
+    fn pending_imports(imported: &HashMap<String, ExportedProfile>) -> Vec<PendingImport> {
+        let mut pending: Vec<_> = imported
+            .iter()
+            .map(|(profile_name, profile)| PendingImport {
+                profile_name: profile_name.clone(),
+                logins: profile.logins(),
+                profile: profile.clone(),
+            })
+            .collect();
+        pending.sort_by(|a, b| a.profile_name.cmp(&b.profile_name));
+        pending
+    }
+
+    /// Imported profiles that still require a login, see [`Self::import_profiles`].
+    pub fn imported_profiles(&self) -> Vec<PendingImport> {
+        Self::pending_imports(&self.profiles.lock().imported)
+    }
+
+    /// Forget an imported profile that should not be logged in.
+    pub async fn discard_imported_profile(&self, profile_name: &str) {
+        let profiles_state;
+        {
+            let mut profiles = self.profiles.lock();
+            profiles.imported.remove(profile_name);
+            profiles_state = Self::to_profile_states(&profiles);
+            drop(profiles);
+        }
+
+        let _ = profiles_state.save(&self.fs).await;
+    }
+
     /// Sets arbitrary data specified by the implementation, saved inside the profiles struct
     pub async fn set_profile_user_data(
         &self,
@@ -867,6 +1100,7 @@ pub struct ProfilesLoading<
     F: Deref<
             Target = dyn Fn(
                 PathBuf,
+                Arc<dyn KeyStore>,
             )
                 -> Pin<Box<dyn Future<Output = anyhow::Result<C>> + Sync + Send>>,
         > + Debug
@@ -877,6 +1111,7 @@ pub struct ProfilesLoading<
     pub factory: Arc<F>,
     pub secure_base_path: PathBuf,
     fs: Fs,
+    key_store: Arc<dyn KeyStore>,
 }
 
 impl<
@@ -884,6 +1119,7 @@ impl<
         F: Deref<
                 Target = dyn Fn(
                     PathBuf,
+                    Arc<dyn KeyStore>,
                 )
                     -> Pin<Box<dyn Future<Output = anyhow::Result<C>> + Sync + Send>>,
             > + Debug
@@ -891,15 +1127,29 @@ impl<
             + Send,
     > ProfilesLoading<C, F>
 {
-    pub async fn new(secure_base_path: PathBuf, factory: Arc<F>) -> anyhow::Result<Self> {
+    /// The key store is passed to the factory & is also used for the
+    /// keys of the accountless profile, see [`crate::key_store`].
+    pub async fn new(
+        secure_base_path: PathBuf,
+        factory: Arc<F>,
+        key_store: Arc<dyn KeyStore>,
+    ) -> anyhow::Result<Self> {
         let fs = Fs::new(secure_base_path.clone()).await?;
-        let profiles_state = ProfilesState::load_or_default(&fs).await;
+        let mut profiles_state = ProfilesState::load_or_default(&fs).await;
+        if !profiles_state.keys_migrated {
+            Self::migrate_plain_keys(&fs, &secure_base_path, &profiles_state, key_store.as_ref())
+                .await;
+            profiles_state.keys_migrated = true;
+            profiles_state.save(&fs).await?;
+        }
         let mut profiles: HashMap<String, ActiveProfile<C>> = Default::default();
         for (profile_key, profile) in profiles_state.profiles {
             profiles.insert(
                 profile_key.clone(),
                 ActiveProfile {
-                    client: Arc::new(factory(secure_base_path.join(profile_key)).await?),
+                    client: Arc::new(
+                        factory(secure_base_path.join(profile_key), key_store.clone()).await?,
+                    ),
                     cur_cert: Default::default(),
                     profile_data: profile,
                 },
@@ -909,10 +1159,28 @@ impl<
             profiles: parking_lot::Mutex::new(ActiveProfiles {
                 profiles,
                 cur_profile: profiles_state.cur_profile,
+                imported: profiles_state.imported,
             }),
             factory,
             fs,
+            key_store,
             secure_base_path,
         })
     }
+
+    /// Older clients wrote the keys unencrypted, they are moved into the key store once.
+    /// Keys that can't be migrated require a new login.
+    async fn migrate_plain_keys(
+        fs: &Fs,
+        secure_base_path: &Path,
+        profiles_state: &ProfilesState,
+        key_store: &dyn KeyStore,
+    ) {
+        let _ = key_store.migrate_plain(fs, ACCOUNTLESS_KEYS_FILE).await;
+        for profile_key in profiles_state.profiles.keys() {
+            if let Ok(fs) = Fs::new(secure_base_path.join(profile_key)).await {
+                let _ = key_store.migrate_plain(&fs, SESSION_KEY_FILE).await;
+            }
+        }
+    }
 }
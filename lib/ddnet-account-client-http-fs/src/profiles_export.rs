@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use ddnet_account_client::account_info::AccountInfoResult;
+use ddnet_accounts_shared::account_server::account_info::CredentialType;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    key_store::{EncryptedFileKeyStore, KeyStoreSecret},
+    profiles::ProfileData,
+};
+
+/// Authenticated together with the archive,
+/// so no other encrypted file can be imported as archive.
+const ARCHIVE_NAME: &str = "ddnet-accounts-profiles-export";
+
+/// A profile inside an export archive.
+///
+/// Does not contain the session keys, the player has to log in again
+/// on the new machine. The credentials help finding the right login flow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedProfile {
+    pub profile_data: ProfileData,
+    /// The credentials linked to the account,
+    /// as far as they could be fetched on export.
+    /// The email is only partially readable.
+    pub credentials: Vec<CredentialType>,
+}
+
+impl ExportedProfile {
+    /// The logins that restore this profile, see [`PendingImport`].
+    pub fn logins(&self) -> Vec<ImportLogin> {
+        self.credentials
+            .iter()
+            .map(|credential| match credential {
+                CredentialType::Email(partial_email) => ImportLogin::Email {
+                    partial_email: partial_email.clone(),
+                },
+                CredentialType::Steam(steamid64) => ImportLogin::Steam {
+                    steamid64: *steamid64,
+                },
+            })
+            .collect()
+    }
+}
+
+/// A login that restores an imported profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportLogin {
+    /// Request a login token for the email, e.g. with
+    /// [`crate::profiles::Profiles::credential_auth_email_token`]
+    /// & log in with [`crate::profiles::Profiles::login_email`].
+    /// The email is only partially readable, the player has to enter it.
+    Email { partial_email: String },
+    /// Request a login token with a steam ticket of this steam account, e.g. with
+    /// [`crate::profiles::Profiles::credential_auth_steam_token`]
+    /// & log in with [`crate::profiles::Profiles::login_steam`].
+    Steam { steamid64: i64 },
+}
+
+/// An imported profile that still requires a login,
+/// see [`crate::profiles::Profiles::import_profiles`].
+#[derive(Debug, Clone)]
+pub struct PendingImport {
+    pub profile_name: String,
+    pub profile: ExportedProfile,
+    /// Any of these logins restores the profile.
+    /// Empty if the credentials were unknown on export,
+    /// then only the player knows how to log in.
+    pub logins: Vec<ImportLogin>,
+}
+
+/// The result of [`crate::profiles::Profiles::export_profiles`].
+#[derive(Debug)]
+pub struct ProfilesExport {
+    /// The encrypted archive.
+    pub archive: Vec<u8>,
+    /// The profiles whose credentials could not be fetched from the account server,
+    /// they are exported without credentials.
+    pub failed: HashMap<String, AccountInfoResult>,
+}
+
+/// The content of an export archive, see [`crate::profiles::Profiles::export_profiles`].
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProfilesArchive {
+    pub profiles: HashMap<String, ExportedProfile>,
+    pub cur_profile: String,
+}
+
+impl ProfilesArchive {
+    /// Serializes & encrypts the archive with the passphrase.
+    pub async fn encrypt(&self, passphrase: String) -> anyhow::Result<Vec<u8>> {
+        let file = serde_json::to_vec(self)?;
+        Ok(
+            EncryptedFileKeyStore::new(KeyStoreSecret::Passphrase(passphrase))
+                .encrypt(ARCHIVE_NAME, file)
+                .await?,
+        )
+    }
+
+    /// Decrypts & deserializes an archive created by [`Self::encrypt`].
+    pub async fn decrypt(archive: &[u8], passphrase: String) -> anyhow::Result<Self> {
+        let file = EncryptedFileKeyStore::new(KeyStoreSecret::Passphrase(passphrase))
+            .decrypt(ARCHIVE_NAME, archive)
+            .await
+            .map_err(|_| anyhow!("not a profile archive or the passphrase is wrong"))?;
+        Ok(serde_json::from_slice(&file)?)
+    }
+}
@@ -5,7 +5,12 @@ use ddnet_account_client::{
     errors::{FsLikeError, HttpLikeError},
     interface::Io,
 };
-use ddnet_account_client_http_fs::{client::ClientHttpTokioFs, fs::Fs, http::Http};
+use ddnet_account_client_http_fs::{
+    client::ClientHttpTokioFs,
+    fs::Fs,
+    http::Http,
+    key_store::{default_key_store, KeyStore},
+};
 use reqwest::header::{HeaderValue, CONTENT_TYPE};
 use url::Url;
 
@@ -77,6 +82,15 @@ pub struct ClientReqwestTokioFs {
 
 impl ClientReqwestTokioFs {
     pub async fn new(base_urls: Vec<Url>, secure_path: &Path) -> anyhow::Result<Self, FsLikeError> {
+        Self::new_with_key_store(base_urls, secure_path, default_key_store()).await
+    }
+
+    /// Like [`Self::new`], but the session key pair is stored in the given key store.
+    pub async fn new_with_key_store(
+        base_urls: Vec<Url>,
+        secure_path: &Path,
+        key_store: Arc<dyn KeyStore>,
+    ) -> anyhow::Result<Self, FsLikeError> {
         Ok(Self {
             client: Arc::new(ClientHttpTokioFs {
                 http: base_urls
@@ -88,6 +102,7 @@ impl ClientReqwestTokioFs {
                     .collect(),
                 cur_http: Default::default(),
                 fs: Fs::new(secure_path.into()).await?,
+                key_store,
             }),
         })
     }
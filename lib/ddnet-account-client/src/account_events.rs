@@ -0,0 +1,69 @@
+use ddnet_accounts_shared::{
+    account_server::account_events::AccountEventsResponse,
+    client::{account_events::prepare_account_events_request, machine_id::machine_uid},
+};
+use thiserror::Error;
+
+use crate::{
+    errors::{FsLikeError, HttpLikeError},
+    interface::Io,
+    safe_interface::{IoSafe, SafeIo},
+};
+
+/// The result of an [`account_events`] request.
+#[derive(Error, Debug)]
+pub enum AccountEventsResult {
+    /// Session was invalid, must login again.
+    #[error("The session was not valid anymore.")]
+    SessionWasInvalid,
+    /// A file system like error occurred.
+    /// This usually means the user was not yet logged in.
+    #[error("{0}")]
+    FsLikeError(FsLikeError),
+    /// A http like error occurred.
+    #[error("{0}")]
+    HttpLikeError(HttpLikeError),
+    /// Errors that are not handled explicitly.
+    #[error("Fetching account events failed: {0}")]
+    Other(anyhow::Error),
+}
+
+impl From<HttpLikeError> for AccountEventsResult {
+    fn from(value: HttpLikeError) -> Self {
+        Self::HttpLikeError(value)
+    }
+}
+
+impl From<FsLikeError> for AccountEventsResult {
+    fn from(value: FsLikeError) -> Self {
+        Self::FsLikeError(value)
+    }
+}
+
+/// Lists the recent security relevant events (logins, credential changes etc.)
+/// of the account the current session belongs to.
+///
+/// # Errors
+///
+/// If an error occurs this usually means that the session is not valid anymore.
+pub async fn account_events(
+    io: &dyn Io,
+) -> anyhow::Result<AccountEventsResponse, AccountEventsResult> {
+    account_events_impl(io.into()).await
+}
+
+async fn account_events_impl(
+    io: IoSafe<'_>,
+) -> anyhow::Result<AccountEventsResponse, AccountEventsResult> {
+    // read session's key-pair
+    let key_pair = io.read_serialized_session_key_pair().await?;
+
+    let hashed_hw_id = machine_uid().map_err(AccountEventsResult::Other)?;
+
+    // do the account events request using the above private key
+    let msg =
+        prepare_account_events_request(hashed_hw_id, &key_pair.private_key, key_pair.public_key);
+    io.request_account_events(msg)
+        .await?
+        .map_err(|err| AccountEventsResult::Other(err.into()))
+}
@@ -1,5 +1,8 @@
 use ddnet_accounts_shared::{
-    account_server::account_info::AccountInfoResponse,
+    account_server::{
+        account_info::{AccountInfoError, AccountInfoResponse},
+        errors::AccountServerRequestError,
+    },
     client::{account_info::prepare_account_info_request, machine_id::machine_uid},
 };
 use thiserror::Error;
@@ -23,6 +26,9 @@ pub enum AccountInfoResult {
     /// A http like error occurred.
     #[error("{0}")]
     HttpLikeError(HttpLikeError),
+    /// The account server responded with an error.
+    #[error("{0}")]
+    AccountServerRequstError(AccountServerRequestError<AccountInfoError>),
     /// Errors that are not handled explicitly.
     #[error("Fetching account info failed: {0}")]
     Other(anyhow::Error),
@@ -62,5 +68,10 @@ async fn account_info_impl(
         prepare_account_info_request(hashed_hw_id, &key_pair.private_key, key_pair.public_key);
     io.request_account_info(msg)
         .await?
-        .map_err(|err| AccountInfoResult::Other(err.into()))
+        .map_err(|err| match err {
+            AccountServerRequestError::LogicError(AccountInfoError::SessionInvalid) => {
+                AccountInfoResult::SessionWasInvalid
+            }
+            err => AccountInfoResult::AccountServerRequstError(err),
+        })
 }
@@ -66,13 +66,26 @@ pub async fn account_token_email(
     secret_key_hex: Option<String>,
     io: &dyn Io,
 ) -> anyhow::Result<(), AccountTokenResult> {
-    account_token_email_impl(email, op, secret_key_hex, io.into()).await
+    account_token_email_impl(email, op, secret_key_hex, None, io.into()).await
+}
+
+/// Same as [`account_token_email`], but requests the email in the given language
+/// (e.g. `de` or `pt-br`), if the account server has templates for it.
+pub async fn account_token_email_with_locale(
+    email: email_address::EmailAddress,
+    op: AccountTokenOperation,
+    secret_key_hex: Option<String>,
+    locale: String,
+    io: &dyn Io,
+) -> anyhow::Result<(), AccountTokenResult> {
+    account_token_email_impl(email, op, secret_key_hex, Some(locale), io.into()).await
 }
 
 async fn account_token_email_impl(
     email: email_address::EmailAddress,
     op: AccountTokenOperation,
     secret_key_hex: Option<String>,
+    locale: Option<String>,
     io: IoSafe<'_>,
 ) -> anyhow::Result<(), AccountTokenResult> {
     if secret_key_hex.is_some() {
@@ -80,6 +93,7 @@ async fn account_token_email_impl(
             email,
             secret_key: get_secret_key(secret_key_hex)?,
             op,
+            locale,
         })
         .await
     } else {
@@ -87,6 +101,7 @@ async fn account_token_email_impl(
             email,
             secret_key: get_secret_key(secret_key_hex)?,
             op,
+            locale,
         })
         .await
     }?
@@ -69,13 +69,26 @@ pub async fn credential_auth_token_email(
     secret_key_hex: Option<String>,
     io: &dyn Io,
 ) -> anyhow::Result<(), CredentialAuthTokenResult> {
-    credential_auth_token_email_impl(email, op, secret_key_hex, io.into()).await
+    credential_auth_token_email_impl(email, op, secret_key_hex, None, io.into()).await
+}
+
+/// Same as [`credential_auth_token_email`], but requests the email in the given language
+/// (e.g. `de` or `pt-br`), if the account server has templates for it.
+pub async fn credential_auth_token_email_with_locale(
+    email: email_address::EmailAddress,
+    op: CredentialAuthTokenOperation,
+    secret_key_hex: Option<String>,
+    locale: String,
+    io: &dyn Io,
+) -> anyhow::Result<(), CredentialAuthTokenResult> {
+    credential_auth_token_email_impl(email, op, secret_key_hex, Some(locale), io.into()).await
 }
 
 async fn credential_auth_token_email_impl(
     email: email_address::EmailAddress,
     op: CredentialAuthTokenOperation,
     secret_key_hex: Option<String>,
+    locale: Option<String>,
     io: IoSafe<'_>,
 ) -> anyhow::Result<(), CredentialAuthTokenResult> {
     let secret_key = get_secret_key(secret_key_hex)?;
@@ -84,6 +97,7 @@ async fn credential_auth_token_email_impl(
             email,
             secret_key,
             op,
+            locale,
         })
         .await?
         .map_err(CredentialAuthTokenResult::AccountServerRequstError)?;
@@ -92,6 +106,7 @@ async fn credential_auth_token_email_impl(
             email,
             secret_key,
             op,
+            locale,
         })
         .await?
         .map_err(CredentialAuthTokenResult::AccountServerRequstError)?;
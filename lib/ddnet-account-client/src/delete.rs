@@ -1,4 +1,7 @@
-use ddnet_accounts_shared::client::delete;
+use ddnet_accounts_shared::{
+    account_server::{delete::DeleteError, errors::AccountServerRequestError},
+    client::delete,
+};
 use thiserror::Error;
 
 use crate::{
@@ -16,6 +19,9 @@ pub enum DeleteResult {
     /// A fs like error occurred.
     #[error("{0}")]
     FsLikeError(FsLikeError),
+    /// The account server responded with an error.
+    #[error("{0}")]
+    AccountServerRequstError(AccountServerRequestError<DeleteError>),
     /// Errors that are not handled explicitly.
     #[error("Delete failed: {0}")]
     Other(anyhow::Error),
@@ -46,7 +52,7 @@ async fn delete_impl(
 
     io.request_delete_account(delete_req)
         .await?
-        .map_err(|err| DeleteResult::Other(err.into()))?;
+        .map_err(DeleteResult::AccountServerRequstError)?;
     // this is generally allowed to fail
     let _ = io.remove_serialized_session_key_pair().await;
 
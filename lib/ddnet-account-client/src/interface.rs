@@ -95,6 +95,17 @@ pub trait Io: Sync + Send {
     /// Requests the account info of the account.
     /// Sends & receives it as arbitrary data.
     async fn request_account_info(&self, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpLikeError>;
+    /// Requests the list of all sessions of the account.
+    /// Sends & receives it as arbitrary data.
+    async fn request_sessions(&self, data: Vec<u8>) -> anyhow::Result<Vec<u8>, HttpLikeError>;
+    /// Requests to revoke a single session of the account.
+    /// Sends & receives it as arbitrary data.
+    async fn request_session_revoke(&self, data: Vec<u8>)
+        -> anyhow::Result<Vec<u8>, HttpLikeError>;
+    /// Requests the recent events of the account.
+    /// Sends & receives it as arbitrary data.
+    async fn request_account_events(&self, data: Vec<u8>)
+        -> anyhow::Result<Vec<u8>, HttpLikeError>;
     /// Downloads the latest certificates of the account server.
     /// Sends & receives it as arbitrary data.
     async fn download_account_server_certificates(&self) -> anyhow::Result<Vec<u8>, HttpLikeError>;
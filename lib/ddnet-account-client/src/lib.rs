@@ -13,6 +13,8 @@
 
 pub(crate) mod safe_interface;
 
+/// Requests the recent events of the account.
+pub mod account_events;
 /// Requests the account info of the account.
 pub mod account_info;
 /// Requests an account token email based.
@@ -38,6 +40,10 @@ pub mod login;
 pub mod logout;
 /// Request to log out all sessions of a user.
 pub mod logout_all;
+/// Requests to revoke a single session of the account.
+pub mod session_revoke;
+/// Requests the list of all sessions of the account.
+pub mod sessions;
 /// Sign an already existing session key-pair
 /// with a certificate on the account server.
 pub mod sign;
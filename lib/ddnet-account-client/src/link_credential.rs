@@ -1,5 +1,5 @@
 use ddnet_accounts_shared::{
-    account_server::errors::{AccountServerRequestError, Empty},
+    account_server::{errors::AccountServerRequestError, link_credential::LinkCredentialError},
     client::link_credential::{self},
 };
 
@@ -22,7 +22,7 @@ pub enum LinkCredentialResult {
     FsLikeError(FsLikeError),
     /// The account server responded with an error.
     #[error("{0}")]
-    AccountServerRequstError(AccountServerRequestError<Empty>),
+    AccountServerRequstError(AccountServerRequestError<LinkCredentialError>),
     /// Errors that are not handled explicitly.
     #[error("Linking credential failed: {0}")]
     Other(anyhow::Error),
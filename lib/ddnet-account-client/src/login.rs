@@ -107,15 +107,29 @@ pub async fn login(
     credential_auth_token_hex: String,
     io: &dyn Io,
 ) -> anyhow::Result<(AccountId, LoginWriter), LoginResult> {
-    login_impl(credential_auth_token_hex, io.into()).await
+    login_impl(credential_auth_token_hex, None, io.into()).await
+}
+
+/// Create a new session (or account if not existed) on the account server.
+///
+/// The device label is shown when listing the sessions of the account,
+/// see [`crate::sessions::sessions`].
+pub async fn login_with_device_label(
+    credential_auth_token_hex: String,
+    device_label: String,
+    io: &dyn Io,
+) -> anyhow::Result<(AccountId, LoginWriter), LoginResult> {
+    login_impl(credential_auth_token_hex, Some(device_label), io.into()).await
 }
 
 async fn login_impl(
     credential_auth_token_hex: String,
+    device_label: Option<String>,
     io: IoSafe<'_>,
 ) -> anyhow::Result<(AccountId, LoginWriter), LoginResult> {
-    let (login_req, login_data) =
+    let (mut login_req, login_data) =
         login::login(credential_auth_token_hex).map_err(LoginResult::Other)?;
+    login_req.device_label = device_label;
 
     login_inner_impl(login_req, login_data, io).await
 }
@@ -1,4 +1,7 @@
-use ddnet_accounts_shared::client::{logout_all, machine_id::machine_uid};
+use ddnet_accounts_shared::{
+    account_server::{errors::AccountServerRequestError, logout_all::LogoutAllError},
+    client::{logout_all, machine_id::machine_uid},
+};
 use thiserror::Error;
 
 use crate::{
@@ -16,6 +19,9 @@ pub enum LogoutAllResult {
     /// A fs like error occurred.
     #[error("{0}")]
     FsLikeError(FsLikeError),
+    /// The account server responded with an error.
+    #[error("{0}")]
+    AccountServerRequstError(AccountServerRequestError<LogoutAllError>),
     /// Errors that are not handled explicitly.
     #[error("Delete failed: {0}")]
     Other(anyhow::Error),
@@ -61,7 +67,7 @@ async fn logout_all_impl(
 
     io.request_logout_all(delete_req)
         .await?
-        .map_err(|err| LogoutAllResult::Other(err.into()))?;
+        .map_err(LogoutAllResult::AccountServerRequstError)?;
 
     Ok(())
 }
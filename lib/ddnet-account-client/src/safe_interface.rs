@@ -2,12 +2,23 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use ddnet_accounts_shared::{
     account_server::{
-        account_info::AccountInfoResponse, account_token::AccountTokenError,
-        credential_auth_token::CredentialAuthTokenError, errors::Empty, login::LoginError,
-        result::AccountServerReqResult, sign::SignResponseSuccess,
+        account_events::AccountEventsResponse,
+        account_info::{AccountInfoError, AccountInfoResponse},
+        account_token::AccountTokenError,
+        credential_auth_token::CredentialAuthTokenError,
+        delete::DeleteError,
+        errors::Empty,
+        link_credential::LinkCredentialError,
+        login::LoginError,
+        logout_all::LogoutAllError,
+        result::AccountServerReqResult,
+        sessions::{SessionRevokeError, SessionsResponse},
+        sign::{SignError, SignResponseSuccess},
+        unlink_credential::UnlinkCredentialError,
     },
     client::{
         account_data::AccountDataForClient,
+        account_events::AccountEventsRequest,
         account_info::AccountInfoRequest,
         account_token::{AccountTokenEmailRequest, AccountTokenSteamRequest},
         credential_auth_token::{CredentialAuthTokenEmailRequest, CredentialAuthTokenSteamRequest},
@@ -16,6 +27,7 @@ use ddnet_accounts_shared::{
         login::LoginRequest,
         logout::LogoutRequest,
         logout_all::LogoutAllRequest,
+        sessions::{SessionRevokeRequest, SessionsRequest},
         sign::SignRequest,
         unlink_credential::UnlinkCredentialRequest,
     },
@@ -58,7 +70,7 @@ pub trait SafeIo: Sync + Send {
     async fn request_sign(
         &self,
         data: SignRequest,
-    ) -> anyhow::Result<AccountServerReqResult<SignResponseSuccess, Empty>, HttpLikeError>;
+    ) -> anyhow::Result<AccountServerReqResult<SignResponseSuccess, SignError>, HttpLikeError>;
     async fn request_account_token_email(
         &self,
         data: AccountTokenEmailRequest,
@@ -78,23 +90,35 @@ pub trait SafeIo: Sync + Send {
     async fn request_logout_all(
         &self,
         data: LogoutAllRequest,
-    ) -> anyhow::Result<AccountServerReqResult<(), Empty>, HttpLikeError>;
+    ) -> anyhow::Result<AccountServerReqResult<(), LogoutAllError>, HttpLikeError>;
     async fn request_delete_account(
         &self,
         data: DeleteRequest,
-    ) -> anyhow::Result<AccountServerReqResult<(), Empty>, HttpLikeError>;
+    ) -> anyhow::Result<AccountServerReqResult<(), DeleteError>, HttpLikeError>;
     async fn request_link_credential(
         &self,
         data: LinkCredentialRequest,
-    ) -> anyhow::Result<AccountServerReqResult<(), Empty>, HttpLikeError>;
+    ) -> anyhow::Result<AccountServerReqResult<(), LinkCredentialError>, HttpLikeError>;
     async fn request_unlink_credential(
         &self,
         data: UnlinkCredentialRequest,
-    ) -> anyhow::Result<AccountServerReqResult<(), Empty>, HttpLikeError>;
+    ) -> anyhow::Result<AccountServerReqResult<(), UnlinkCredentialError>, HttpLikeError>;
     async fn request_account_info(
         &self,
         data: AccountInfoRequest,
-    ) -> anyhow::Result<AccountServerReqResult<AccountInfoResponse, Empty>, HttpLikeError>;
+    ) -> anyhow::Result<AccountServerReqResult<AccountInfoResponse, AccountInfoError>, HttpLikeError>;
+    async fn request_sessions(
+        &self,
+        data: SessionsRequest,
+    ) -> anyhow::Result<AccountServerReqResult<SessionsResponse, Empty>, HttpLikeError>;
+    async fn request_session_revoke(
+        &self,
+        data: SessionRevokeRequest,
+    ) -> anyhow::Result<AccountServerReqResult<(), SessionRevokeError>, HttpLikeError>;
+    async fn request_account_events(
+        &self,
+        data: AccountEventsRequest,
+    ) -> anyhow::Result<AccountServerReqResult<AccountEventsResponse, Empty>, HttpLikeError>;
     async fn download_account_server_certificates(
         &self,
     ) -> anyhow::Result<AccountServerReqResult<Vec<Vec<u8>>, Empty>, HttpLikeError>;
@@ -201,7 +225,7 @@ impl SafeIo for IoSafe<'_> {
     async fn request_sign(
         &self,
         data: SignRequest,
-    ) -> anyhow::Result<AccountServerReqResult<SignResponseSuccess, Empty>, HttpLikeError> {
+    ) -> anyhow::Result<AccountServerReqResult<SignResponseSuccess, SignError>, HttpLikeError> {
         let res = self
             .io
             .request_sign(serde_json::to_string(&data)?.into_bytes())
@@ -251,7 +275,7 @@ impl SafeIo for IoSafe<'_> {
     async fn request_logout_all(
         &self,
         data: LogoutAllRequest,
-    ) -> anyhow::Result<AccountServerReqResult<(), Empty>, HttpLikeError> {
+    ) -> anyhow::Result<AccountServerReqResult<(), LogoutAllError>, HttpLikeError> {
         let res = self
             .io
             .request_logout_all(serde_json::to_string(&data)?.into_bytes())
@@ -261,7 +285,7 @@ impl SafeIo for IoSafe<'_> {
     async fn request_delete_account(
         &self,
         data: DeleteRequest,
-    ) -> anyhow::Result<AccountServerReqResult<(), Empty>, HttpLikeError> {
+    ) -> anyhow::Result<AccountServerReqResult<(), DeleteError>, HttpLikeError> {
         let res = self
             .io
             .request_delete_account(serde_json::to_string(&data)?.into_bytes())
@@ -271,7 +295,7 @@ impl SafeIo for IoSafe<'_> {
     async fn request_link_credential(
         &self,
         data: LinkCredentialRequest,
-    ) -> anyhow::Result<AccountServerReqResult<(), Empty>, HttpLikeError> {
+    ) -> anyhow::Result<AccountServerReqResult<(), LinkCredentialError>, HttpLikeError> {
         let res = self
             .io
             .request_link_credential(serde_json::to_string(&data)?.into_bytes())
@@ -281,7 +305,7 @@ impl SafeIo for IoSafe<'_> {
     async fn request_unlink_credential(
         &self,
         data: UnlinkCredentialRequest,
-    ) -> anyhow::Result<AccountServerReqResult<(), Empty>, HttpLikeError> {
+    ) -> anyhow::Result<AccountServerReqResult<(), UnlinkCredentialError>, HttpLikeError> {
         let res = self
             .io
             .request_unlink_credential(serde_json::to_string(&data)?.into_bytes())
@@ -291,13 +315,44 @@ impl SafeIo for IoSafe<'_> {
     async fn request_account_info(
         &self,
         data: AccountInfoRequest,
-    ) -> anyhow::Result<AccountServerReqResult<AccountInfoResponse, Empty>, HttpLikeError> {
+    ) -> anyhow::Result<AccountServerReqResult<AccountInfoResponse, AccountInfoError>, HttpLikeError>
+    {
         let res = self
             .io
             .request_account_info(serde_json::to_string(&data)?.into_bytes())
             .await?;
         Self::des_from_vec(res)
     }
+    async fn request_sessions(
+        &self,
+        data: SessionsRequest,
+    ) -> anyhow::Result<AccountServerReqResult<SessionsResponse, Empty>, HttpLikeError> {
+        let res = self
+            .io
+            .request_sessions(serde_json::to_string(&data)?.into_bytes())
+            .await?;
+        Self::des_from_vec(res)
+    }
+    async fn request_session_revoke(
+        &self,
+        data: SessionRevokeRequest,
+    ) -> anyhow::Result<AccountServerReqResult<(), SessionRevokeError>, HttpLikeError> {
+        let res = self
+            .io
+            .request_session_revoke(serde_json::to_string(&data)?.into_bytes())
+            .await?;
+        Self::des_from_vec(res)
+    }
+    async fn request_account_events(
+        &self,
+        data: AccountEventsRequest,
+    ) -> anyhow::Result<AccountServerReqResult<AccountEventsResponse, Empty>, HttpLikeError> {
+        let res = self
+            .io
+            .request_account_events(serde_json::to_string(&data)?.into_bytes())
+            .await?;
+        Self::des_from_vec(res)
+    }
     async fn download_account_server_certificates(
         &self,
     ) -> anyhow::Result<AccountServerReqResult<Vec<Vec<u8>>, Empty>, HttpLikeError> {
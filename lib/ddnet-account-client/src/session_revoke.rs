@@ -0,0 +1,91 @@
+use ddnet_accounts_shared::{
+    account_server::{
+        errors::AccountServerRequestError,
+        sessions::{SessionFingerprint, SessionRevokeError},
+    },
+    client::{machine_id::machine_uid, sessions::prepare_session_revoke_request},
+};
+use thiserror::Error;
+
+use crate::{
+    errors::{FsLikeError, HttpLikeError},
+    interface::Io,
+    safe_interface::{IoSafe, SafeIo},
+};
+
+/// The result of a [`revoke_session`] request.
+#[derive(Error, Debug)]
+pub enum SessionRevokeResult {
+    /// Session was invalid, must login again.
+    #[error("The session was not valid anymore.")]
+    SessionWasInvalid,
+    /// No session of the account has the fingerprint,
+    /// e.g. because it was revoked already.
+    #[error("The session to revoke does not exist.")]
+    SessionNotFound,
+    /// A file system like error occurred.
+    /// This usually means the user was not yet logged in.
+    #[error("{0}")]
+    FsLikeError(FsLikeError),
+    /// A http like error occurred.
+    #[error("{0}")]
+    HttpLikeError(HttpLikeError),
+    /// Errors that are not handled explicitly.
+    #[error("Revoking the session failed: {0}")]
+    Other(anyhow::Error),
+}
+
+impl From<HttpLikeError> for SessionRevokeResult {
+    fn from(value: HttpLikeError) -> Self {
+        Self::HttpLikeError(value)
+    }
+}
+
+impl From<FsLikeError> for SessionRevokeResult {
+    fn from(value: FsLikeError) -> Self {
+        Self::FsLikeError(value)
+    }
+}
+
+/// Revokes the session with the given fingerprint,
+/// which must belong to the same account as the current session.
+/// The fingerprints can be obtained by [`crate::sessions::sessions`].
+///
+/// If the current session is revoked, the local session data is
+/// not removed, but it is not valid anymore.
+///
+/// # Errors
+///
+/// If an error occurs this usually means that the session is not valid anymore.
+pub async fn revoke_session(
+    fingerprint: SessionFingerprint,
+    io: &dyn Io,
+) -> anyhow::Result<(), SessionRevokeResult> {
+    revoke_session_impl(fingerprint, io.into()).await
+}
+
+async fn revoke_session_impl(
+    fingerprint: SessionFingerprint,
+    io: IoSafe<'_>,
+) -> anyhow::Result<(), SessionRevokeResult> {
+    // read session's key-pair
+    let key_pair = io.read_serialized_session_key_pair().await?;
+
+    let hashed_hw_id = machine_uid().map_err(SessionRevokeResult::Other)?;
+
+    // do the session revoke request using the above private key
+    let msg = prepare_session_revoke_request(
+        fingerprint,
+        hashed_hw_id,
+        &key_pair.private_key,
+        key_pair.public_key,
+    );
+    io.request_session_revoke(msg)
+        .await?
+        .map_err(|err| match err {
+            AccountServerRequestError::LogicError(SessionRevokeError::SessionNotFound) => {
+                SessionRevokeResult::SessionNotFound
+            }
+            err => SessionRevokeResult::Other(err.into()),
+        })
+}
@@ -0,0 +1,63 @@
+use ddnet_accounts_shared::{
+    account_server::sessions::SessionsResponse,
+    client::{machine_id::machine_uid, sessions::prepare_sessions_request},
+};
+use thiserror::Error;
+
+use crate::{
+    errors::{FsLikeError, HttpLikeError},
+    interface::Io,
+    safe_interface::{IoSafe, SafeIo},
+};
+
+/// The result of a [`sessions`] request.
+#[derive(Error, Debug)]
+pub enum SessionsResult {
+    /// Session was invalid, must login again.
+    #[error("The session was not valid anymore.")]
+    SessionWasInvalid,
+    /// A file system like error occurred.
+    /// This usually means the user was not yet logged in.
+    #[error("{0}")]
+    FsLikeError(FsLikeError),
+    /// A http like error occurred.
+    #[error("{0}")]
+    HttpLikeError(HttpLikeError),
+    /// Errors that are not handled explicitly.
+    #[error("Fetching sessions failed: {0}")]
+    Other(anyhow::Error),
+}
+
+impl From<HttpLikeError> for SessionsResult {
+    fn from(value: HttpLikeError) -> Self {
+        Self::HttpLikeError(value)
+    }
+}
+
+impl From<FsLikeError> for SessionsResult {
+    fn from(value: FsLikeError) -> Self {
+        Self::FsLikeError(value)
+    }
+}
+
+/// Lists all sessions of the account the current session belongs to.
+///
+/// # Errors
+///
+/// If an error occurs this usually means that the session is not valid anymore.
+pub async fn sessions(io: &dyn Io) -> anyhow::Result<SessionsResponse, SessionsResult> {
+    sessions_impl(io.into()).await
+}
+
+async fn sessions_impl(io: IoSafe<'_>) -> anyhow::Result<SessionsResponse, SessionsResult> {
+    // read session's key-pair
+    let key_pair = io.read_serialized_session_key_pair().await?;
+
+    let hashed_hw_id = machine_uid().map_err(SessionsResult::Other)?;
+
+    // do the sessions request using the above private key
+    let msg = prepare_sessions_request(hashed_hw_id, &key_pair.private_key, key_pair.public_key);
+    io.request_sessions(msg)
+        .await?
+        .map_err(|err| SessionsResult::Other(err.into()))
+}
@@ -1,6 +1,9 @@
 use anyhow::anyhow;
-use ddnet_accounts_shared::client::{
-    account_data::AccountDataForClient, machine_id::machine_uid, sign::prepare_sign_request,
+use ddnet_accounts_shared::{
+    account_server::{ban::AccountBan, errors::AccountServerRequestError, sign::SignError},
+    client::{
+        account_data::AccountDataForClient, machine_id::machine_uid, sign::prepare_sign_request,
+    },
 };
 use thiserror::Error;
 use x509_parser::oid_registry::asn1_rs::FromDer;
@@ -29,6 +32,15 @@ pub enum SignResult {
         /// The account data that the client could use as fallback
         account_data: AccountDataForClient,
     },
+    /// The account is banned, the ban contains the reason
+    /// and how long the ban lasts.
+    #[error("{ban}")]
+    Banned {
+        /// The ban of the account
+        ban: AccountBan,
+        /// The account data that the client could use as fallback
+        account_data: AccountDataForClient,
+    },
     /// Errors that are not handled explicitly.
     #[error("Signing failed: {err}")]
     Other {
@@ -90,9 +102,18 @@ async fn sign_impl(io: IoSafe<'_>) -> anyhow::Result<SignData, SignResult> {
             account_data: key_pair.clone(),
             err,
         })?
-        .map_err(|err| SignResult::Other {
-            err: err.into(),
-            account_data: key_pair.clone(),
+        .map_err(|err| match err {
+            AccountServerRequestError::LogicError(SignError::SessionInvalid) => {
+                SignResult::SessionWasInvalid
+            }
+            AccountServerRequestError::LogicError(SignError::Banned(ban)) => SignResult::Banned {
+                ban,
+                account_data: key_pair.clone(),
+            },
+            err => SignResult::Other {
+                err: err.into(),
+                account_data: key_pair.clone(),
+            },
         })?;
     let certificate = {
         x509_parser::certificate::X509Certificate::from_der(&sign_res.cert_der)
@@ -100,18 +121,14 @@ async fn sign_impl(io: IoSafe<'_>) -> anyhow::Result<SignData, SignResult> {
             .then_some(sign_res.cert_der)
     };
 
-    certificate.map_or_else(
-        || {
-            Err(SignResult::Other {
-                err: anyhow!("the certificate is not in a valid der format"),
-                account_data: key_pair.clone(),
-            })
-        },
-        |certificate| {
-            Ok(SignData {
-                certificate_der: certificate,
-                session_key_pair: key_pair.clone(),
-            })
-        },
-    )
+    match certificate {
+        Some(certificate) => Ok(SignData {
+            certificate_der: certificate,
+            session_key_pair: key_pair,
+        }),
+        None => Err(SignResult::Other {
+            err: anyhow!("the certificate is not in a valid der format"),
+            account_data: key_pair,
+        }),
+    }
 }
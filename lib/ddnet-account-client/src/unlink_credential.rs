@@ -1,5 +1,5 @@
 use ddnet_accounts_shared::{
-    account_server::errors::{AccountServerRequestError, Empty},
+    account_server::{errors::AccountServerRequestError, unlink_credential::UnlinkCredentialError},
     client::unlink_credential,
 };
 
@@ -22,7 +22,7 @@ pub enum UnlinkCredentialResult {
     FsLikeError(FsLikeError),
     /// The account server responded with an error.
     #[error("{0}")]
-    AccountServerRequstError(AccountServerRequestError<Empty>),
+    AccountServerRequstError(AccountServerRequestError<UnlinkCredentialError>),
     /// Errors that are not handled explicitly.
     #[error("Unlinking credential failed: {0}")]
     Other(anyhow::Error),
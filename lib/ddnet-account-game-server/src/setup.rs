@@ -1,9 +1,14 @@
 use ddnet_account_sql::any::AnyConnection;
 use ddnet_account_sql::any::AnyPool;
-use ddnet_account_sql::version::get_version;
+use ddnet_account_sql::version::Migrations;
+use futures::future::BoxFuture;
 
 const VERSION_NAME: &str = "account-game-server";
 
+fn unsupported_database() -> anyhow::Error {
+    anyhow::anyhow!("the database is not supported, enable its feature of the game server crate")
+}
+
 #[cfg(feature = "mysql")]
 mod mysql {
     use ddnet_account_sql::any::AnyConnection;
@@ -22,8 +27,6 @@ mod mysql {
         // afterwards actually create tables
         user.query().execute(&mut *con).await?;
 
-        set_version(&mut AnyConnection::MySql(con), VERSION_NAME, 1).await?;
-
         Ok(())
     }
 
@@ -64,8 +67,6 @@ mod sqlite {
         // afterwards actually create tables
         user.query().execute(&mut *con).await?;
 
-        set_version(&mut AnyConnection::Sqlite(con), VERSION_NAME, 1).await?;
-
         Ok(())
     }
 
@@ -88,31 +89,30 @@ mod sqlite {
     }
 }
 
-async fn setup_version1(con: &mut AnyConnection<'_>) -> anyhow::Result<()> {
-    match con {
-        #[cfg(feature = "mysql")]
-        AnyConnection::MySql(con) => mysql::setup_version1(con).await,
-        #[cfg(feature = "sqlite")]
-        AnyConnection::Sqlite(con) => sqlite::setup_version1(con).await,
-    }
+fn setup_version1<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            #[cfg(feature = "mysql")]
+            AnyConnection::MySql(con) => mysql::setup_version1(con).await,
+            #[cfg(feature = "sqlite")]
+            AnyConnection::Sqlite(con) => sqlite::setup_version1(con).await,
+            // other crates of the build might enable more databases
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
 }
 
-/// Sets up all tables required for a game server user
+/// All migrations of the game server tables, in order.
+fn migrations() -> Migrations {
+    Migrations::new(VERSION_NAME).step(setup_version1, None)
+}
+
+/// Sets up all tables required for a game server user,
+/// or migrates them to the latest version.
 pub async fn setup(pool: &AnyPool) -> anyhow::Result<()> {
-    let mut pool_con = pool.acquire().await?;
-    let mut con = pool_con.acquire().await?;
-
-    con.transaction(|mut trans| {
-        Box::pin(async move {
-            let version = get_version(&mut trans.con(), VERSION_NAME).await?;
-            if version < 1 {
-                setup_version1(&mut trans.con()).await?;
-            }
-
-            anyhow::Ok(())
-        })
-    })
-    .await
+    migrations().up(pool).await?;
+    Ok(())
 }
 
 /// Drop all tables related to a game server database setup
@@ -124,5 +124,7 @@ pub async fn delete(pool: &AnyPool) -> anyhow::Result<()> {
         AnyConnection::MySql(con) => mysql::delete(con).await,
         #[cfg(feature = "sqlite")]
         AnyConnection::Sqlite(con) => sqlite::delete(con).await,
+        #[allow(unreachable_patterns)]
+        _ => Err(unsupported_database()),
     }
 }
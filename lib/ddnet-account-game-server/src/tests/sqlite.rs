@@ -22,6 +22,8 @@ pub async fn sqlite() -> anyhow::Result<()> {
 
     // setup
     crate::setup::setup(&pool).await?;
+    // already on the latest version, nothing to do
+    crate::setup::setup(&pool).await?;
 
     let shared = crate::prepare::prepare(&pool).await?;
 
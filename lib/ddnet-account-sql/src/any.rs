@@ -10,6 +10,9 @@ pub enum AnyStatement<'a> {
     /// Sqlite statement
     #[cfg(feature = "sqlite")]
     Sqlite(sqlx::sqlite::SqliteStatement<'a>),
+    /// Postgres statement
+    #[cfg(feature = "postgres")]
+    Postgres(sqlx::postgres::PgStatement<'a>),
 }
 
 /// Enum variant over a database row result
@@ -20,6 +23,9 @@ pub enum AnyRow {
     /// Sqlite query row result
     #[cfg(feature = "sqlite")]
     Sqlite(sqlx::sqlite::SqliteRow),
+    /// Postgres query row result
+    #[cfg(feature = "postgres")]
+    Postgres(sqlx::postgres::PgRow),
 }
 
 #[derive(Debug)]
@@ -31,6 +37,9 @@ pub enum AnyQueryResult {
     /// Sqlite query result
     #[cfg(feature = "sqlite")]
     Sqlite(sqlx::sqlite::SqliteQueryResult),
+    /// Postgres query result
+    #[cfg(feature = "postgres")]
+    Postgres(sqlx::postgres::PgQueryResult),
 }
 
 impl AnyQueryResult {
@@ -41,6 +50,8 @@ impl AnyQueryResult {
             Self::MySql(res) => res.rows_affected(),
             #[cfg(feature = "sqlite")]
             Self::Sqlite(res) => res.rows_affected(),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(res) => res.rows_affected(),
         }
     }
 }
@@ -53,6 +64,9 @@ pub enum AnyQuery<'a> {
     /// Sqlite query
     #[cfg(feature = "sqlite")]
     Sqlite(sqlx::query::Query<'a, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'a>>),
+    /// Postgres query
+    #[cfg(feature = "postgres")]
+    Postgres(sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>),
 }
 
 impl AnyQuery<'_> {
@@ -80,6 +94,15 @@ impl AnyQuery<'_> {
                 };
                 AnyQueryResult::Sqlite(query.execute(&mut **con).await?)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(query) => {
+                let AnyConnection::Postgres(con) = con else {
+                    return Err(sqlx::Error::AnyDriverError(
+                        "Connection was not of postgres type, while query was.".into(),
+                    ));
+                };
+                AnyQueryResult::Postgres(query.execute(&mut **con).await?)
+            }
         })
     }
 
@@ -107,6 +130,15 @@ impl AnyQuery<'_> {
                 };
                 AnyRow::Sqlite(query.fetch_one(&mut **con).await?)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(query) => {
+                let AnyConnection::Postgres(con) = con else {
+                    return Err(sqlx::Error::AnyDriverError(
+                        "Connection was not of postgres type, while query was.".into(),
+                    ));
+                };
+                AnyRow::Postgres(query.fetch_one(&mut **con).await?)
+            }
         })
     }
 
@@ -144,6 +176,20 @@ impl AnyQuery<'_> {
                     .map(AnyRow::Sqlite)
                     .collect()
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(query) => {
+                let AnyConnection::Postgres(con) = con else {
+                    return Err(sqlx::Error::AnyDriverError(
+                        "Connection was not of postgres type, while query was.".into(),
+                    ));
+                };
+                query
+                    .fetch_all(&mut **con)
+                    .await?
+                    .into_iter()
+                    .map(AnyRow::Postgres)
+                    .collect()
+            }
         })
     }
 
@@ -174,6 +220,18 @@ impl AnyQuery<'_> {
                 };
                 query.fetch_optional(&mut **con).await?.map(AnyRow::Sqlite)
             }
+            #[cfg(feature = "postgres")]
+            Self::Postgres(query) => {
+                let AnyConnection::Postgres(con) = con else {
+                    return Err(sqlx::Error::AnyDriverError(
+                        "Connection was not of postgres type, while query was.".into(),
+                    ));
+                };
+                query
+                    .fetch_optional(&mut **con)
+                    .await?
+                    .map(AnyRow::Postgres)
+            }
         })
     }
 }
@@ -187,6 +245,9 @@ pub enum AnyTransaction<'a, 'b> {
     /// Sqlite connection transaction
     #[cfg(feature = "sqlite")]
     Sqlite(&'a mut sqlx::Transaction<'b, sqlx::Sqlite>),
+    /// Postgres connection transaction
+    #[cfg(feature = "postgres")]
+    Postgres(&'a mut sqlx::Transaction<'b, sqlx::Postgres>),
 }
 
 impl AnyTransaction<'_, '_> {
@@ -197,6 +258,8 @@ impl AnyTransaction<'_, '_> {
             AnyTransaction::MySql(trans) => AnyConnection::MySql(trans),
             #[cfg(feature = "sqlite")]
             AnyTransaction::Sqlite(trans) => AnyConnection::Sqlite(trans),
+            #[cfg(feature = "postgres")]
+            AnyTransaction::Postgres(trans) => AnyConnection::Postgres(trans),
         }
     }
 }
@@ -210,6 +273,9 @@ pub enum AnyConnection<'a> {
     /// Sqlite connection
     #[cfg(feature = "sqlite")]
     Sqlite(&'a mut sqlx::sqlite::SqliteConnection),
+    /// Postgres connection
+    #[cfg(feature = "postgres")]
+    Postgres(&'a mut sqlx::postgres::PgConnection),
 }
 
 impl AnyConnection<'_> {
@@ -234,12 +300,19 @@ impl AnyConnection<'_> {
                 con.transaction(|transaction| callback(AnyTransaction::Sqlite(transaction)))
                     .await
             }
+            #[cfg(feature = "postgres")]
+            AnyConnection::Postgres(con) => {
+                con.transaction(|transaction| callback(AnyTransaction::Postgres(transaction)))
+                    .await
+            }
         }
     }
 }
 
 #[derive(Debug)]
 /// Enum variant over a database pooled connection
+// the postgres connection is bigger, but pool connections are short lived anyway
+#[allow(clippy::large_enum_variant)]
 pub enum AnyPoolConnection {
     #[cfg(feature = "mysql")]
     /// Mysql pool connection
@@ -247,18 +320,23 @@ pub enum AnyPoolConnection {
     /// Sqlite pool connection
     #[cfg(feature = "sqlite")]
     Sqlite(sqlx::pool::PoolConnection<sqlx::Sqlite>),
+    /// Postgres pool connection
+    #[cfg(feature = "postgres")]
+    Postgres(sqlx::pool::PoolConnection<sqlx::Postgres>),
 }
 
 impl AnyPoolConnection {
     /// Retrieves the inner connection of this pool connection.
     ///
     /// See [sqlx::Acquire::acquire].
-    pub async fn acquire(&mut self) -> Result<AnyConnection, sqlx::Error> {
+    pub async fn acquire(&mut self) -> Result<AnyConnection<'_>, sqlx::Error> {
         Ok(match self {
             #[cfg(feature = "mysql")]
             Self::MySql(con) => AnyConnection::MySql(con.acquire().await?),
             #[cfg(feature = "sqlite")]
             Self::Sqlite(con) => AnyConnection::Sqlite(con.acquire().await?),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(con) => AnyConnection::Postgres(con.acquire().await?),
         })
     }
 }
@@ -272,6 +350,9 @@ pub enum AnyPool {
     /// Sqlite pool
     #[cfg(feature = "sqlite")]
     Sqlite(sqlx::sqlite::SqlitePool),
+    /// Postgres pool
+    #[cfg(feature = "postgres")]
+    Postgres(sqlx::postgres::PgPool),
 }
 
 impl AnyPool {
@@ -284,6 +365,8 @@ impl AnyPool {
             Self::MySql(pool) => AnyPoolConnection::MySql(pool.acquire().await?),
             #[cfg(feature = "sqlite")]
             Self::Sqlite(pool) => AnyPoolConnection::Sqlite(pool.acquire().await?),
+            #[cfg(feature = "postgres")]
+            Self::Postgres(pool) => AnyPoolConnection::Postgres(pool.acquire().await?),
         })
     }
 }
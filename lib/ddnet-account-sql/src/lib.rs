@@ -8,8 +8,8 @@
 
 use any::AnyQueryResult;
 
-#[cfg(not(any(feature = "mysql", feature = "sqlite")))]
-std::compile_error!("at least the mysql or sqlite feature must be used.");
+#[cfg(not(any(feature = "mysql", feature = "sqlite", feature = "postgres")))]
+std::compile_error!("at least the mysql, sqlite or postgres feature must be used.");
 
 /// Our version of sqlx any variants
 pub mod any;
@@ -20,6 +20,9 @@ pub mod version;
 
 /// Checks if the query result resulted in an error that indicates
 /// a duplicate entry.
+///
+/// Note that for postgres a failed statement aborts the surrounding
+/// transaction, so the transaction can not be continued afterwards.
 pub fn is_duplicate_entry(res: &Result<AnyQueryResult, sqlx::Error>) -> bool {
     res.as_ref().is_err_and(|err| {
         if let sqlx::Error::Database(err) = err {
@@ -29,3 +32,6 @@ pub fn is_duplicate_entry(res: &Result<AnyQueryResult, sqlx::Error>) -> bool {
         }
     })
 }
+
+#[cfg(test)]
+mod tests;
@@ -1,4 +1,6 @@
+use anyhow::anyhow;
 use async_trait::async_trait;
+use sqlx::Statement;
 
 use crate::any::{AnyConnection, AnyQuery, AnyRow, AnyStatement};
 
@@ -6,16 +8,37 @@ use crate::any::{AnyConnection, AnyQuery, AnyRow, AnyStatement};
 #[async_trait]
 pub trait Query<A> {
     /// MySQL version of [`Query::prepare`].
+    ///
+    /// Queries that don't support MySQL can skip the MySQL versions,
+    /// preparing them fails then.
     #[cfg(feature = "mysql")]
     async fn prepare_mysql(
-        connection: &mut sqlx::mysql::MySqlConnection,
-    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>>;
+        _connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Err(anyhow!("this query does not support MySQL"))
+    }
 
     /// Sqlite version of [`Query::prepare`].
+    ///
+    /// Queries that don't support Sqlite can skip the Sqlite versions,
+    /// preparing them fails then.
     #[cfg(feature = "sqlite")]
     async fn prepare_sqlite(
-        connection: &mut sqlx::sqlite::SqliteConnection,
-    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>>;
+        _connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Err(anyhow!("this query does not support Sqlite"))
+    }
+
+    /// Postgres version of [`Query::prepare`].
+    ///
+    /// Queries that don't support Postgres can skip the Postgres versions,
+    /// preparing them fails then.
+    #[cfg(feature = "postgres")]
+    async fn prepare_postgres(
+        _connection: &mut sqlx::postgres::PgConnection,
+    ) -> anyhow::Result<sqlx::postgres::PgStatement<'static>> {
+        Err(anyhow!("this query does not support Postgres"))
+    }
 
     /// Prepare a statement to be later used by [`Query::query`].
     async fn prepare(connection: &mut AnyConnection) -> anyhow::Result<AnyStatement<'static>> {
@@ -28,6 +51,10 @@ pub trait Query<A> {
             AnyConnection::Sqlite(connection) => {
                 AnyStatement::Sqlite(Self::prepare_sqlite(connection).await?)
             }
+            #[cfg(feature = "postgres")]
+            AnyConnection::Postgres(connection) => {
+                AnyStatement::Postgres(Self::prepare_postgres(connection).await?)
+            }
         })
     }
 
@@ -36,14 +63,30 @@ pub trait Query<A> {
     fn query_mysql<'a>(
         &'a self,
         statement: &'a sqlx::mysql::MySqlStatement<'static>,
-    ) -> sqlx::query::Query<'a, sqlx::MySql, sqlx::mysql::MySqlArguments>;
+    ) -> sqlx::query::Query<'a, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        // never called, the statement can't be prepared
+        statement.query()
+    }
 
     /// Sqlite version of [`Query::query`].
     #[cfg(feature = "sqlite")]
     fn query_sqlite<'a>(
         &'a self,
         statement: &'a sqlx::sqlite::SqliteStatement<'static>,
-    ) -> sqlx::query::Query<'a, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'a>>;
+    ) -> sqlx::query::Query<'a, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'a>> {
+        // never called, the statement can't be prepared
+        statement.query()
+    }
+
+    /// Postgres version of [`Query::query`].
+    #[cfg(feature = "postgres")]
+    fn query_postgres<'a>(
+        &'a self,
+        statement: &'a sqlx::postgres::PgStatement<'static>,
+    ) -> sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments> {
+        // never called, the statement can't be prepared
+        statement.query()
+    }
 
     /// Get a query with all arguments bound already, ready to be fetched.
     fn query<'a>(&'a self, statement: &'a AnyStatement<'static>) -> AnyQuery<'a> {
@@ -52,16 +95,28 @@ pub trait Query<A> {
             AnyStatement::MySql(statement) => AnyQuery::MySql(self.query_mysql(statement)),
             #[cfg(feature = "sqlite")]
             AnyStatement::Sqlite(statement) => AnyQuery::Sqlite(self.query_sqlite(statement)),
+            #[cfg(feature = "postgres")]
+            AnyStatement::Postgres(statement) => AnyQuery::Postgres(self.query_postgres(statement)),
         }
     }
 
     /// MySQL version of [`Query::row_data`].
     #[cfg(feature = "mysql")]
-    fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<A>;
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<A> {
+        Err(anyhow!("this query does not support MySQL"))
+    }
 
     /// Sqlite version of [`Query::row_data`].
     #[cfg(feature = "sqlite")]
-    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<A>;
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<A> {
+        Err(anyhow!("this query does not support Sqlite"))
+    }
+
+    /// Postgres version of [`Query::row_data`].
+    #[cfg(feature = "postgres")]
+    fn row_data_postgres(_row: &sqlx::postgres::PgRow) -> anyhow::Result<A> {
+        Err(anyhow!("this query does not support Postgres"))
+    }
 
     /// Gets the row data for a result row of this query
     fn row_data(row: &AnyRow) -> anyhow::Result<A> {
@@ -70,6 +125,8 @@ pub trait Query<A> {
             AnyRow::MySql(row) => Self::row_data_mysql(row),
             #[cfg(feature = "sqlite")]
             AnyRow::Sqlite(row) => Self::row_data_sqlite(row),
+            #[cfg(feature = "postgres")]
+            AnyRow::Postgres(row) => Self::row_data_postgres(row),
         }
     }
 }
@@ -0,0 +1,58 @@
+use anyhow::anyhow;
+use futures::future::BoxFuture;
+use sqlx::{sqlite::SqlitePoolOptions, Executor};
+
+use crate::{
+    any::{AnyConnection, AnyPool},
+    version::Migrations,
+};
+
+const VERSION_NAME: &str = "account-sql-test";
+
+fn setup_version1<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        let AnyConnection::Sqlite(con) = con else {
+            return Err(anyhow!("only sqlite is tested"));
+        };
+        con.execute("CREATE TABLE test_entry (id INTEGER PRIMARY KEY);")
+            .await?;
+        Ok(())
+    })
+}
+
+fn setup_version2<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        let AnyConnection::Sqlite(con) = con else {
+            return Err(anyhow!("only sqlite is tested"));
+        };
+        con.execute("ALTER TABLE test_entry ADD COLUMN name TEXT;")
+            .await?;
+        Ok(())
+    })
+}
+
+/// Tests that an older binary does not use tables of a newer version
+#[tokio::test]
+async fn migrations_newer_version() -> anyhow::Result<()> {
+    // a single connection, so all of them use the same in-memory database
+    let pool = AnyPool::Sqlite(
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await?,
+    );
+
+    let new_migrations = Migrations::new(VERSION_NAME)
+        .step(setup_version1, None)
+        .step(setup_version2, None);
+    assert_eq!(new_migrations.up(&pool).await?, 0);
+
+    let old_migrations = Migrations::new(VERSION_NAME).step(setup_version1, None);
+    let err = old_migrations.up(&pool).await.unwrap_err();
+    assert!(err.to_string().contains("newer"));
+
+    // the newer binary still works
+    assert_eq!(new_migrations.up(&pool).await?, 2);
+
+    Ok(())
+}
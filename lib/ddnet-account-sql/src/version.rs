@@ -1,3 +1,6 @@
+use anyhow::anyhow;
+use futures::future::BoxFuture;
+
 use crate::any::{AnyConnection, AnyPool};
 
 #[cfg(feature = "mysql")]
@@ -180,6 +183,97 @@ mod sqlite {
     }
 }
 
+#[cfg(feature = "postgres")]
+mod postgres {
+    use sqlx::Executor;
+    use sqlx::Row;
+    use sqlx::Statement;
+
+    pub(super) async fn try_setup(con: &mut sqlx::postgres::PgConnection) -> anyhow::Result<()> {
+        // first create all statements (syntax check)
+        let version = con
+            .prepare(include_str!("version/postgres/version.sql"))
+            .await?;
+
+        // afterwards actually create tables
+        version.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn get_or_set_version(
+        con: &mut sqlx::postgres::PgConnection,
+        name: &str,
+    ) -> anyhow::Result<i64> {
+        // first create all statements (syntax check)
+        let get_version = con
+            .prepare(include_str!("version/postgres/get_version.sql"))
+            .await?;
+        let set_version = con
+            .prepare(include_str!("version/postgres/set_version.sql"))
+            .await?;
+
+        let name = name.to_string();
+
+        // afterwards actually create tables
+        if let Some(row) = get_version
+            .query()
+            .bind(&name)
+            .fetch_optional(&mut *con)
+            .await?
+        {
+            anyhow::Ok(row.try_get("version")?)
+        } else {
+            // insert new entry
+            set_version
+                .query()
+                .bind(&name)
+                // postgres is strict about the integer size
+                .bind(0i64)
+                .bind(0i64)
+                .execute(&mut *con)
+                .await?;
+            anyhow::Ok(0)
+        }
+    }
+
+    pub(super) async fn set_version(
+        con: &mut sqlx::postgres::PgConnection,
+        name: &str,
+        version: i64,
+    ) -> anyhow::Result<()> {
+        // first create all statements (syntax check)
+        let set_version = con
+            .prepare(include_str!("version/postgres/set_version.sql"))
+            .await?;
+
+        Ok(set_version
+            .query()
+            .bind(name)
+            .bind(version)
+            .bind(version)
+            .execute(&mut *con)
+            .await
+            .map(|_| ())?)
+    }
+
+    pub(super) async fn delete(con: &mut sqlx::postgres::PgConnection) -> anyhow::Result<()> {
+        // first create all statements (syntax check)
+        // delete in reverse order to creating
+        let version = con
+            .prepare(include_str!("version/postgres/delete/version.sql"))
+            .await?;
+
+        // afterwards actually drop tables
+        let version = version.query().execute(&mut *con).await;
+
+        // handle errors at once
+        version?;
+
+        Ok(())
+    }
+}
+
 /// Use this function to obtain the current version number.
 ///
 /// If the version table does not exist, sets up the version table.
@@ -199,6 +293,12 @@ pub async fn get_version(con: &mut AnyConnection<'_>, name: &str) -> anyhow::Res
             sqlite::try_setup(con).await?;
             sqlite::get_or_set_version(con, name).await
         }
+        #[cfg(feature = "postgres")]
+        AnyConnection::Postgres(con) => {
+            // try setup
+            postgres::try_setup(con).await?;
+            postgres::get_or_set_version(con, name).await
+        }
     }
 }
 
@@ -214,6 +314,8 @@ pub async fn set_version(
         AnyConnection::MySql(con) => mysql::set_version(con, name, version).await,
         #[cfg(feature = "sqlite")]
         AnyConnection::Sqlite(con) => sqlite::set_version(con, name, version).await,
+        #[cfg(feature = "postgres")]
+        AnyConnection::Postgres(con) => postgres::set_version(con, name, version).await,
     }
 }
 
@@ -227,5 +329,140 @@ pub async fn delete(pool: &AnyPool) -> anyhow::Result<()> {
         AnyConnection::MySql(con) => mysql::delete(con).await,
         #[cfg(feature = "sqlite")]
         AnyConnection::Sqlite(con) => sqlite::delete(con).await,
+        #[cfg(feature = "postgres")]
+        AnyConnection::Postgres(con) => postgres::delete(con).await,
+    }
+}
+
+/// A function that applies (or reverts) a single migration step
+/// on the given connection.
+///
+/// The step must not set the version itself, this is done
+/// by [`Migrations`].
+pub type MigrationFn =
+    for<'a, 'b> fn(&'a mut AnyConnection<'b>) -> BoxFuture<'a, anyhow::Result<()>>;
+
+/// A single migration step, see [`Migrations::step`].
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Migrates the tables from the previous version to this one.
+    pub up: MigrationFn,
+    /// Optionally migrates the tables back to the previous version.
+    pub down: Option<MigrationFn>,
+}
+
+/// An ordered list of migrations for a group of tables
+/// that share the same version name.
+///
+/// The first step migrates from version 0 (no tables) to version 1,
+/// the second from version 1 to version 2 and so on.
+/// Every step runs in its own transaction together with the update of
+/// the version, so a failing step leaves the tables at the last
+/// successful version.  
+/// __IMPORTANT__: MySQL implicitly commits most schema changes (e.g. `CREATE TABLE`),
+/// so there a failing step might only be partially reverted.
+#[derive(Debug, Clone)]
+pub struct Migrations {
+    name: &'static str,
+    steps: Vec<Migration>,
+}
+
+impl Migrations {
+    /// Creates an empty migration list for the tables
+    /// identified by `name`.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Adds the next migration step.
+    pub fn step(mut self, up: MigrationFn, down: Option<MigrationFn>) -> Self {
+        self.steps.push(Migration { up, down });
+        self
+    }
+
+    /// The version the tables have after all migrations ran.
+    pub const fn latest_version(&self) -> i64 {
+        self.steps.len() as i64
+    }
+
+    /// Runs all migrations that were not yet applied.
+    ///
+    /// Fails if the tables have a newer version than the latest migration,
+    /// i.e. they were set up by a newer binary.
+    ///
+    /// Returns the version before the migration.
+    pub async fn up(&self, pool: &AnyPool) -> anyhow::Result<i64> {
+        let mut pool_con = pool.acquire().await?;
+        let mut con = pool_con.acquire().await?;
+
+        let latest_version = self.latest_version();
+        let mut prev_version = None;
+        for (index, step) in self.steps.iter().enumerate() {
+            let step = *step;
+            let name = self.name;
+            let next_version = index as i64 + 1;
+            let version = con
+                .transaction(|mut trans| {
+                    Box::pin(async move {
+                        let version = get_version(&mut trans.con(), name).await?;
+                        anyhow::ensure!(
+                            version <= latest_version,
+                            "version {version} of {name} is newer than the latest known \
+                            version {latest_version}, the tables were set up by a newer version"
+                        );
+                        if version < next_version {
+                            anyhow::ensure!(
+                                version == next_version - 1,
+                                "version {version} of {name} is unknown to the migrations"
+                            );
+                            (step.up)(&mut trans.con()).await?;
+                            set_version(&mut trans.con(), name, next_version).await?;
+                        }
+
+                        anyhow::Ok(version)
+                    })
+                })
+                .await?;
+            prev_version.get_or_insert(version);
+        }
+
+        Ok(prev_version.unwrap_or_default())
+    }
+
+    /// Reverts the migrations until the tables are at the given version.
+    ///
+    /// Fails if one of the required migration steps has no down step.
+    pub async fn down(&self, pool: &AnyPool, target_version: i64) -> anyhow::Result<()> {
+        let mut pool_con = pool.acquire().await?;
+        let mut con = pool_con.acquire().await?;
+
+        for (index, step) in self.steps.iter().enumerate().rev() {
+            let step = *step;
+            let name = self.name;
+            let step_version = index as i64 + 1;
+            if step_version <= target_version {
+                break;
+            }
+            con.transaction(|mut trans| {
+                Box::pin(async move {
+                    let version = get_version(&mut trans.con(), name).await?;
+                    if version >= step_version {
+                        let down = step.down.ok_or_else(|| {
+                            anyhow!("version {step_version} of {name} can not be reverted")
+                        })?;
+                        down(&mut trans.con()).await?;
+                        set_version(&mut trans.con(), name, step_version - 1).await?;
+                    }
+
+                    anyhow::Ok(())
+                })
+            })
+            .await?;
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,6 @@
+SELECT
+    table_infra_version.version
+FROM
+    table_infra_version
+WHERE
+    table_infra_version.name = $1;
@@ -0,0 +1,16 @@
+INSERT INTO
+    table_infra_version (
+        name,
+        version,
+        last_update_time
+    )
+VALUES
+    (
+        $1,
+        $2,
+        timezone('UTC', now())
+    ) ON CONFLICT(name) DO
+UPDATE
+SET
+    version = $3,
+    last_update_time = timezone('UTC', now());
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS table_infra_version (
+    id BIGINT GENERATED ALWAYS AS IDENTITY,
+    -- the group name of the tables to be created
+    name VARCHAR(64) NOT NULL COLLATE "C",
+    version BIGINT NOT NULL,
+    -- UTC timestamp! (timezone('UTC', now())) of the
+    -- last time this table was update
+    last_update_time TIMESTAMP NOT NULL,
+    PRIMARY KEY(id),
+    UNIQUE(name)
+);
@@ -0,0 +1,67 @@
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use strum::{EnumString, IntoStaticStr};
+
+use crate::client::machine_id::MachineUid;
+
+/// Identifies the machine that triggered an account event,
+/// without exposing the hardware id of the session.
+pub type HwIdHash = [u8; 32];
+
+/// Generates the hash of the given hardware id,
+/// so the client can find out which events were triggered by itself.
+pub fn hw_id_hash(hw_id: &MachineUid) -> HwIdHash {
+    Sha256::digest(hw_id).into()
+}
+
+/// The kind of a security relevant event of an account.
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr, EnumString, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "lowercase")]
+pub enum AccountEventKind {
+    /// A new session was created.
+    Login,
+    /// A credential was linked to the account.
+    LinkCredential,
+    /// A credential was unlinked from the account.
+    UnlinkCredential,
+    /// All sessions were logged out at once.
+    LogoutAll,
+    /// The account was deleted.
+    Delete,
+}
+
+/// The type of credential that was involved in an account event.
+#[derive(Debug, Serialize, Deserialize, IntoStaticStr, EnumString, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "lowercase")]
+pub enum AccountEventCredential {
+    /// An email address.
+    Email,
+    /// A steam account.
+    Steam,
+}
+
+/// A single security relevant event of an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountEvent {
+    /// What happened.
+    pub kind: AccountEventKind,
+    /// The UTC date when the event happened.
+    pub time: chrono::DateTime<chrono::Utc>,
+    /// The ip of the client that triggered the event.
+    pub client_ip: IpAddr,
+    /// The hash of the hardware id of the client that triggered the event,
+    /// see [`hw_id_hash`].
+    /// Only known if the event was triggered by a session.
+    pub hw_id_hash: Option<HwIdHash>,
+    /// The credential that was used for the event, if any.
+    pub credential: Option<AccountEventCredential>,
+}
+
+/// The response of an account events request from the client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountEventsResponse {
+    /// The most recent events of the account, newest first.
+    pub events: Vec<AccountEvent>,
+}
@@ -1,5 +1,6 @@
 use ddnet_accounts_types::account_id::AccountId;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 /// A linked credential type of an account
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,3 +21,14 @@ pub struct AccountInfoResponse {
     /// the credentials linked to this account
     pub credentials: Vec<CredentialType>,
 }
+
+/// The logic errors of an account info request by the client.
+#[derive(Debug, Error, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AccountInfoError {
+    /// The signature does not belong to the session.
+    #[error("The request was not signed by the session.")]
+    InvalidSignature,
+    /// The session does not exist (anymore), the client has to log in again.
+    #[error("The session is not valid anymore.")]
+    SessionInvalid,
+}
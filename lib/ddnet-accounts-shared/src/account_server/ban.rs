@@ -0,0 +1,27 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Information about the ban of an account,
+/// so the user knows why the request was denied.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountBan {
+    /// The reason for the ban.
+    pub reason: String,
+    /// The UTC date until the ban lasts,
+    /// `None` if the ban is permanent.
+    pub expires: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Display for AccountBan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.expires {
+            Some(expires) => write!(f, "The account is banned until {expires}"),
+            None => write!(f, "The account is banned permanently"),
+        }?;
+        if !self.reason.is_empty() {
+            write!(f, ": {}", self.reason)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::ban::AccountBan;
+
+/// The logic errors of a delete request by the client.
+#[derive(Debug, Error, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DeleteError {
+    /// The account token is invalid, probably timed out
+    /// or already used. A token of another operation is a
+    /// [`crate::account_server::errors::RequestFailure::WrongTokenOp`].
+    #[error("The provided token is not valid anymore.")]
+    TokenInvalid,
+    /// The account is banned, only an admin can delete it.
+    #[error("{0}")]
+    Banned(AccountBan),
+}
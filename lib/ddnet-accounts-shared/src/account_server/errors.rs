@@ -17,21 +17,46 @@ pub enum AccountServerRequestError<E> {
     /// Any kind of layer reported to block this connection.
     #[error("{0}")]
     Other(String),
+    /// A known failure, e.g. an expired token.
+    #[error("{0}")]
+    Failure(RequestFailure),
     /// Database errors or similar.
     #[error("{target}: {err}. Bt: {bt}")]
     Unexpected {
         /// Where the error happened
         target: String,
-        /// The error as string
+        /// The error as string,
+        /// only contains details if the account server is in debug mode.
         err: String,
-        /// A backtrace.
+        /// A backtrace,
+        /// only set if the account server is in debug mode.
         bt: String,
+        /// An opaque id to find the details of
+        /// the error in the logs of the account server.
+        #[serde(default)]
+        incident: Option<String>,
     },
     /// Error caused by the logic.
     #[error("{0}")]
     LogicError(E),
 }
 
+/// Failures that can happen in many requests
+/// and are caused by the client, not by the account server.
+///
+/// Failures of a single request are part of its logic error instead,
+/// e.g. an invalid token.
+#[derive(Error, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RequestFailure {
+    /// The token is valid, but was created for a different operation.
+    #[error("The token was not created for this operation.")]
+    WrongTokenOp,
+    /// The time stamp of a signed request was too old
+    /// or in the future.
+    #[error("The time stamp was not in a valid time frame.")]
+    TimestampOutOfWindow,
+}
+
 /// Empty logic error wrapper, which implements display
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Empty;
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// The state of the account server or of one of its dependencies.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// Everything works.
+    Ok,
+    /// Requests are handled, but some features might not work,
+    /// e.g. emails are delayed.
+    Degraded,
+    /// Requests can't be handled.
+    Failed,
+}
+
+/// The result of checking a single dependency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheck {
+    /// The name of the dependency, e.g. `database`.
+    pub name: String,
+    /// The state of the dependency.
+    pub status: HealthStatus,
+    /// Why the dependency is not ok.
+    pub message: Option<String>,
+    /// How long the check took in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// The response of the readiness endpoint (`/health/ready`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// The worst state of all checks.
+    pub status: HealthStatus,
+    /// The checks of all dependencies.
+    pub checks: Vec<HealthCheck>,
+}
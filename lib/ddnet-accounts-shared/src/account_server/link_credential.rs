@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The logic errors of a link credential request by the client.
+#[derive(Debug, Error, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LinkCredentialError {
+    /// The account token is invalid, probably timed out
+    /// or already used. A token of another operation is a
+    /// [`crate::account_server::errors::RequestFailure::WrongTokenOp`].
+    #[error("The provided account token is not valid anymore.")]
+    AccountTokenInvalid,
+    /// The credential auth token is invalid, probably timed out
+    /// or already used. A token of another operation is a
+    /// [`crate::account_server::errors::RequestFailure::WrongTokenOp`].
+    #[error("The provided credential auth token is not valid anymore.")]
+    CredentialAuthTokenInvalid,
+    /// The credential is already linked to a different account.
+    #[error("This credential is already used for a different account.")]
+    CredentialAlreadyLinked,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The logic errors of a logout all request by the client.
+#[derive(Debug, Error, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LogoutAllError {
+    /// The account token is invalid, probably timed out
+    /// or already used. A token of another operation is a
+    /// [`crate::account_server::errors::RequestFailure::WrongTokenOp`].
+    #[error("The provided token is not valid anymore.")]
+    TokenInvalid,
+}
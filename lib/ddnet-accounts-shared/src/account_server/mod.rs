@@ -1,8 +1,13 @@
+/// Types related to a client reading the
+/// recent security relevant events of its account.
+pub mod account_events;
 /// Types related to a client doing an
 /// account info request.
 pub mod account_info;
 /// Types related to a client requesting a account token.
 pub mod account_token;
+/// Types related to a banned account.
+pub mod ban;
 /// The account data as extension for a x509 certificate.
 pub mod cert_account_ext;
 /// Types related to account server certificates.
@@ -10,16 +15,33 @@ pub mod certs;
 /// Types related to a client requesting a login
 /// token.
 pub mod credential_auth_token;
+/// Types related to a client deleting its account.
+pub mod delete;
 /// Types related to errors generated by the account server.
 pub mod errors;
+/// Types related to the health & readiness
+/// endpoints of the account server.
+pub mod health;
+/// Types related to a client linking
+/// a credential to its account.
+pub mod link_credential;
 /// Types related to a client doing a login
 /// request.
 pub mod login;
+/// Types related to a client logging out
+/// all sessions of its account.
+pub mod logout_all;
 /// Types related to security of connections.
 /// otp = one time password
 pub mod otp;
 /// Types related to results generated by the account server.
 pub mod result;
+/// Types related to a client listing
+/// or revoking its sessions.
+pub mod sessions;
 /// Types related to a client doing an
 /// auth request.
 pub mod sign;
+/// Types related to a client unlinking
+/// a credential from its account.
+pub mod unlink_credential;
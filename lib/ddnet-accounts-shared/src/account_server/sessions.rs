@@ -0,0 +1,46 @@
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Identifies a session of an account without exposing
+/// any data the session needs to authenticate.
+pub type SessionFingerprint = [u8; 32];
+
+/// Generates the fingerprint of the session with the given public key.
+pub fn session_fingerprint(public_key: &VerifyingKey) -> SessionFingerprint {
+    Sha256::digest(public_key.as_bytes()).into()
+}
+
+/// A single session of an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+    /// The fingerprint of the session,
+    /// used to revoke the session.
+    pub fingerprint: SessionFingerprint,
+    /// The UTC date when the session was created.
+    pub create_time: chrono::DateTime<chrono::Utc>,
+    /// The UTC date when the session was last signed
+    /// by the account server.
+    pub last_sign_time: chrono::DateTime<chrono::Utc>,
+    /// The label the client sent when creating the session.
+    pub device_label: Option<String>,
+    /// Whether this is the session that requested the list.
+    pub is_current: bool,
+}
+
+/// The response of a sessions request from the client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionsResponse {
+    /// All sessions of the account.
+    pub sessions: Vec<SessionInfo>,
+}
+
+/// The logic errors of a session revoke request by the client.
+#[derive(Debug, Error, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SessionRevokeError {
+    /// No session of the account has the fingerprint,
+    /// e.g. because it was revoked already.
+    #[error("No session with the given fingerprint found.")]
+    SessionNotFound,
+}
@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::ban::AccountBan;
 
 /// The response of an sign request from the client.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,3 +9,14 @@ pub struct SignResponseSuccess {
     /// certificate, serialized in der format.
     pub cert_der: Vec<u8>,
 }
+
+/// The logic errors of a sign request by the client.
+#[derive(Debug, Error, Clone, Serialize, Deserialize)]
+pub enum SignError {
+    /// The session does not exist (anymore), the client has to log in again.
+    #[error("The session is not valid anymore.")]
+    SessionInvalid,
+    /// The account is banned.
+    #[error("{0}")]
+    Banned(AccountBan),
+}
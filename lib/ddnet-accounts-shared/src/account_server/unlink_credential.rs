@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The logic errors of an unlink credential request by the client.
+#[derive(Debug, Error, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UnlinkCredentialError {
+    /// The credential auth token is invalid, probably timed out
+    /// or already used. A token of another operation is a
+    /// [`crate::account_server::errors::RequestFailure::WrongTokenOp`].
+    #[error("The provided token is not valid anymore.")]
+    TokenInvalid,
+    /// The credential is not linked to any account.
+    #[error("This credential is not linked to an account.")]
+    CredentialNotLinked,
+    /// The credential is the last one of its account.
+    #[error("The last credential of an account can not be unlinked.")]
+    LastCredential,
+}
@@ -37,6 +37,11 @@ pub struct AccountTokenEmailRequest {
     /// It is optional, since these verification
     /// processes differ from user to user.
     pub secret_key: Option<SecretKey>,
+    /// The preferred language of the email, e.g. `de` or `pt-br`.
+    /// Falls back to english if the account server has no
+    /// templates for this language.
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 /// A request for an account token by steam.
@@ -36,6 +36,11 @@ pub struct CredentialAuthTokenEmailRequest {
     /// The operation that this credential authorization
     /// should perform.
     pub op: CredentialAuthTokenOperation,
+    /// The preferred language of the email, e.g. `de` or `pt-br`.
+    /// Falls back to english if the account server has no
+    /// templates for this language.
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 /// A request for a token that is used for the
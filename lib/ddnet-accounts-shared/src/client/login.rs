@@ -13,6 +13,10 @@ use super::account_data::{
 /// for a steam login attempt.
 pub type CredentialAuthToken = Otp;
 
+/// The maximum number of characters of a device label,
+/// that the account server stores.
+pub const MAX_DEVICE_LABEL_LEN: usize = 64;
+
 /// Represents the data required for a login attempt.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoginRequest {
@@ -25,6 +29,12 @@ pub struct LoginRequest {
     /// used to make sure the public key corresponds
     /// to a valid private key.
     pub credential_auth_token_signature: Signature,
+    /// An optional label for the device of the new session
+    /// (e.g. the host name), so the user can recognize
+    /// the session in the session list later.
+    /// At most [`MAX_DEVICE_LABEL_LEN`] characters are stored.
+    #[serde(default)]
+    pub device_label: Option<String>,
 }
 
 fn login_from_account_data(
@@ -44,6 +54,7 @@ fn login_from_account_data(
             credential_auth_token: credential_auth_token
                 .try_into()
                 .map_err(|_| anyhow!("Invalid credential auth token."))?,
+            device_label: None,
         },
         account_data.for_client,
     ))
@@ -4,6 +4,9 @@
 /// uniquely on game-servers.
 pub mod account_data;
 /// Data types and operations related to prepering
+/// an account events request.
+pub mod account_events;
+/// Data types and operations related to prepering
 /// an account info request.
 pub mod account_info;
 /// A data type that is used for various account related operations.
@@ -31,6 +34,9 @@ pub mod logout_all;
 /// Get a unique identifier per machine.
 /// On unsupported systems this creates a default id.
 pub mod machine_id;
+/// Data types and operations related to prepering
+/// a sessions or session revoke request.
+pub mod sessions;
 /// Data types and operations that the client uses
 /// when an auth to the account server is issued.
 pub mod sign;
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{ed25519::signature::Signer, Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::account_server::sessions::SessionFingerprint;
+
+use super::{account_data::AccountDataForServer, machine_id::MachineUid};
+
+/// Represents the data required to list the sessions of an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionsRequest {
+    /// The account data related to the sessions request.
+    pub account_data: AccountDataForServer,
+    /// The timestamp when the sessions request was triggered
+    pub time_stamp: DateTime<Utc>,
+    /// The signature for the above time stamp
+    pub signature: Signature,
+}
+
+/// Generate data for a sessions request
+pub fn prepare_sessions_request(
+    hw_id: MachineUid,
+    key: &SigningKey,
+    pub_key: VerifyingKey,
+) -> SessionsRequest {
+    let time_stamp = chrono::Utc::now();
+    let time_str = time_stamp.to_string();
+
+    let signature = key.sign(time_str.as_bytes());
+
+    SessionsRequest {
+        account_data: AccountDataForServer {
+            public_key: pub_key,
+            hw_id,
+        },
+        signature,
+        time_stamp,
+    }
+}
+
+/// Represents the data required to revoke a session of an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRevokeRequest {
+    /// The account data related to the session revoke request.
+    pub account_data: AccountDataForServer,
+    /// The fingerprint of the session that should be revoked.
+    pub fingerprint: SessionFingerprint,
+    /// The timestamp when the session revoke request was triggered
+    pub time_stamp: DateTime<Utc>,
+    /// The signature for the above time stamp and fingerprint,
+    /// see [`session_revoke_signed_data`].
+    pub signature: Signature,
+}
+
+/// The data that is signed for a session revoke request.
+pub fn session_revoke_signed_data(
+    time_stamp: &DateTime<Utc>,
+    fingerprint: &SessionFingerprint,
+) -> String {
+    format!("{}{}", time_stamp, hex::encode(fingerprint))
+}
+
+/// Generate data for a session revoke request
+pub fn prepare_session_revoke_request(
+    fingerprint: SessionFingerprint,
+    hw_id: MachineUid,
+    key: &SigningKey,
+    pub_key: VerifyingKey,
+) -> SessionRevokeRequest {
+    let time_stamp = chrono::Utc::now();
+
+    let signature = key.sign(session_revoke_signed_data(&time_stamp, &fingerprint).as_bytes());
+
+    SessionRevokeRequest {
+        account_data: AccountDataForServer {
+            public_key: pub_key,
+            hw_id,
+        },
+        fingerprint,
+        signature,
+        time_stamp,
+    }
+}
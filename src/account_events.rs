@@ -0,0 +1,120 @@
+pub mod queries;
+
+use std::{net::IpAddr, str::FromStr, sync::Arc};
+
+use axum::Json;
+use ddnet_account_sql::{
+    any::{AnyConnection, AnyPool},
+    query::Query,
+};
+use ddnet_accounts_shared::{
+    account_server::{
+        account_events::{
+            hw_id_hash, AccountEvent, AccountEventCredential, AccountEventKind,
+            AccountEventsResponse, HwIdHash,
+        },
+        errors::{Empty, RequestFailure},
+        result::AccountServerReqResult,
+    },
+    client::{account_events::AccountEventsRequest, machine_id::MachineUid},
+};
+use ddnet_accounts_types::account_id::AccountId;
+
+use crate::{
+    request_error::request_error,
+    shared::{Shared, CERT_MAX_AGE_DELTA, CERT_MIN_AGE_DELTA},
+};
+
+use self::queries::{AccountEvents, AddAccountEvent};
+
+/// Writes a security relevant event to the audit log of the given account.
+/// Should be called inside the transaction of the operation itself,
+/// so that the event is only recorded if the operation succeeds.
+pub async fn add_account_event(
+    shared: &Shared,
+    connection: &mut AnyConnection<'_>,
+    account_id: &AccountId,
+    kind: AccountEventKind,
+    client_ip: IpAddr,
+    hw_id: Option<&MachineUid>,
+    credential: Option<AccountEventCredential>,
+) -> anyhow::Result<()> {
+    let hw_id_hash = hw_id.map(hw_id_hash);
+    let qry = AddAccountEvent {
+        account_id,
+        kind,
+        client_ip: &client_ip.to_string(),
+        hw_id_hash: hw_id_hash.as_ref(),
+        credential,
+    };
+
+    qry.query(&shared.db.add_account_event_statement)
+        .execute(connection)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn account_events_request(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Json(data): Json<AccountEventsRequest>,
+) -> Json<AccountServerReqResult<AccountEventsResponse, Empty>> {
+    Json(
+        account_events(shared, pool, data)
+            .await
+            .map_err(|err| request_error("account_events", err)),
+    )
+}
+
+pub async fn account_events(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    data: AccountEventsRequest,
+) -> anyhow::Result<AccountEventsResponse> {
+    data.account_data
+        .public_key
+        .verify_strict(data.time_stamp.to_string().as_bytes(), &data.signature)?;
+    let now = chrono::Utc::now();
+    let delta = now.signed_duration_since(data.time_stamp);
+    anyhow::ensure!(
+        delta < CERT_MAX_AGE_DELTA && delta > CERT_MIN_AGE_DELTA,
+        RequestFailure::TimestampOutOfWindow
+    );
+
+    let mut connection = pool.acquire().await?;
+    let mut connection = connection.acquire().await?;
+
+    let qry = AccountEvents {
+        session_pub_key: data.account_data.public_key.as_bytes(),
+        session_hw_id: &data.account_data.hw_id,
+    };
+    let rows = qry
+        .query(&shared.db.account_events_statement)
+        .fetch_all(&mut connection)
+        .await?;
+
+    let mut events = Vec::new();
+    for row in rows {
+        let event = AccountEvents::row_data(&row)?;
+        events.push(AccountEvent {
+            kind: AccountEventKind::from_str(&event.event)?,
+            time: event.create_time,
+            client_ip: event.client_ip.parse()?,
+            hw_id_hash: event
+                .hw_id_hash
+                .map(|hash| {
+                    HwIdHash::try_from(hash.as_slice())
+                        .map_err(|_| anyhow::anyhow!("Hardware id hash has an invalid length."))
+                })
+                .transpose()?,
+            credential: event
+                .credential_type
+                .as_deref()
+                .map(AccountEventCredential::from_str)
+                .transpose()?,
+        });
+    }
+
+    Ok(AccountEventsResponse { events })
+}
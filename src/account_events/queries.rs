@@ -0,0 +1,159 @@
+use anyhow::anyhow;
+use axum::async_trait;
+use ddnet_account_sql::query::Query;
+use ddnet_accounts_shared::{
+    account_server::account_events::{AccountEventCredential, AccountEventKind, HwIdHash},
+    client::machine_id::MachineUid,
+};
+use ddnet_accounts_types::account_id::AccountId;
+use sqlx::Executor;
+use sqlx::Row;
+use sqlx::Statement;
+
+pub struct AddAccountEvent<'a> {
+    pub account_id: &'a AccountId,
+    pub kind: AccountEventKind,
+    pub client_ip: &'a str,
+    pub hw_id_hash: Option<&'a HwIdHash>,
+    pub credential: Option<AccountEventCredential>,
+}
+
+#[async_trait]
+impl Query<()> for AddAccountEvent<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/add_account_event.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/add_account_event.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        let kind: &'static str = self.kind.into();
+        let credential: Option<&'static str> = self.credential.map(Into::into);
+        statement
+            .query()
+            .bind(self.account_id)
+            .bind(kind)
+            .bind(self.client_ip)
+            .bind(self.hw_id_hash.map(|hash| hash.as_slice()))
+            .bind(credential)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        let kind: &'static str = self.kind.into();
+        let credential: Option<&'static str> = self.credential.map(Into::into);
+        statement
+            .query()
+            .bind(self.account_id)
+            .bind(kind)
+            .bind(self.client_ip)
+            .bind(self.hw_id_hash.map(|hash| hash.as_slice()))
+            .bind(credential)
+    }
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+}
+
+/// The most recent events of the account the given session belongs to.
+pub struct AccountEvents<'a> {
+    pub session_pub_key: &'a [u8; 32],
+    pub session_hw_id: &'a MachineUid,
+}
+
+pub struct AccountEventData {
+    pub event: String,
+    pub create_time: sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    pub client_ip: String,
+    pub hw_id_hash: Option<Vec<u8>>,
+    pub credential_type: Option<String>,
+}
+
+#[async_trait]
+impl Query<AccountEventData> for AccountEvents<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/account_events.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/account_events.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement
+            .query()
+            .bind(self.session_pub_key.as_slice())
+            .bind(self.session_hw_id.as_slice())
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement
+            .query()
+            .bind(self.session_pub_key.as_slice())
+            .bind(self.session_hw_id.as_slice())
+    }
+    fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<AccountEventData> {
+        Ok(AccountEventData {
+            event: row
+                .try_get("event")
+                .map_err(|err| anyhow!("Failed get column event: {err}"))?,
+            create_time: row
+                .try_get("create_time")
+                .map_err(|err| anyhow!("Failed get column create_time: {err}"))?,
+            client_ip: row
+                .try_get("client_ip")
+                .map_err(|err| anyhow!("Failed get column client_ip: {err}"))?,
+            hw_id_hash: row
+                .try_get("hw_id_hash")
+                .map_err(|err| anyhow!("Failed get column hw_id_hash: {err}"))?,
+            credential_type: row
+                .try_get("credential_type")
+                .map_err(|err| anyhow!("Failed get column credential_type: {err}"))?,
+        })
+    }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<AccountEventData> {
+        Ok(AccountEventData {
+            event: row
+                .try_get("event")
+                .map_err(|err| anyhow!("Failed get column event: {err}"))?,
+            create_time: row
+                .try_get("create_time")
+                .map_err(|err| anyhow!("Failed get column create_time: {err}"))?,
+            client_ip: row
+                .try_get("client_ip")
+                .map_err(|err| anyhow!("Failed get column client_ip: {err}"))?,
+            hw_id_hash: row
+                .try_get("hw_id_hash")
+                .map_err(|err| anyhow!("Failed get column hw_id_hash: {err}"))?,
+            credential_type: row
+                .try_get("credential_type")
+                .map_err(|err| anyhow!("Failed get column credential_type: {err}"))?,
+        })
+    }
+}
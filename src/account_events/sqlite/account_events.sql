@@ -0,0 +1,16 @@
+SELECT
+    account_events.event,
+    account_events.create_time,
+    account_events.client_ip,
+    account_events.hw_id_hash,
+    account_events.credential_type
+FROM
+    account_events
+    INNER JOIN user_session ON user_session.account_id = account_events.account_id
+WHERE
+    user_session.pub_key = ?
+    AND user_session.hw_id = ?
+ORDER BY
+    account_events.id DESC
+LIMIT
+    50;
@@ -0,0 +1,11 @@
+INSERT INTO
+    account_events (
+        account_id,
+        event,
+        create_time,
+        client_ip,
+        hw_id_hash,
+        credential_type
+    )
+VALUES
+    (?, ?, datetime('now'), ?, ?, ?);
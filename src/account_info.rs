@@ -6,28 +6,29 @@ use axum::Json;
 use ddnet_account_sql::{any::AnyPool, query::Query};
 use ddnet_accounts_shared::{
     account_server::{
-        account_info::{AccountInfoResponse, CredentialType},
-        errors::{AccountServerRequestError, Empty},
+        account_info::{AccountInfoError, AccountInfoResponse, CredentialType},
+        errors::RequestFailure,
         result::AccountServerReqResult,
     },
     client::account_info::AccountInfoRequest,
 };
 use queries::AccountInfo;
 
-use crate::shared::{Shared, CERT_MAX_AGE_DELTA, CERT_MIN_AGE_DELTA};
+use crate::{
+    request_error::request_logic_error,
+    shared::{Shared, CERT_MAX_AGE_DELTA, CERT_MIN_AGE_DELTA},
+};
 
 pub async fn account_info_request(
     shared: Arc<Shared>,
     pool: AnyPool,
     Json(data): Json<AccountInfoRequest>,
-) -> Json<AccountServerReqResult<AccountInfoResponse, Empty>> {
-    Json(account_info(shared, pool, data).await.map_err(|err| {
-        AccountServerRequestError::Unexpected {
-            target: "account_info".into(),
-            err: err.to_string(),
-            bt: err.backtrace().to_string(),
-        }
-    }))
+) -> Json<AccountServerReqResult<AccountInfoResponse, AccountInfoError>> {
+    Json(
+        account_info(shared, pool, data)
+            .await
+            .map_err(|err| request_logic_error("account_info", err)),
+    )
 }
 
 pub async fn account_info(
@@ -37,12 +38,13 @@ pub async fn account_info(
 ) -> anyhow::Result<AccountInfoResponse> {
     data.account_data
         .public_key
-        .verify_strict(data.time_stamp.to_string().as_bytes(), &data.signature)?;
+        .verify_strict(data.time_stamp.to_string().as_bytes(), &data.signature)
+        .map_err(|_| AccountInfoError::InvalidSignature)?;
     let now = chrono::Utc::now();
     let delta = now.signed_duration_since(data.time_stamp);
     anyhow::ensure!(
         delta < CERT_MAX_AGE_DELTA && delta > CERT_MIN_AGE_DELTA,
-        "time stamp was not in a valid time frame."
+        RequestFailure::TimestampOutOfWindow
     );
 
     let mut connection = pool.acquire().await?;
@@ -56,8 +58,9 @@ pub async fn account_info(
 
     let row = qry
         .query(&shared.db.account_info)
-        .fetch_one(&mut connection)
-        .await?;
+        .fetch_optional(&mut connection)
+        .await?
+        .ok_or(AccountInfoError::SessionInvalid)?;
 
     let account_info = AccountInfo::row_data(&row)?;
     Ok(AccountInfoResponse {
@@ -28,6 +28,13 @@ impl Query<AccountInfoData> for AccountInfo<'_> {
             .prepare(include_str!("mysql/account_info.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/account_info.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
@@ -37,6 +44,15 @@ impl Query<AccountInfoData> for AccountInfo<'_> {
             .bind(self.session_pub_key.as_slice())
             .bind(self.session_hw_id.as_slice())
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement
+            .query()
+            .bind(self.session_pub_key.as_slice())
+            .bind(self.session_hw_id.as_slice())
+    }
     fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<AccountInfoData> {
         Ok(AccountInfoData {
             account_id: row
@@ -53,4 +69,20 @@ impl Query<AccountInfoData> for AccountInfo<'_> {
                 .map_err(|err| anyhow!("Failed get column linked_steam: {err}"))?,
         })
     }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<AccountInfoData> {
+        Ok(AccountInfoData {
+            account_id: row
+                .try_get("account_id")
+                .map_err(|err| anyhow!("Failed get column account_id: {err}"))?,
+            creation_date: row
+                .try_get("creation_date")
+                .map_err(|err| anyhow!("Failed get column creation_date: {err}"))?,
+            linked_email: row
+                .try_get("linked_email")
+                .map_err(|err| anyhow!("Failed get column linked_email: {err}"))?,
+            linked_steam: row
+                .try_get("linked_steam")
+                .map_err(|err| anyhow!("Failed get column linked_steam: {err}"))?,
+        })
+    }
 }
@@ -9,13 +9,14 @@ use ddnet_accounts_shared::{
         account_token::AccountTokenError, errors::AccountServerRequestError, otp::generate_otp,
         result::AccountServerReqResult,
     },
-    client::account_token::{
-        AccountTokenEmailRequest, AccountTokenOperation, AccountTokenSteamRequest,
-    },
+    client::account_token::{AccountTokenEmailRequest, AccountTokenSteamRequest},
 };
 use queries::{AddAccountTokenEmail, AddAccountTokenSteam};
 
-use crate::shared::Shared;
+use crate::{
+    email_outbox::queue_email, email_templates::EmailKind, request_error::request_error,
+    secret_verifier::check_secret_key, shared::Shared,
+};
 
 pub async fn account_token_email(
     shared: Arc<Shared>,
@@ -23,22 +24,21 @@ pub async fn account_token_email(
     requires_secret: bool,
     Json(data): Json<AccountTokenEmailRequest>,
 ) -> Json<AccountServerReqResult<(), AccountTokenError>> {
-    // After this check a validation process could be added
-    if requires_secret && data.secret_key.is_none() {
-        return Json(AccountServerReqResult::Err(
-            AccountServerRequestError::Other(
-                "This function is only for requests with a secret verification token.".to_string(),
-            ),
-        ));
+    match check_secret_key(&shared, requires_secret, data.secret_key.as_ref()).await {
+        Ok(None) => {}
+        Ok(Some(url)) => {
+            return Json(AccountServerReqResult::Err(
+                AccountServerRequestError::LogicError(
+                    AccountTokenError::WebValidationProcessNeeded { url },
+                ),
+            ));
+        }
+        Err(err) => return Json(AccountServerReqResult::Err(err)),
     }
     Json(
         account_token_email_impl(shared, pool, data)
             .await
-            .map_err(|err| AccountServerRequestError::Unexpected {
-                target: "account_token".into(),
-                err: err.to_string(),
-                bt: err.backtrace().to_string(),
-            }),
+            .map_err(|err| request_error("account_token", err)),
     )
 }
 
@@ -64,40 +64,43 @@ pub async fn account_token_email_impl(
             == Some(data.email.as_str()),
         "Email must only contain email part with name & domain (name@example.com)"
     );
+    shared.token_limiter.check_email(&data.email)?;
 
     // Add a account token and send it by email
     let token = generate_otp();
     let token_hex = hex::encode(token);
-    let query_add_account_token = AddAccountTokenEmail {
-        token: &token,
-        email: &data.email,
-        ty: &data.op,
-    };
-    let mut connection = pool.acquire().await?;
-    let mut con = connection.acquire().await?;
-
-    let account_token_res = query_add_account_token
-        .query(&shared.db.account_token_email_statement)
-        .execute(&mut con)
-        .await?;
-    anyhow::ensure!(
-        account_token_res.rows_affected() >= 1,
-        "No account token could be added."
+    let mail = shared.email.render(
+        EmailKind::AccountToken(data.op),
+        data.locale.as_deref(),
+        &[
+            ("%SUBJECT%", data.email.local_part()),
+            ("%CODE%", &token_hex),
+        ],
     );
+    let mut connection = pool.acquire().await?;
+    let mut connection = connection.acquire().await?;
 
-    let header = match data.op {
-        AccountTokenOperation::LogoutAll => "DDNet Logout All Sessions",
-        AccountTokenOperation::LinkCredential => "DDNet Link Credential",
-        AccountTokenOperation::Delete => "DDNet Delete Account",
-    };
+    // the token is only added if the email is queued
+    connection
+        .transaction(|mut connection| {
+            Box::pin(async move {
+                let query_add_account_token = AddAccountTokenEmail {
+                    token: &token,
+                    email: &data.email,
+                    ty: &data.op,
+                };
+                let account_token_res = query_add_account_token
+                    .query(&shared.db.account_token_email_statement)
+                    .execute(&mut connection.con())
+                    .await?;
+                anyhow::ensure!(
+                    account_token_res.rows_affected() >= 1,
+                    "No account token could be added."
+                );
 
-    let mail = shared.account_tokens_email.read().clone();
-    let mail = mail
-        .replace("%SUBJECT%", data.email.local_part())
-        .replace("%CODE%", &token_hex);
-    shared
-        .email
-        .send_email(data.email.as_str(), header, mail)
+                queue_email(&shared, &mut connection.con(), data.email.as_str(), &mail).await
+            })
+        })
         .await?;
 
     Ok(())
@@ -109,22 +112,21 @@ pub async fn account_token_steam(
     requires_secret: bool,
     Json(data): Json<AccountTokenSteamRequest>,
 ) -> Json<AccountServerReqResult<String, AccountTokenError>> {
-    // After this check a validation process could be added
-    if requires_secret && data.secret_key.is_none() {
-        return Json(AccountServerReqResult::Err(
-            AccountServerRequestError::Other(
-                "This function is only for requests with a secret verification token.".to_string(),
-            ),
-        ));
+    match check_secret_key(&shared, requires_secret, data.secret_key.as_ref()).await {
+        Ok(None) => {}
+        Ok(Some(url)) => {
+            return Json(AccountServerReqResult::Err(
+                AccountServerRequestError::LogicError(
+                    AccountTokenError::WebValidationProcessNeeded { url },
+                ),
+            ));
+        }
+        Err(err) => return Json(AccountServerReqResult::Err(err)),
     }
     Json(
         account_token_steam_impl(shared, pool, data)
             .await
-            .map_err(|err| AccountServerRequestError::Unexpected {
-                target: "account_token".into(),
-                err: err.to_string(),
-                bt: err.backtrace().to_string(),
-            }),
+            .map_err(|err| request_error("account_token", err)),
     )
 }
 
@@ -138,12 +140,15 @@ pub async fn account_token_steam_impl(
         "Steam session auth ticket must not be bigger than 1024 bytes."
     );
 
+    let steamid64 = shared.steam.verify_steamid64(data.steam_ticket).await?;
+    shared.token_limiter.check_steam(steamid64)?;
+
     // Add a account token and send it by steam
     let token = generate_otp();
     let token_hex = hex::encode(token);
     let query_add_account_token = AddAccountTokenSteam {
         token: &token,
-        steamid64: &shared.steam.verify_steamid64(data.steam_ticket).await?,
+        steamid64: &steamid64,
         ty: &data.op,
     };
     let mut connection = pool.acquire().await?;
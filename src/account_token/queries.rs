@@ -27,6 +27,13 @@ impl Query<()> for AddAccountTokenEmail<'_> {
             .prepare(include_str!("mysql/add_account_token_email.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/add_account_token_email.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
@@ -38,9 +45,23 @@ impl Query<()> for AddAccountTokenEmail<'_> {
             .bind(self.email.as_str())
             .bind(ty)
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        let ty: &'static str = self.ty.into();
+        statement
+            .query()
+            .bind(self.token.as_slice())
+            .bind(self.email.as_str())
+            .bind(ty)
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
 
 #[derive(Debug)]
@@ -59,6 +80,13 @@ impl Query<()> for AddAccountTokenSteam<'_> {
             .prepare(include_str!("mysql/add_account_token_steam.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/add_account_token_steam.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
@@ -70,9 +98,23 @@ impl Query<()> for AddAccountTokenSteam<'_> {
             .bind(self.steamid64)
             .bind(ty)
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        let ty: &'static str = self.ty.into();
+        statement
+            .query()
+            .bind(self.token.as_slice())
+            .bind(self.steamid64)
+            .bind(ty)
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
 
 pub struct AccountTokenQry<'a> {
@@ -93,12 +135,25 @@ impl Query<AccountTokenData> for AccountTokenQry<'_> {
             .prepare(include_str!("mysql/account_token_data.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/account_token_data.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query().bind(self.token.as_slice())
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.token.as_slice())
+    }
     fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<AccountTokenData> {
         Ok(AccountTokenData {
             account_id: row
@@ -110,6 +165,17 @@ impl Query<AccountTokenData> for AccountTokenQry<'_> {
             )?,
         })
     }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<AccountTokenData> {
+        Ok(AccountTokenData {
+            account_id: row
+                .try_get("account_id")
+                .map_err(|err| anyhow!("Failed get column account_id: {err}"))?,
+            ty: AccountTokenType::from_str(
+                row.try_get("ty")
+                    .map_err(|err| anyhow!("Failed get column ty: {err}"))?,
+            )?,
+        })
+    }
 }
 
 pub struct InvalidateAccountToken<'a> {
@@ -125,13 +191,29 @@ impl Query<()> for InvalidateAccountToken<'_> {
             .prepare(include_str!("mysql/invalidate_account_token.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/invalidate_account_token.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query().bind(self.token.as_slice())
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.token.as_slice())
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
@@ -0,0 +1,8 @@
+SELECT
+    account_tokens.account_id,
+    account_tokens.ty
+FROM
+    account_tokens
+WHERE
+    account_tokens.token = ?
+    AND account_tokens.valid_until > datetime('now');
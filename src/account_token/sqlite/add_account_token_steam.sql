@@ -0,0 +1,28 @@
+INSERT INTO
+    account_tokens (
+        token,
+        valid_until,
+        account_id,
+        ty
+    )
+VALUES
+    (
+        ?,
+        datetime('now', '+15 minutes'),
+        (
+            SELECT
+                id
+            FROM
+                account
+            WHERE
+                id = (
+                    SELECT
+                        account_id
+                    FROM
+                        credential_steam
+                    WHERE
+                        steamid64 = ?
+                )
+        ),
+        ?
+    );
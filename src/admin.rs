@@ -0,0 +1,576 @@
+pub mod queries;
+
+use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header::AUTHORIZATION, Request, StatusCode},
+    middleware::Next,
+    response::Response,
+    Extension, Json, Router,
+};
+use ddnet_account_sql::{any::AnyPool, query::Query};
+use ddnet_accounts_shared::account_server::{errors::Empty, result::AccountServerReqResult};
+use ddnet_accounts_types::account_id::AccountId;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto,
+    service::TowerToHyperService,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::net::{TcpListener, TcpSocket, UnixListener};
+
+use crate::{
+    delete::queries::RemoveAccount,
+    link_credential::queries::{UnlinkCredentialEmail, UnlinkCredentialSteam},
+    login::queries::{AccountIdFromEmail, AccountIdFromSteam},
+    logout_all::queries::RemoveSessionsExcept,
+    request_error::request_error,
+    request_log::{request_log_layer, RequestLog},
+    shared::Shared,
+    shutdown::Shutdown,
+};
+
+use self::queries::{AddBan, AdminAccountInfo, RemoveAccountTokens, RemoveBan};
+
+/// Where the admin api listens for requests.
+/// It should never be reachable from the public internet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AdminListen {
+    /// A tcp port on localhost.
+    Port { port: u16 },
+    /// A unix domain socket, the file is replaced on startup.
+    UnixSocket { unix_socket: PathBuf },
+}
+
+/// A key that authorizes requests to the admin api.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminKey {
+    /// A name to identify who used the key, e.g. in logs.
+    pub name: String,
+    /// The secret that must be sent as bearer token.
+    pub key: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminDetails {
+    pub listen: AdminListen,
+    pub keys: Vec<AdminKey>,
+}
+
+/// The account data as seen by the admin api.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminAccountInfoResponse {
+    pub account_id: AccountId,
+    pub creation_date: chrono::DateTime<chrono::Utc>,
+    pub email: Option<String>,
+    pub steamid64: Option<i64>,
+    pub ban: Option<AdminAccountBan>,
+}
+
+/// The ban of an account, including expired bans.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminAccountBan {
+    /// The UTC date since when the account is banned.
+    pub since: chrono::DateTime<chrono::Utc>,
+    pub reason: String,
+    /// The name of the admin key that issued the ban.
+    pub issuer: String,
+    /// The UTC date until the ban lasts, `None` if permanent.
+    pub expires: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminBanRequest {
+    pub banned: bool,
+    /// Shown to the banned user.
+    #[serde(default)]
+    pub reason: String,
+    /// The UTC date until the ban lasts, `None` if permanent.
+    #[serde(default)]
+    pub expires: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// The admin key that authorized the current request.
+#[derive(Debug, Clone)]
+pub struct AdminIssuer(pub String);
+
+/// Which credential to unlink from an account.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdminCredential {
+    Email,
+    Steam,
+}
+
+pub enum AdminListener {
+    Tcp(TcpListener),
+    Unix(UnixListener),
+}
+
+struct AdminKeys {
+    /// Name & sha256 of the key, so comparing the keys does not leak
+    /// anything about their content.
+    keys: Vec<(String, [u8; 32])>,
+}
+
+impl AdminKeys {
+    fn find(&self, key: &str) -> Option<&str> {
+        let hash: [u8; 32] = Sha256::digest(key.as_bytes()).into();
+        self.keys
+            .iter()
+            .find(|(_, key_hash)| *key_hash == hash)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+async fn admin_auth_layer(
+    State(keys): State<Arc<AdminKeys>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<Response<Body>, StatusCode> {
+    let name = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|val| val.to_str().ok())
+        .and_then(|val| val.strip_prefix("Bearer "))
+        .and_then(|key| keys.find(key))
+        .ok_or(StatusCode::UNAUTHORIZED)?
+        .to_string();
+
+    log::info!(target: "admin", "{} {} by {}", req.method(), req.uri().path(), name);
+    req.extensions_mut().insert(AdminIssuer(name));
+    Ok(next.run(req).await)
+}
+
+fn map_err<T>(target: &str, res: anyhow::Result<T>) -> Json<AccountServerReqResult<T, Empty>> {
+    Json(res.map_err(|err| request_error(target, err)))
+}
+
+async fn account_info(
+    shared: &Shared,
+    pool: &AnyPool,
+    account_id: AccountId,
+) -> anyhow::Result<Option<AdminAccountInfoResponse>> {
+    let mut connection = pool.acquire().await?;
+    let mut connection = connection.acquire().await?;
+
+    let qry = AdminAccountInfo {
+        account_id: &account_id,
+    };
+    let row = qry
+        .query(&shared.db.admin_account_info_statement)
+        .fetch_optional(&mut connection)
+        .await?;
+
+    row.map(|row| {
+        let info = AdminAccountInfo::row_data(&row)?;
+        anyhow::Ok(AdminAccountInfoResponse {
+            account_id: info.account_id,
+            creation_date: info.creation_date,
+            email: info.linked_email,
+            steamid64: info.linked_steam,
+            ban: info.ban_time.map(|since| AdminAccountBan {
+                since,
+                reason: info.ban_reason.unwrap_or_default(),
+                issuer: info.ban_issuer.unwrap_or_default(),
+                expires: info.ban_expire_time,
+            }),
+        })
+    })
+    .transpose()
+}
+
+pub async fn admin_account_by_id(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Path(account_id): Path<AccountId>,
+) -> Json<AccountServerReqResult<Option<AdminAccountInfoResponse>, Empty>> {
+    map_err(
+        "admin_account_by_id",
+        account_info(&shared, &pool, account_id).await,
+    )
+}
+
+pub async fn admin_account_by_email(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Path(email): Path<String>,
+) -> Json<AccountServerReqResult<Option<AdminAccountInfoResponse>, Empty>> {
+    map_err(
+        "admin_account_by_email",
+        async {
+            let email = email_address::EmailAddress::from_str(&email)?;
+            let account_id = {
+                let mut connection = pool.acquire().await?;
+                let mut connection = connection.acquire().await?;
+
+                let qry = AccountIdFromEmail { email: &email };
+                let row = qry
+                    .query(&shared.db.account_id_from_email_qry_statement)
+                    .fetch_optional(&mut connection)
+                    .await?;
+                row.map(|row| AccountIdFromEmail::row_data(&row))
+                    .transpose()?
+            };
+            match account_id {
+                Some(account) => account_info(&shared, &pool, account.account_id).await,
+                None => Ok(None),
+            }
+        }
+        .await,
+    )
+}
+
+pub async fn admin_account_by_steam(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Path(steamid64): Path<i64>,
+) -> Json<AccountServerReqResult<Option<AdminAccountInfoResponse>, Empty>> {
+    map_err(
+        "admin_account_by_steam",
+        async {
+            let account_id = {
+                let mut connection = pool.acquire().await?;
+                let mut connection = connection.acquire().await?;
+
+                let qry = AccountIdFromSteam {
+                    steamid64: &steamid64,
+                };
+                let row = qry
+                    .query(&shared.db.account_id_from_steam_qry_statement)
+                    .fetch_optional(&mut connection)
+                    .await?;
+                row.map(|row| AccountIdFromSteam::row_data(&row))
+                    .transpose()?
+            };
+            match account_id {
+                Some(account) => account_info(&shared, &pool, account.account_id).await,
+                None => Ok(None),
+            }
+        }
+        .await,
+    )
+}
+
+pub async fn admin_logout_all(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Path(account_id): Path<AccountId>,
+) -> Json<AccountServerReqResult<(), Empty>> {
+    map_err(
+        "admin_logout_all",
+        async {
+            let mut connection = pool.acquire().await?;
+            let mut connection = connection.acquire().await?;
+
+            let qry = RemoveSessionsExcept {
+                account_id: &account_id,
+                session_data: &None,
+            };
+            qry.query(&shared.db.remove_sessions_except_statement)
+                .execute(&mut connection)
+                .await?;
+
+            anyhow::Ok(())
+        }
+        .await,
+    )
+}
+
+/// Unlike the user facing unlink, this also allows to unlink
+/// the last credential of an account.
+pub async fn admin_unlink_credential(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Path((account_id, credential)): Path<(AccountId, AdminCredential)>,
+) -> Json<AccountServerReqResult<(), Empty>> {
+    map_err(
+        "admin_unlink_credential",
+        async {
+            let mut connection = pool.acquire().await?;
+            let mut connection = connection.acquire().await?;
+
+            let affected_rows = match credential {
+                AdminCredential::Email => {
+                    let qry = UnlinkCredentialEmail {
+                        account_id: &account_id,
+                    };
+                    qry.query(&shared.db.unlink_credential_email_statement)
+                        .execute(&mut connection)
+                        .await?
+                        .rows_affected()
+                }
+                AdminCredential::Steam => {
+                    let qry = UnlinkCredentialSteam {
+                        account_id: &account_id,
+                    };
+                    qry.query(&shared.db.unlink_credential_steam_statement)
+                        .execute(&mut connection)
+                        .await?
+                        .rows_affected()
+                }
+            };
+            anyhow::ensure!(affected_rows > 0, "No credential was unlinked.");
+
+            anyhow::Ok(())
+        }
+        .await,
+    )
+}
+
+pub async fn admin_delete(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Path(account_id): Path<AccountId>,
+) -> Json<AccountServerReqResult<(), Empty>> {
+    map_err(
+        "admin_delete",
+        async {
+            let mut connection = pool.acquire().await?;
+            let mut connection = connection.acquire().await?;
+
+            connection
+                .transaction(|mut connection| {
+                    Box::pin(async move {
+                        // remove all sessions
+                        let qry = RemoveSessionsExcept {
+                            account_id: &account_id,
+                            session_data: &None,
+                        };
+                        qry.query(&shared.db.remove_sessions_except_statement)
+                            .execute(&mut connection.con())
+                            .await?;
+
+                        // Unlink all credentials
+                        let qry = UnlinkCredentialEmail {
+                            account_id: &account_id,
+                        };
+                        qry.query(&shared.db.unlink_credential_email_statement)
+                            .execute(&mut connection.con())
+                            .await?;
+
+                        let qry = UnlinkCredentialSteam {
+                            account_id: &account_id,
+                        };
+                        qry.query(&shared.db.unlink_credential_steam_statement)
+                            .execute(&mut connection.con())
+                            .await?;
+
+                        // pending account tokens & bans reference the account
+                        let qry = RemoveAccountTokens {
+                            account_id: &account_id,
+                        };
+                        qry.query(&shared.db.remove_account_tokens_statement)
+                            .execute(&mut connection.con())
+                            .await?;
+
+                        let qry = RemoveBan {
+                            account_id: &account_id,
+                        };
+                        qry.query(&shared.db.remove_ban_statement)
+                            .execute(&mut connection.con())
+                            .await?;
+
+                        // delete account
+                        let qry = RemoveAccount {
+                            account_id: &account_id,
+                        };
+                        let affected_rows = qry
+                            .query(&shared.db.remove_account_statement)
+                            .execute(&mut connection.con())
+                            .await?
+                            .rows_affected();
+                        anyhow::ensure!(affected_rows > 0, "Account was not found.");
+
+                        anyhow::Ok(())
+                    })
+                })
+                .await
+        }
+        .await,
+    )
+}
+
+pub async fn admin_ban(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Path(account_id): Path<AccountId>,
+    Extension(issuer): Extension<AdminIssuer>,
+    Json(data): Json<AdminBanRequest>,
+) -> Json<AccountServerReqResult<(), Empty>> {
+    map_err(
+        "admin_ban",
+        async {
+            anyhow::ensure!(
+                account_info(&shared, &pool, account_id).await?.is_some(),
+                "Account was not found."
+            );
+
+            let mut connection = pool.acquire().await?;
+            let mut connection = connection.acquire().await?;
+
+            if data.banned {
+                let qry = AddBan {
+                    account_id: &account_id,
+                    reason: &data.reason,
+                    issuer: &issuer.0,
+                    expire_time: &data.expires,
+                };
+                qry.query(&shared.db.add_ban_statement)
+                    .execute(&mut connection)
+                    .await?;
+            } else {
+                let qry = RemoveBan {
+                    account_id: &account_id,
+                };
+                qry.query(&shared.db.remove_ban_statement)
+                    .execute(&mut connection)
+                    .await?;
+            }
+
+            anyhow::Ok(())
+        }
+        .await,
+    )
+}
+
+pub async fn prepare_admin_listener(listen: &AdminListen) -> anyhow::Result<AdminListener> {
+    Ok(match listen {
+        AdminListen::Port { port } => {
+            let tcp_socket = TcpSocket::new_v4()?;
+            tcp_socket.set_reuseaddr(true)?;
+            tcp_socket.bind(format!("127.0.0.1:{}", port).parse()?)?;
+            AdminListener::Tcp(tcp_socket.listen(1024)?)
+        }
+        AdminListen::UnixSocket { unix_socket } => {
+            // a left over socket of a previous run
+            let _ = tokio::fs::remove_file(unix_socket).await;
+            AdminListener::Unix(UnixListener::bind(unix_socket)?)
+        }
+    })
+}
+
+pub fn prepare_admin_router(
+    details: &AdminDetails,
+    shared: &Arc<Shared>,
+    pool: &AnyPool,
+) -> Router {
+    let keys = Arc::new(AdminKeys {
+        keys: details
+            .keys
+            .iter()
+            .map(|key| (key.name.clone(), Sha256::digest(key.key.as_bytes()).into()))
+            .collect(),
+    });
+
+    let mut app = Router::new();
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.route(
+        "/account/:account_id",
+        axum::routing::get(move |path: Path<_>| {
+            admin_account_by_id(shared_clone, pool_clone, path)
+        }),
+    );
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.route(
+        "/account/email/:email",
+        axum::routing::get(move |path: Path<_>| {
+            admin_account_by_email(shared_clone, pool_clone, path)
+        }),
+    );
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.route(
+        "/account/steam/:steamid64",
+        axum::routing::get(move |path: Path<_>| {
+            admin_account_by_steam(shared_clone, pool_clone, path)
+        }),
+    );
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.route(
+        "/account/:account_id/logout-all",
+        axum::routing::post(move |path: Path<_>| admin_logout_all(shared_clone, pool_clone, path)),
+    );
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.route(
+        "/account/:account_id/unlink/:credential",
+        axum::routing::post(move |path: Path<_>| {
+            admin_unlink_credential(shared_clone, pool_clone, path)
+        }),
+    );
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.route(
+        "/account/:account_id/delete",
+        axum::routing::post(move |path: Path<_>| admin_delete(shared_clone, pool_clone, path)),
+    );
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.route(
+        "/account/:account_id/ban",
+        axum::routing::post(move |path: Path<_>, issuer: Extension<_>, qry: Json<_>| {
+            admin_ban(shared_clone, pool_clone, path, issuer, qry)
+        }),
+    );
+
+    app.layer(axum::middleware::from_fn_with_state(keys, admin_auth_layer))
+        // also logs the rejected requests
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(RequestLog::new(false)),
+            request_log_layer,
+        ))
+}
+
+/// Serves the admin api until the shutdown, finishing the in-flight requests.
+pub async fn run_admin(
+    listener: AdminListener,
+    app: Router,
+    shutdown: Shutdown,
+) -> anyhow::Result<()> {
+    match listener {
+        AdminListener::Tcp(listener) => {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async move { shutdown.wait().await })
+                .await?
+        }
+        AdminListener::Unix(listener) => {
+            let mut connections = tokio::task::JoinSet::new();
+            loop {
+                // axum can only serve tcp listeners directly
+                let (socket, _) = tokio::select! {
+                    res = listener.accept() => res?,
+                    _ = shutdown.wait() => break,
+                };
+                let service = TowerToHyperService::new(app.clone());
+                let shutdown = shutdown.clone();
+                connections.spawn(async move {
+                    let builder = auto::Builder::new(TokioExecutor::new());
+                    let conn = builder.serve_connection(TokioIo::new(socket), service);
+                    tokio::pin!(conn);
+                    let res = tokio::select! {
+                        res = conn.as_mut() => res,
+                        _ = shutdown.wait() => {
+                            conn.as_mut().graceful_shutdown();
+                            conn.await
+                        }
+                    };
+                    if let Err(err) = res {
+                        log::debug!(target: "admin", "connection failed: {err}");
+                    }
+                });
+                // forget finished connections
+                while connections.try_join_next().is_some() {}
+            }
+            while connections.join_next().await.is_some() {}
+        }
+    }
+    Ok(())
+}
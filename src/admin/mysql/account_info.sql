@@ -0,0 +1,16 @@
+SELECT
+    account.id AS account_id,
+    account.create_time AS creation_date,
+    credential_email.email AS linked_email,
+    credential_steam.steamid64 AS linked_steam,
+    account_ban.create_time AS ban_time,
+    account_ban.reason AS ban_reason,
+    account_ban.issuer AS ban_issuer,
+    account_ban.expire_time AS ban_expire_time
+FROM
+    account
+    LEFT JOIN credential_email ON credential_email.account_id = account.id
+    LEFT JOIN credential_steam ON credential_steam.account_id = account.id
+    LEFT JOIN account_ban ON account_ban.account_id = account.id
+WHERE
+    account.id = ?;
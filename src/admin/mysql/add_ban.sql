@@ -0,0 +1,14 @@
+INSERT INTO
+    account_ban (
+        account_id,
+        create_time,
+        reason,
+        issuer,
+        expire_time
+    )
+VALUES
+    (?, UTC_TIMESTAMP(), ?, ?, ?) ON DUPLICATE KEY
+UPDATE
+    reason = VALUES(reason),
+    issuer = VALUES(issuer),
+    expire_time = VALUES(expire_time);
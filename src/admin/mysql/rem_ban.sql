@@ -0,0 +1,4 @@
+DELETE FROM
+    account_ban
+WHERE
+    account_id = ?;
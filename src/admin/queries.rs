@@ -0,0 +1,242 @@
+use anyhow::anyhow;
+use axum::async_trait;
+use ddnet_account_sql::query::Query;
+use ddnet_accounts_types::account_id::AccountId;
+use sqlx::Executor;
+use sqlx::Row;
+use sqlx::Statement;
+
+pub struct AdminAccountInfo<'a> {
+    pub account_id: &'a AccountId,
+}
+
+pub struct AdminAccountInfoData {
+    pub account_id: AccountId,
+    pub creation_date: sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    pub linked_email: Option<String>,
+    pub linked_steam: Option<i64>,
+    pub ban_time: Option<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>,
+    pub ban_reason: Option<String>,
+    pub ban_issuer: Option<String>,
+    pub ban_expire_time: Option<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>,
+}
+
+#[async_trait]
+impl Query<AdminAccountInfoData> for AdminAccountInfo<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/account_info.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/account_info.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement.query().bind(self.account_id)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.account_id)
+    }
+    fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<AdminAccountInfoData> {
+        Ok(AdminAccountInfoData {
+            account_id: row
+                .try_get("account_id")
+                .map_err(|err| anyhow!("Failed get column account_id: {err}"))?,
+            creation_date: row
+                .try_get("creation_date")
+                .map_err(|err| anyhow!("Failed get column creation_date: {err}"))?,
+            linked_email: row
+                .try_get("linked_email")
+                .map_err(|err| anyhow!("Failed get column linked_email: {err}"))?,
+            linked_steam: row
+                .try_get("linked_steam")
+                .map_err(|err| anyhow!("Failed get column linked_steam: {err}"))?,
+            ban_time: row
+                .try_get("ban_time")
+                .map_err(|err| anyhow!("Failed get column ban_time: {err}"))?,
+            ban_reason: row
+                .try_get("ban_reason")
+                .map_err(|err| anyhow!("Failed get column ban_reason: {err}"))?,
+            ban_issuer: row
+                .try_get("ban_issuer")
+                .map_err(|err| anyhow!("Failed get column ban_issuer: {err}"))?,
+            ban_expire_time: row
+                .try_get("ban_expire_time")
+                .map_err(|err| anyhow!("Failed get column ban_expire_time: {err}"))?,
+        })
+    }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<AdminAccountInfoData> {
+        Ok(AdminAccountInfoData {
+            account_id: row
+                .try_get("account_id")
+                .map_err(|err| anyhow!("Failed get column account_id: {err}"))?,
+            creation_date: row
+                .try_get("creation_date")
+                .map_err(|err| anyhow!("Failed get column creation_date: {err}"))?,
+            linked_email: row
+                .try_get("linked_email")
+                .map_err(|err| anyhow!("Failed get column linked_email: {err}"))?,
+            linked_steam: row
+                .try_get("linked_steam")
+                .map_err(|err| anyhow!("Failed get column linked_steam: {err}"))?,
+            ban_time: row
+                .try_get("ban_time")
+                .map_err(|err| anyhow!("Failed get column ban_time: {err}"))?,
+            ban_reason: row
+                .try_get("ban_reason")
+                .map_err(|err| anyhow!("Failed get column ban_reason: {err}"))?,
+            ban_issuer: row
+                .try_get("ban_issuer")
+                .map_err(|err| anyhow!("Failed get column ban_issuer: {err}"))?,
+            ban_expire_time: row
+                .try_get("ban_expire_time")
+                .map_err(|err| anyhow!("Failed get column ban_expire_time: {err}"))?,
+        })
+    }
+}
+
+pub struct AddBan<'a> {
+    pub account_id: &'a AccountId,
+    pub reason: &'a str,
+    pub issuer: &'a str,
+    pub expire_time: &'a Option<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>,
+}
+
+#[async_trait]
+impl Query<()> for AddBan<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/add_ban.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/add_ban.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement
+            .query()
+            .bind(self.account_id)
+            .bind(self.reason)
+            .bind(self.issuer)
+            .bind(self.expire_time)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement
+            .query()
+            .bind(self.account_id)
+            .bind(self.reason)
+            .bind(self.issuer)
+            // sqlite compares the dates as strings
+            .bind(self.expire_time.map(|time| time.naive_utc()))
+    }
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+}
+
+pub struct RemoveBan<'a> {
+    pub account_id: &'a AccountId,
+}
+
+#[async_trait]
+impl Query<()> for RemoveBan<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/rem_ban.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/rem_ban.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement.query().bind(self.account_id)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.account_id)
+    }
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+}
+
+pub struct RemoveAccountTokens<'a> {
+    pub account_id: &'a AccountId,
+}
+
+#[async_trait]
+impl Query<()> for RemoveAccountTokens<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/rem_account_tokens.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/rem_account_tokens.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement.query().bind(self.account_id)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.account_id)
+    }
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+}
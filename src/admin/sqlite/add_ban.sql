@@ -0,0 +1,15 @@
+INSERT INTO
+    account_ban (
+        account_id,
+        create_time,
+        reason,
+        issuer,
+        expire_time
+    )
+VALUES
+    (?, datetime('now'), ?, ?, ?) ON CONFLICT(account_id) DO
+UPDATE
+SET
+    reason = excluded.reason,
+    issuer = excluded.issuer,
+    expire_time = excluded.expire_time;
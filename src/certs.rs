@@ -5,10 +5,7 @@ use std::{str::FromStr, sync::Arc, time::Duration};
 use anyhow::anyhow;
 use axum::Json;
 use ddnet_account_sql::{any::AnyPool, query::Query};
-use ddnet_accounts_shared::account_server::{
-    errors::{AccountServerRequestError, Empty},
-    result::AccountServerReqResult,
-};
+use ddnet_accounts_shared::account_server::{errors::Empty, result::AccountServerReqResult};
 use der::{Decode, Encode};
 use p256::ecdsa::{DerSignature, SigningKey};
 use queries::{AddCert, GetCerts};
@@ -21,7 +18,7 @@ use x509_cert::{
     time::Validity,
 };
 
-use crate::{db::DbConnectionShared, shared::Shared};
+use crate::{db::DbConnectionShared, request_error::request_error, shared::Shared};
 
 #[derive(Debug, Clone)]
 pub struct PrivateKeys {
@@ -31,6 +28,23 @@ pub struct PrivateKeys {
     pub next_cert: x509_cert::Certificate,
 }
 
+impl PrivateKeys {
+    /// Checks that every key belongs to its certificate.
+    pub fn check(&self) -> anyhow::Result<()> {
+        for (name, key, cert) in [
+            ("current", &self.current_key, &self.current_cert),
+            ("next", &self.next_key, &self.next_cert),
+        ] {
+            let pub_key = SubjectPublicKeyInfoOwned::from_key(*key.verifying_key())?;
+            anyhow::ensure!(
+                pub_key == cert.tbs_certificate.subject_public_key_info,
+                "The {name} key does not belong to the {name} certificate."
+            );
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct PrivateKeysSer {
     pub current_key: Vec<u8>,
@@ -121,6 +135,15 @@ pub fn generate_key_and_cert(
     ))
 }
 
+/// Writes the keys to a temporary file first & then replaces `signing_keys.json`,
+/// so the file is never partially written.
+pub async fn write_signing_keys(keys: &PrivateKeys) -> anyhow::Result<()> {
+    const TMP_FILE: &str = "signing_keys.json.tmp";
+    tokio::fs::write(TMP_FILE, serde_json::to_vec(keys)?).await?;
+    tokio::fs::rename(TMP_FILE, "signing_keys.json").await?;
+    Ok(())
+}
+
 pub async fn store_cert(
     db: &DbConnectionShared,
     pool: &AnyPool,
@@ -192,10 +215,6 @@ pub async fn certs_request(
             .iter()
             .map(|cert| cert.to_der().map_err(|err| anyhow!(err)))
             .collect::<anyhow::Result<Vec<_>>>()
-            .map_err(|err| AccountServerRequestError::Unexpected {
-                target: "certs_request".into(),
-                err: err.to_string(),
-                bt: err.backtrace().to_string(),
-            }),
+            .map_err(|err| request_error("certs_request", err)),
     )
 }
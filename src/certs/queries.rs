@@ -19,15 +19,31 @@ impl Query<()> for AddCert<'_> {
             .prepare(include_str!("mysql/add_cert.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/add_cert.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query().bind(self.cert_der).bind(self.valid_until)
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.cert_der).bind(self.valid_until)
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
 
 pub struct GetCerts {}
@@ -45,12 +61,25 @@ impl Query<SingleCertData> for GetCerts {
             .prepare(include_str!("mysql/get_certs.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/get_certs.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query()
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query()
+    }
     fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<SingleCertData> {
         Ok(SingleCertData {
             cert_der: row
@@ -58,4 +87,11 @@ impl Query<SingleCertData> for GetCerts {
                 .map_err(|err| anyhow!("Failed get column cert_der: {err}"))?,
         })
     }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<SingleCertData> {
+        Ok(SingleCertData {
+            cert_der: row
+                .try_get("cert_der")
+                .map_err(|err| anyhow!("Failed get column cert_der: {err}"))?,
+        })
+    }
 }
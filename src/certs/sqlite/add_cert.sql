@@ -0,0 +1,4 @@
+INSERT INTO
+    certs (cert_der, valid_until)
+VALUES
+    (?, datetime(?));
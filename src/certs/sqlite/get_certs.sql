@@ -0,0 +1,6 @@
+SELECT
+    certs.cert_der
+FROM
+    certs
+WHERE
+    certs.valid_until > datetime('now');
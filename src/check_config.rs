@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use ddnet_account_sql::any::AnyPool;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+use crate::{
+    certs::PrivateKeys, prepare_db, prepare_email_transport, prepare_statements, DbDetails,
+    Details, EmailDetails, SteamDetails,
+};
+
+async fn check_db(details: &DbDetails) -> anyhow::Result<()> {
+    let pool = match details {
+        // checking must not create the database file
+        DbDetails::Sqlite(details) => AnyPool::Sqlite(
+            SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect_with(
+                    SqliteConnectOptions::new()
+                        .filename(&details.path)
+                        .create_if_missing(false),
+                )
+                .await
+                .map_err(|err| {
+                    anyhow!(
+                        "{}: {err} (was `--setup` executed?)",
+                        details.path.display()
+                    )
+                })?,
+        ),
+        details => prepare_db(details).await?,
+    };
+    // preparing the statements fails if any table is missing
+    prepare_statements(&pool)
+        .await
+        .map_err(|err| anyhow!("{err} (was `--setup` executed?)"))?;
+    Ok(())
+}
+
+async fn check_email(details: &EmailDetails) -> anyhow::Result<()> {
+    prepare_email_transport(&details.transport)?
+        .test_connection()
+        .await
+}
+
+async fn check_steam(details: &SteamDetails) -> anyhow::Result<()> {
+    let url = details
+        .auth_url
+        .clone()
+        .unwrap_or_else(|| crate::OFFICIAL_STEAM_AUTH_URL.try_into().unwrap());
+    anyhow::ensure!(
+        ["https", "http"].contains(&url.scheme()),
+        "The auth url must be a http(s) url."
+    );
+    // any response is fine, without a ticket the request can't succeed anyway
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?
+        .get(url)
+        .send()
+        .await?;
+    Ok(())
+}
+
+async fn check_signing_keys() -> anyhow::Result<()> {
+    let Ok(keys) = tokio::fs::read("signing_keys.json").await else {
+        log::info!(
+            target: "check-config",
+            "signing_keys.json does not exist yet, it is created on the first start."
+        );
+        return Ok(());
+    };
+    serde_json::from_slice::<PrivateKeys>(&keys)?.check()
+}
+
+/// Checks the settings & everything the account server depends on,
+/// without starting the http server. Returns `true` if all checks passed.
+pub async fn check_config(details: &Details) -> bool {
+    let mut ok = true;
+    let mut report = |name: &str, res: anyhow::Result<()>| match res {
+        Ok(()) => log::info!(target: "check-config", "{name}: ok"),
+        Err(err) => {
+            log::error!(target: "check-config", "{name}: {err}");
+            ok = false;
+        }
+    };
+    report("database", check_db(&details.db).await);
+    report("email", check_email(&details.email).await);
+    report("steam", check_steam(&details.steam).await);
+    report("signing keys", check_signing_keys().await);
+    ok
+}
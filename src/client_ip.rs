@@ -0,0 +1,94 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, Request},
+    middleware::Next,
+    response::Response,
+};
+
+/// The ip of the client that sent a request,
+/// added to the request extensions by [`client_ip_layer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// Resolves the client ip of requests.
+/// `X-Forwarded-For` is only used if the request
+/// comes from one of the trusted proxies.
+#[derive(Debug, Default)]
+pub struct ClientIpResolver {
+    ipv4: iprange::IpRange<ipnet::Ipv4Net>,
+    ipv6: iprange::IpRange<ipnet::Ipv6Net>,
+}
+
+impl ClientIpResolver {
+    pub fn new(trusted_proxies: &[ipnet::IpNet]) -> Self {
+        let mut res = Self::default();
+        for proxy in trusted_proxies {
+            match proxy {
+                ipnet::IpNet::V4(ipv4_net) => {
+                    res.ipv4.add(*ipv4_net);
+                }
+                ipnet::IpNet::V6(ipv6_net) => {
+                    res.ipv6.add(*ipv6_net);
+                }
+            }
+        }
+        res
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        match ip.to_canonical() {
+            IpAddr::V4(ip) => self.ipv4.contains(&ipnet::Ipv4Net::from(ip)),
+            IpAddr::V6(ip) => self.ipv6.contains(&ipnet::Ipv6Net::from(ip)),
+        }
+    }
+
+    /// Every proxy appends the ip it got the request from to `X-Forwarded-For`,
+    /// so the client is the last entry that is not a trusted proxy.
+    pub fn resolve(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let mut client = peer.to_canonical();
+        if !self.is_trusted(client) {
+            return client;
+        }
+
+        let forwarded_for: Vec<&str> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|header| header.to_str().ok())
+            .flat_map(|header| header.split(','))
+            .map(str::trim)
+            .collect();
+        for ip in forwarded_for.into_iter().rev() {
+            // some proxies add the port
+            let Some(ip) = ip
+                .parse::<IpAddr>()
+                .ok()
+                .or_else(|| ip.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+            else {
+                // anything before an invalid entry can't be trusted
+                break;
+            };
+            client = ip.to_canonical();
+            if !self.is_trusted(client) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+pub async fn client_ip_layer(
+    State(resolver): State<Arc<ClientIpResolver>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let client_ip = resolver.resolve(peer.ip(), req.headers());
+    req.extensions_mut().insert(ClientIp(client_ip));
+    next.run(req).await
+}
@@ -10,13 +10,14 @@ use ddnet_accounts_shared::{
         otp::generate_otp, result::AccountServerReqResult,
     },
     client::credential_auth_token::{
-        CredentialAuthTokenEmailRequest, CredentialAuthTokenOperation,
-        CredentialAuthTokenSteamRequest,
+        CredentialAuthTokenEmailRequest, CredentialAuthTokenSteamRequest,
     },
 };
 
 use crate::{
-    credential_auth_token::queries::AddCredentialAuthToken, shared::Shared, types::TokenType,
+    credential_auth_token::queries::AddCredentialAuthToken, email_outbox::queue_email,
+    email_templates::EmailKind, request_error::request_error, secret_verifier::check_secret_key,
+    shared::Shared, types::TokenType,
 };
 
 pub async fn credential_auth_token_email(
@@ -42,22 +43,21 @@ pub async fn credential_auth_token_email(
         ));
     }
 
-    // Before this call a validation process could be added
-    if requires_secret && data.secret_key.is_none() {
-        return Json(AccountServerReqResult::Err(
-            AccountServerRequestError::Other(
-                "This function is only for requests with a secret verification token.".to_string(),
-            ),
-        ));
+    match check_secret_key(&shared, requires_secret, data.secret_key.as_ref()).await {
+        Ok(None) => {}
+        Ok(Some(url)) => {
+            return Json(AccountServerReqResult::Err(
+                AccountServerRequestError::LogicError(
+                    CredentialAuthTokenError::WebValidationProcessNeeded { url },
+                ),
+            ));
+        }
+        Err(err) => return Json(AccountServerReqResult::Err(err)),
     }
     Json(
         credential_auth_token_email_impl(shared, pool, data)
             .await
-            .map_err(|err| AccountServerRequestError::Unexpected {
-                target: "credential_auth_token_email".into(),
-                err: err.to_string(),
-                bt: err.backtrace().to_string(),
-            }),
+            .map_err(|err| request_error("credential_auth_token_email", err)),
     )
 }
 
@@ -83,42 +83,44 @@ pub async fn credential_auth_token_email_impl(
             == Some(data.email.as_str()),
         "Email must only contain email part with name & domain (name@example.com)"
     );
+    shared.token_limiter.check_email(&data.email)?;
 
-    // write the new account to the database
     // Add a credential auth token and send it by email
     let token = generate_otp();
     let token_hex = hex::encode(token);
-    let query_add_credential_auth_token = AddCredentialAuthToken {
-        token: &token,
-        ty: &TokenType::Email,
-        identifier: data.email.as_str(),
-        op: &data.op,
-    };
-    let mut connection = pool.acquire().await?;
-    let mut con = connection.acquire().await?;
-
-    let credential_auth_token_res = query_add_credential_auth_token
-        .query(&shared.db.credential_auth_token_statement)
-        .execute(&mut con)
-        .await?;
-    anyhow::ensure!(
-        credential_auth_token_res.rows_affected() >= 1,
-        "No credential auth token could be added."
+    let mail = shared.email.render(
+        EmailKind::CredentialAuthToken(data.op),
+        data.locale.as_deref(),
+        &[
+            ("%SUBJECT%", data.email.local_part()),
+            ("%CODE%", &token_hex),
+        ],
     );
-
-    let header = match data.op {
-        CredentialAuthTokenOperation::Login => "DDNet Account Login",
-        CredentialAuthTokenOperation::LinkCredential => "DDNet Link E-mail To Account",
-        CredentialAuthTokenOperation::UnlinkCredential => "DDNet Unlink Credential",
-    };
-
-    let mail = shared.credential_auth_tokens_email.read().clone();
-    let mail = mail
-        .replace("%SUBJECT%", data.email.local_part())
-        .replace("%CODE%", &token_hex);
-    shared
-        .email
-        .send_email(data.email.as_str(), header, mail)
+    let mut connection = pool.acquire().await?;
+    let mut connection = connection.acquire().await?;
+
+    // the token is only added if the email is queued
+    connection
+        .transaction(|mut connection| {
+            Box::pin(async move {
+                let query_add_credential_auth_token = AddCredentialAuthToken {
+                    token: &token,
+                    ty: &TokenType::Email,
+                    identifier: data.email.as_str(),
+                    op: &data.op,
+                };
+                let credential_auth_token_res = query_add_credential_auth_token
+                    .query(&shared.db.credential_auth_token_statement)
+                    .execute(&mut connection.con())
+                    .await?;
+                anyhow::ensure!(
+                    credential_auth_token_res.rows_affected() >= 1,
+                    "No credential auth token could be added."
+                );
+
+                queue_email(&shared, &mut connection.con(), data.email.as_str(), &mail).await
+            })
+        })
         .await?;
 
     Ok(())
@@ -130,22 +132,21 @@ pub async fn credential_auth_token_steam(
     requires_secret: bool,
     Json(data): Json<CredentialAuthTokenSteamRequest>,
 ) -> Json<AccountServerReqResult<String, CredentialAuthTokenError>> {
-    // After this check a validation process could be added
-    if requires_secret && data.secret_key.is_none() {
-        return Json(AccountServerReqResult::Err(
-            AccountServerRequestError::Other(
-                "This function is only for requests with a secret verification token.".to_string(),
-            ),
-        ));
+    match check_secret_key(&shared, requires_secret, data.secret_key.as_ref()).await {
+        Ok(None) => {}
+        Ok(Some(url)) => {
+            return Json(AccountServerReqResult::Err(
+                AccountServerRequestError::LogicError(
+                    CredentialAuthTokenError::WebValidationProcessNeeded { url },
+                ),
+            ));
+        }
+        Err(err) => return Json(AccountServerReqResult::Err(err)),
     }
     Json(
         credential_auth_token_steam_impl(shared, pool, data)
             .await
-            .map_err(|err| AccountServerRequestError::Unexpected {
-                target: "credential_auth_token_steam".into(),
-                err: err.to_string(),
-                bt: err.backtrace().to_string(),
-            }),
+            .map_err(|err| request_error("credential_auth_token_steam", err)),
     )
 }
 
@@ -160,6 +161,7 @@ pub async fn credential_auth_token_steam_impl(
     );
 
     let steamid64 = shared.steam.verify_steamid64(data.steam_ticket).await?;
+    shared.token_limiter.check_steam(steamid64)?;
 
     // write the new account to the database
     // Add a credential auth token and send it by steam
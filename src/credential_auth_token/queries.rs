@@ -24,6 +24,13 @@ impl Query<()> for AddCredentialAuthToken<'_> {
             .prepare(include_str!("mysql/add_credential_auth_token.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/add_credential_auth_token.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
@@ -37,7 +44,23 @@ impl Query<()> for AddCredentialAuthToken<'_> {
             .bind(self.identifier)
             .bind(op)
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        let ty: &'static str = self.ty.into();
+        let op: &'static str = self.op.into();
+        statement
+            .query()
+            .bind(self.token.as_slice())
+            .bind(ty)
+            .bind(self.identifier)
+            .bind(op)
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
@@ -0,0 +1,16 @@
+INSERT INTO
+    credential_auth_tokens (
+        token,
+        valid_until,
+        ty,
+        identifier,
+        op
+    )
+VALUES
+    (
+        ?,
+        datetime('now', '+15 minutes'),
+        ?,
+        ?,
+        ?
+    );
@@ -30,4 +30,20 @@ pub struct DbConnectionShared {
     pub unlink_credential_by_email_statement: AnyStatement<'static>,
     pub unlink_credential_by_steam_statement: AnyStatement<'static>,
     pub account_info: AnyStatement<'static>,
+    pub sessions_statement: AnyStatement<'static>,
+    pub update_sign_time_statement: AnyStatement<'static>,
+    pub account_ban_statement: AnyStatement<'static>,
+    pub admin_account_info_statement: AnyStatement<'static>,
+    pub add_ban_statement: AnyStatement<'static>,
+    pub remove_ban_statement: AnyStatement<'static>,
+    pub remove_account_tokens_statement: AnyStatement<'static>,
+    pub add_account_event_statement: AnyStatement<'static>,
+    pub account_events_statement: AnyStatement<'static>,
+    pub cleanup_account_events_statement: AnyStatement<'static>,
+    pub linked_email_statement: AnyStatement<'static>,
+    pub add_email_statement: AnyStatement<'static>,
+    pub pending_emails_statement: AnyStatement<'static>,
+    pub remove_email_statement: AnyStatement<'static>,
+    pub retry_email_statement: AnyStatement<'static>,
+    pub claim_email_statement: AnyStatement<'static>,
 }
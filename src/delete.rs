@@ -1,22 +1,27 @@
 pub mod queries;
 
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc};
 
-use axum::Json;
+use axum::{Extension, Json};
 use ddnet_account_sql::{any::AnyPool, query::Query};
 use ddnet_accounts_shared::{
     account_server::{
-        errors::{AccountServerRequestError, Empty},
+        account_events::AccountEventKind, delete::DeleteError, errors::RequestFailure,
         result::AccountServerReqResult,
     },
     client::delete::DeleteRequest,
 };
 
 use crate::{
+    account_events::add_account_event,
     account_token::queries::{AccountTokenQry, InvalidateAccountToken},
+    admin::queries::{RemoveAccountTokens, RemoveBan},
+    client_ip::ClientIp,
     link_credential::queries::{UnlinkCredentialEmail, UnlinkCredentialSteam},
     logout_all::queries::RemoveSessionsExcept,
+    request_error::request_logic_error,
     shared::Shared,
+    sign::account_ban,
     types::AccountTokenType,
 };
 
@@ -25,20 +30,22 @@ use self::queries::RemoveAccount;
 pub async fn delete_request(
     shared: Arc<Shared>,
     pool: AnyPool,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
     Json(data): Json<DeleteRequest>,
-) -> Json<AccountServerReqResult<(), Empty>> {
+) -> Json<AccountServerReqResult<(), DeleteError>> {
     Json(
-        delete(shared, pool, data)
+        delete(shared, pool, client_ip, data)
             .await
-            .map_err(|err| AccountServerRequestError::Unexpected {
-                target: "delete_request".into(),
-                err: err.to_string(),
-                bt: err.backtrace().to_string(),
-            }),
+            .map_err(|err| request_logic_error("delete_request", err)),
     )
 }
 
-pub async fn delete(shared: Arc<Shared>, pool: AnyPool, data: DeleteRequest) -> anyhow::Result<()> {
+pub async fn delete(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    client_ip: IpAddr,
+    data: DeleteRequest,
+) -> anyhow::Result<()> {
     let mut connection = pool.acquire().await?;
     let mut connection = connection.acquire().await?;
 
@@ -52,8 +59,9 @@ pub async fn delete(shared: Arc<Shared>, pool: AnyPool, data: DeleteRequest) ->
 
                 let row = acc_token_qry
                     .query(&shared.db.account_token_qry_statement)
-                    .fetch_one(&mut connection.con())
-                    .await?;
+                    .fetch_optional(&mut connection.con())
+                    .await?
+                    .ok_or(DeleteError::TokenInvalid)?;
 
                 let token_data = AccountTokenQry::row_data(&row)?;
 
@@ -67,10 +75,15 @@ pub async fn delete(shared: Arc<Shared>, pool: AnyPool, data: DeleteRequest) ->
 
                 anyhow::ensure!(
                     token_data.ty == AccountTokenType::Delete,
-                    "Account token was not for delete operation."
+                    RequestFailure::WrongTokenOp
                 );
                 let account_id = token_data.account_id;
 
+                // a new account with the same credentials would not be banned
+                if let Some(ban) = account_ban(&shared, &account_id, &mut connection.con()).await? {
+                    return Err(DeleteError::Banned(ban).into());
+                }
+
                 // remove all sessions
                 let qry = RemoveSessionsExcept {
                     account_id: &account_id,
@@ -96,6 +109,21 @@ pub async fn delete(shared: Arc<Shared>, pool: AnyPool, data: DeleteRequest) ->
                     .execute(&mut connection.con())
                     .await?;
 
+                // other pending account tokens & expired bans reference the account
+                let qry = RemoveAccountTokens {
+                    account_id: &account_id,
+                };
+                qry.query(&shared.db.remove_account_tokens_statement)
+                    .execute(&mut connection.con())
+                    .await?;
+
+                let qry = RemoveBan {
+                    account_id: &account_id,
+                };
+                qry.query(&shared.db.remove_ban_statement)
+                    .execute(&mut connection.con())
+                    .await?;
+
                 // delete account
                 let qry = RemoveAccount {
                     account_id: &account_id,
@@ -105,6 +133,18 @@ pub async fn delete(shared: Arc<Shared>, pool: AnyPool, data: DeleteRequest) ->
                     .execute(&mut connection.con())
                     .await?;
 
+                // the events outlive the account, e.g. for support requests
+                add_account_event(
+                    &shared,
+                    &mut connection.con(),
+                    &account_id,
+                    AccountEventKind::Delete,
+                    client_ip,
+                    None,
+                    None,
+                )
+                .await?;
+
                 anyhow::Ok(())
             })
         })
@@ -0,0 +1,4 @@
+DELETE FROM
+    account
+WHERE
+    account.id = ?;
@@ -1,16 +1,27 @@
-use std::{fmt::Debug, path::Path, sync::Arc};
+use std::{fmt::Debug, net::IpAddr, str::FromStr, sync::Arc};
 
-use lettre::{
-    message::SinglePart, transport::smtp::authentication::Credentials, Message, SmtpTransport,
-    Transport,
-};
+use ddnet_account_sql::{any::AnyConnection, query::Query};
+use ddnet_accounts_types::account_id::AccountId;
+
+use lettre::{message::MultiPart, Message};
 use parking_lot::RwLock;
+use tokio::sync::Notify;
 
 use crate::{
     email_limit::{EmailDomainAllowList, EmailDomainDenyList},
+    email_outbox::queue_email,
+    email_templates::{EmailKind, EmailTemplates, RenderedEmail},
     file_watcher::FileWatcher,
+    metrics::Metrics,
+    shared::Shared,
+    types::TokenType,
 };
 
+use self::{queries::LinkedEmail, transport::EmailTransport};
+
+pub mod queries;
+pub mod transport;
+
 pub trait EmailHook: Debug + Sync + Send {
     fn on_mail(&self, email_subject: &str, email_body: &str);
 }
@@ -23,45 +34,93 @@ impl EmailHook for EmailHookDummy {
     }
 }
 
+/// A sensitive account change, that the owner of the account
+/// is notified about by email.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccountNotification {
+    CredentialLinked,
+    CredentialUnlinked,
+    LogoutAll,
+}
+
+/// The email linked to the given account, that receives its notifications.
+pub async fn linked_email(
+    shared: &Shared,
+    connection: &mut AnyConnection<'_>,
+    account_id: &AccountId,
+) -> anyhow::Result<Option<email_address::EmailAddress>> {
+    let qry = LinkedEmail { account_id };
+    let row = qry
+        .query(&shared.db.linked_email_statement)
+        .fetch_optional(connection)
+        .await?;
+
+    row.map(|row| LinkedEmail::row_data(&row))
+        .transpose()?
+        .map(|data| email_address::EmailAddress::from_str(&data.email))
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// Notifies the owner of an account about a sensitive account change.
+/// Failing to queue the notification does not undo the change, so errors are only logged.
+pub async fn send_notification(
+    shared: &Shared,
+    connection: &mut AnyConnection<'_>,
+    to: &email_address::EmailAddress,
+    notification: AccountNotification,
+    credential: Option<TokenType>,
+    client_ip: IpAddr,
+) {
+    let credential: &'static str = credential.map(Into::into).unwrap_or_default();
+    let mail = shared.email.render(
+        EmailKind::Notification(notification),
+        None,
+        &[
+            ("%SUBJECT%", to.local_part()),
+            ("%CREDENTIAL%", credential),
+            ("%CLIENT_IP%", &client_ip.to_string()),
+        ],
+    );
+    if let Err(err) = queue_email(shared, connection, to.as_str(), &mail).await {
+        log::warn!("Queueing the {notification:?} notification failed: {err}");
+    }
+}
+
 /// Shared email helper
 #[derive(Debug)]
 pub struct EmailShared {
-    smtp: SmtpTransport,
-    pub email_from: String,
+    transport: RwLock<Arc<dyn EmailTransport>>,
+    email_from: RwLock<String>,
     mail_hook: Arc<dyn EmailHook>,
+    metrics: Arc<Metrics>,
 
     pub deny_list: RwLock<EmailDomainDenyList>,
     pub allow_list: RwLock<EmailDomainAllowList>,
 
+    /// The templates of all emails, in all available languages
+    pub templates: RwLock<Arc<EmailTemplates>>,
+    /// Wakes up the outbox worker, when a new email was queued
+    pub outbox_notify: Notify,
+
     pub test_mode: bool,
 }
 
 impl EmailShared {
-    pub async fn new(
-        relay: &str,
-        relay_port: u16,
-        from_email: &str,
-        username: &str,
-        password: &str,
-    ) -> anyhow::Result<Self> {
-        let smtp = SmtpTransport::relay(relay)?
-            .port(relay_port)
-            .credentials(Credentials::new(username.into(), password.into()))
-            .build();
-
-        anyhow::ensure!(
-            smtp.test_connection()?,
-            "Could not connect to smtp server: {}",
-            relay
-        );
+    pub async fn new(from_email: &str, transport: Arc<dyn EmailTransport>) -> anyhow::Result<Self> {
+        transport.test_connection().await?;
         Ok(Self {
-            smtp,
+            transport: RwLock::new(transport),
             mail_hook: Arc::new(EmailHookDummy {}),
-            email_from: from_email.into(),
+            metrics: Default::default(),
+            email_from: RwLock::new(from_email.into()),
 
             deny_list: RwLock::new(EmailDomainDenyList::load_from_file().await),
             allow_list: RwLock::new(EmailDomainAllowList::load_from_file().await),
 
+            templates: RwLock::new(Arc::new(Self::load_templates().await)),
+            outbox_notify: Notify::new(),
+
             test_mode: false,
         })
     }
@@ -73,28 +132,69 @@ impl EmailShared {
         self.mail_hook = Arc::new(hook);
     }
 
-    pub async fn send_email(
+    /// Where the outcome of sent emails is counted.
+    pub fn set_metrics(&mut self, metrics: Arc<Metrics>) {
+        self.metrics = metrics;
+    }
+
+    /// Replaces the sender & the transport of all emails that are sent from now on,
+    /// if the new transport is usable.
+    pub async fn set_transport(
         &self,
-        to: &str,
-        subject: &str,
-        html_body: String,
+        email_from: &str,
+        transport: Arc<dyn EmailTransport>,
     ) -> anyhow::Result<()> {
-        self.mail_hook.on_mail(subject, &html_body);
+        email_from.parse::<lettre::message::Mailbox>()?;
+        transport.test_connection().await?;
+        *self.email_from.write() = email_from.into();
+        *self.transport.write() = transport;
+        Ok(())
+    }
+
+    /// Checks if the current transport is usable, e.g. if the smtp server is reachable.
+    pub async fn test_connection(&self) -> anyhow::Result<()> {
+        let transport = self.transport.read().clone();
+        transport.test_connection().await
+    }
+
+    /// Renders the template of the given kind, see [`EmailTemplates::render`].
+    pub fn render(
+        &self,
+        kind: EmailKind,
+        locale: Option<&str>,
+        vars: &[(&str, &str)],
+    ) -> RenderedEmail {
+        self.templates.read().render(kind, locale, vars)
+    }
+
+    /// Called after an email was added to the outbox.
+    pub fn on_queued(&self, mail: &RenderedEmail) {
+        self.mail_hook.on_mail(&mail.subject, &mail.html);
+        self.outbox_notify.notify_one();
+    }
+
+    /// Sends the email as multipart/alternative, with a plain text & a html part.
+    ///
+    /// Request handlers should use [`crate::email_outbox::queue_email`] instead,
+    /// which retries failed emails.
+    pub async fn send_email(&self, to: &str, mail: RenderedEmail) -> anyhow::Result<()> {
         let email = Message::builder()
-            .from(self.email_from.parse().unwrap())
+            .from(self.email_from.read().parse().unwrap())
             .to(to.parse().unwrap())
-            .subject(subject)
-            .singlepart(SinglePart::html(html_body))
+            .subject(mail.subject)
+            .multipart(MultiPart::alternative_plain_html(mail.text, mail.html))
             .unwrap();
-        self.smtp.send(&email)?;
+        let transport = self.transport.read().clone();
+        let res = transport.send(email).await;
+        Metrics::count_result(&self.metrics.emails, &res);
+        res?;
 
         Ok(())
     }
 
     const PATH: &str = "config/";
-    pub async fn load_email_template(name: &str) -> anyhow::Result<String> {
-        let path: &Path = Self::PATH.as_ref();
-        Ok(tokio::fs::read_to_string(path.join(name)).await?)
+    pub async fn load_templates() -> EmailTemplates {
+        EmailTemplates::load(Self::PATH.as_ref()).await
     }
 
     pub fn watcher(name: &str) -> FileWatcher {
@@ -102,7 +202,7 @@ impl EmailShared {
     }
 
     #[cfg(test)]
-    pub fn set_test_mode(&mut self, test_mode: bool) {
+    pub const fn set_test_mode(&mut self, test_mode: bool) {
         self.test_mode = test_mode;
     }
     pub const fn test_mode(&self) -> bool {
@@ -110,16 +210,20 @@ impl EmailShared {
     }
 }
 
-impl From<(&str, SmtpTransport)> for EmailShared {
-    fn from((email_from, smtp): (&str, SmtpTransport)) -> Self {
+impl<T: EmailTransport + 'static> From<(&str, T)> for EmailShared {
+    fn from((email_from, transport): (&str, T)) -> Self {
         Self {
-            smtp,
+            transport: RwLock::new(Arc::new(transport)),
             mail_hook: Arc::new(EmailHookDummy {}),
-            email_from: email_from.into(),
+            metrics: Default::default(),
+            email_from: RwLock::new(email_from.into()),
 
             deny_list: Default::default(),
             allow_list: Default::default(),
 
+            templates: Default::default(),
+            outbox_notify: Notify::new(),
+
             test_mode: false,
         }
     }
@@ -127,23 +231,35 @@ impl From<(&str, SmtpTransport)> for EmailShared {
 
 #[cfg(test)]
 mod test {
-    use lettre::SmtpTransport;
+    use std::sync::Arc;
 
-    use crate::email::EmailShared;
+    use crate::{
+        email::{transport::MemoryEmailTransport, EmailShared},
+        email_templates::RenderedEmail,
+    };
 
     #[tokio::test]
     async fn email_test() {
-        let email: EmailShared = ("test@localhost", SmtpTransport::unencrypted_localhost()).into();
-
-        assert!(email.smtp.test_connection().unwrap());
+        let transport = Arc::new(MemoryEmailTransport::default());
+        let email: EmailShared = ("test@localhost", transport.clone()).into();
 
         email
             .send_email(
                 "TestTo <test@localhost>",
-                "It works",
-                "It indeed works".to_string(),
+                RenderedEmail {
+                    subject: "It works".to_string(),
+                    html: "<p>It indeed works</p>".to_string(),
+                    text: "It indeed works".to_string(),
+                },
             )
             .await
             .unwrap();
+
+        let emails = transport.emails();
+        assert_eq!(emails.len(), 1);
+        let email = String::from_utf8(emails[0].formatted()).unwrap();
+        assert!(email.contains("Subject: It works"));
+        assert!(email.contains("multipart/alternative"));
+        assert!(email.contains("<p>It indeed works</p>"));
     }
 }
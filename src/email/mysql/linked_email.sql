@@ -0,0 +1,6 @@
+SELECT
+    email
+FROM
+    credential_email
+WHERE
+    account_id = ?;
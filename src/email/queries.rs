@@ -0,0 +1,60 @@
+use anyhow::anyhow;
+use axum::async_trait;
+use ddnet_account_sql::query::Query;
+use ddnet_accounts_types::account_id::AccountId;
+use sqlx::Executor;
+use sqlx::Row;
+use sqlx::Statement;
+
+/// The email that is linked to an account, if any.
+pub struct LinkedEmail<'a> {
+    pub account_id: &'a AccountId,
+}
+
+pub struct LinkedEmailData {
+    pub email: String,
+}
+
+#[async_trait]
+impl Query<LinkedEmailData> for LinkedEmail<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/linked_email.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/linked_email.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement.query().bind(self.account_id)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.account_id)
+    }
+    fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<LinkedEmailData> {
+        Ok(LinkedEmailData {
+            email: row
+                .try_get("email")
+                .map_err(|err| anyhow!("Failed get column email: {err}"))?,
+        })
+    }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<LinkedEmailData> {
+        Ok(LinkedEmailData {
+            email: row
+                .try_get("email")
+                .map_err(|err| anyhow!("Failed get column email: {err}"))?,
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use std::{fmt::Debug, path::PathBuf};
+
+use axum::async_trait;
+use lettre::{
+    AsyncFileTransport, AsyncSendmailTransport, AsyncSmtpTransport, AsyncTransport, Message,
+    Tokio1Executor,
+};
+use parking_lot::Mutex;
+
+/// Delivers the emails of the account server.
+#[async_trait]
+pub trait EmailTransport: Debug + Sync + Send {
+    async fn send(&self, email: Message) -> anyhow::Result<()>;
+
+    /// Checks if the transport is usable at all,
+    /// e.g. if the smtp server is reachable.
+    async fn test_connection(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Sends the emails over a smtp relay.
+#[derive(Debug)]
+pub struct SmtpEmailTransport(pub AsyncSmtpTransport<Tokio1Executor>);
+
+#[async_trait]
+impl EmailTransport for SmtpEmailTransport {
+    async fn send(&self, email: Message) -> anyhow::Result<()> {
+        self.0.send(email).await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.0.test_connection().await?,
+            "Could not connect to smtp server"
+        );
+        Ok(())
+    }
+}
+
+/// Hands the emails to a local `sendmail` binary.
+#[derive(Debug)]
+pub struct SendmailEmailTransport(pub AsyncSendmailTransport<Tokio1Executor>);
+
+#[async_trait]
+impl EmailTransport for SendmailEmailTransport {
+    async fn send(&self, email: Message) -> anyhow::Result<()> {
+        self.0.send(email).await?;
+        Ok(())
+    }
+}
+
+/// Writes every email as `<uuid>.eml` file into a directory,
+/// e.g. for development.
+#[derive(Debug)]
+pub struct FileEmailTransport {
+    dir: PathBuf,
+    transport: AsyncFileTransport<Tokio1Executor>,
+}
+
+impl FileEmailTransport {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            transport: AsyncFileTransport::new(&dir),
+            dir,
+        }
+    }
+}
+
+#[async_trait]
+impl EmailTransport for FileEmailTransport {
+    async fn send(&self, email: Message) -> anyhow::Result<()> {
+        self.transport.send(email).await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> anyhow::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        Ok(())
+    }
+}
+
+/// Keeps all emails in memory & logs them,
+/// e.g. for development & tests without a mail server.
+#[derive(Debug, Default)]
+pub struct MemoryEmailTransport {
+    emails: Mutex<Vec<Message>>,
+}
+
+impl MemoryEmailTransport {
+    /// All emails sent so far.
+    #[allow(dead_code)]
+    pub fn emails(&self) -> Vec<Message> {
+        self.emails.lock().clone()
+    }
+}
+
+#[async_trait]
+impl EmailTransport for MemoryEmailTransport {
+    async fn send(&self, email: Message) -> anyhow::Result<()> {
+        log::info!(
+            target: "email",
+            "{}",
+            String::from_utf8_lossy(&email.formatted())
+        );
+        self.emails.lock().push(email);
+        Ok(())
+    }
+}
+
+/// Allows to keep access to the transport, e.g. to the emails of a [`MemoryEmailTransport`].
+#[async_trait]
+impl<T: EmailTransport> EmailTransport for std::sync::Arc<T> {
+    async fn send(&self, email: Message) -> anyhow::Result<()> {
+        self.as_ref().send(email).await
+    }
+
+    async fn test_connection(&self) -> anyhow::Result<()> {
+        self.as_ref().test_connection().await
+    }
+}
@@ -0,0 +1,146 @@
+pub mod queries;
+
+use std::{sync::Arc, time::Duration};
+
+use ddnet_account_sql::{
+    any::{AnyConnection, AnyPool},
+    query::Query,
+};
+use queries::{AddEmail, ClaimEmail, PendingEmails, RemoveEmail, RetryEmail};
+
+use crate::{email_templates::RenderedEmail, shared::Shared};
+
+/// After this many failed attempts an email is dropped.
+const MAX_ATTEMPTS: i32 = 10;
+/// Pending emails are also checked regularly,
+/// e.g. for retries or emails queued by other account servers.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// An email is claimed before sending it, other account servers skip it for this long.
+/// If the account server stops while sending, the email is sent again after the lease.
+const CLAIM_LEASE: Duration = Duration::from_secs(10 * 60);
+
+/// The delay before the next attempt: 30 seconds, doubled on every failure,
+/// but at most 4 hours.
+pub fn retry_delay(failed_attempts: i32) -> Duration {
+    Duration::from_secs(30 * 2u64.pow(failed_attempts.clamp(0, 9) as u32))
+        .min(Duration::from_secs(4 * 60 * 60))
+}
+
+/// Queues an email, it is sent by [`email_outbox_worker`] in the background.
+pub async fn queue_email(
+    shared: &Shared,
+    connection: &mut AnyConnection<'_>,
+    to: &str,
+    mail: &RenderedEmail,
+) -> anyhow::Result<()> {
+    let qry = AddEmail {
+        recipient: to,
+        subject: &mail.subject,
+        html: &mail.html,
+        text: &mail.text,
+    };
+    let res = qry
+        .query(&shared.db.add_email_statement)
+        .execute(connection)
+        .await?;
+    anyhow::ensure!(res.rows_affected() >= 1, "The email could not be queued.");
+
+    shared.email.on_queued(mail);
+
+    Ok(())
+}
+
+/// Tries to send all emails that are due, once.
+pub async fn send_pending_emails(pool: &AnyPool, shared: &Shared) -> anyhow::Result<()> {
+    let mut connection = pool.acquire().await?;
+    let mut connection = connection.acquire().await?;
+
+    let pending = PendingEmails {}
+        .query(&shared.db.pending_emails_statement)
+        .fetch_all(&mut connection)
+        .await?
+        .iter()
+        .map(PendingEmails::row_data)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    for email in pending {
+        // the remaining emails are sent after the restart
+        if shared.shutdown.is_triggered() {
+            break;
+        }
+        // another account server might have claimed it already
+        let claimed = ClaimEmail {
+            id: email.id,
+            lease_secs: CLAIM_LEASE.as_secs() as i64,
+        }
+        .query(&shared.db.claim_email_statement)
+        .execute(&mut connection)
+        .await?;
+        if claimed.rows_affected() == 0 {
+            continue;
+        }
+
+        let mail = RenderedEmail {
+            subject: email.subject,
+            html: email.html,
+            text: email.text,
+        };
+        let res = shared.email.send_email(&email.recipient, mail).await;
+        match res {
+            Ok(()) => {
+                RemoveEmail { id: email.id }
+                    .query(&shared.db.remove_email_statement)
+                    .execute(&mut connection)
+                    .await?;
+            }
+            Err(err) => {
+                let failed_attempts = email.attempts + 1;
+                if failed_attempts >= MAX_ATTEMPTS {
+                    log::error!(
+                        target: "email-outbox",
+                        "Dropping email {} after {failed_attempts} failed attempts: {err}",
+                        email.id
+                    );
+                    RemoveEmail { id: email.id }
+                        .query(&shared.db.remove_email_statement)
+                        .execute(&mut connection)
+                        .await?;
+                } else {
+                    let delay = retry_delay(failed_attempts);
+                    log::warn!(
+                        target: "email-outbox",
+                        "Sending email {} failed ({failed_attempts} failed attempts), \
+                        retrying in {}s: {err}",
+                        email.id,
+                        delay.as_secs()
+                    );
+                    RetryEmail {
+                        id: email.id,
+                        delay_secs: delay.as_secs() as i64,
+                    }
+                    .query(&shared.db.retry_email_statement)
+                    .execute(&mut connection)
+                    .await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends the queued emails, retrying failed ones with exponential backoff.
+/// Stops on shutdown, after the email that is currently sent.
+pub async fn email_outbox_worker(pool: AnyPool, shared: Arc<Shared>) {
+    loop {
+        if let Err(err) = send_pending_emails(&pool, &shared).await {
+            log::error!(target: "email-outbox", "{err}");
+        }
+
+        tokio::select! {
+            _ = shared.email.outbox_notify.notified() => {}
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            _ = shared.shutdown.wait() => return,
+        }
+    }
+}
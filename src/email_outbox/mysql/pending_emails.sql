@@ -0,0 +1,15 @@
+SELECT
+    email_outbox.id,
+    email_outbox.recipient,
+    email_outbox.subject,
+    email_outbox.html,
+    email_outbox.text,
+    email_outbox.attempts
+FROM
+    email_outbox
+WHERE
+    email_outbox.next_attempt <= UTC_TIMESTAMP()
+ORDER BY
+    email_outbox.id ASC
+LIMIT
+    32;
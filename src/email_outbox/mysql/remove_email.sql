@@ -0,0 +1,4 @@
+DELETE FROM
+    email_outbox
+WHERE
+    email_outbox.id = ?;
@@ -0,0 +1,7 @@
+UPDATE
+    email_outbox
+SET
+    email_outbox.attempts = email_outbox.attempts + 1,
+    email_outbox.next_attempt = DATE_ADD(UTC_TIMESTAMP(), INTERVAL ? SECOND)
+WHERE
+    email_outbox.id = ?;
@@ -0,0 +1,270 @@
+use anyhow::anyhow;
+use axum::async_trait;
+use ddnet_account_sql::query::Query;
+use sqlx::Executor;
+use sqlx::Row;
+use sqlx::Statement;
+
+pub struct AddEmail<'a> {
+    pub recipient: &'a str,
+    pub subject: &'a str,
+    pub html: &'a str,
+    pub text: &'a str,
+}
+
+#[async_trait]
+impl Query<()> for AddEmail<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/add_email.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/add_email.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement
+            .query()
+            .bind(self.recipient)
+            .bind(self.subject)
+            .bind(self.html)
+            .bind(self.text)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement
+            .query()
+            .bind(self.recipient)
+            .bind(self.subject)
+            .bind(self.html)
+            .bind(self.text)
+    }
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+}
+
+/// The emails that are due to be sent (again).
+pub struct PendingEmails {}
+
+pub struct PendingEmailData {
+    pub id: i64,
+    pub recipient: String,
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+    pub attempts: i32,
+}
+
+#[async_trait]
+impl Query<PendingEmailData> for PendingEmails {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/pending_emails.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/pending_emails.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement.query()
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query()
+    }
+    fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<PendingEmailData> {
+        Ok(PendingEmailData {
+            id: row
+                .try_get("id")
+                .map_err(|err| anyhow!("Failed get column id: {err}"))?,
+            recipient: row
+                .try_get("recipient")
+                .map_err(|err| anyhow!("Failed get column recipient: {err}"))?,
+            subject: row
+                .try_get("subject")
+                .map_err(|err| anyhow!("Failed get column subject: {err}"))?,
+            html: row
+                .try_get("html")
+                .map_err(|err| anyhow!("Failed get column html: {err}"))?,
+            text: row
+                .try_get("text")
+                .map_err(|err| anyhow!("Failed get column text: {err}"))?,
+            attempts: row
+                .try_get("attempts")
+                .map_err(|err| anyhow!("Failed get column attempts: {err}"))?,
+        })
+    }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<PendingEmailData> {
+        Ok(PendingEmailData {
+            id: row
+                .try_get("id")
+                .map_err(|err| anyhow!("Failed get column id: {err}"))?,
+            recipient: row
+                .try_get("recipient")
+                .map_err(|err| anyhow!("Failed get column recipient: {err}"))?,
+            subject: row
+                .try_get("subject")
+                .map_err(|err| anyhow!("Failed get column subject: {err}"))?,
+            html: row
+                .try_get("html")
+                .map_err(|err| anyhow!("Failed get column html: {err}"))?,
+            text: row
+                .try_get("text")
+                .map_err(|err| anyhow!("Failed get column text: {err}"))?,
+            attempts: row
+                .try_get("attempts")
+                .map_err(|err| anyhow!("Failed get column attempts: {err}"))?,
+        })
+    }
+}
+
+pub struct RemoveEmail {
+    pub id: i64,
+}
+
+#[async_trait]
+impl Query<()> for RemoveEmail {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/remove_email.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/remove_email.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement.query().bind(self.id)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.id)
+    }
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+}
+
+/// Counts a failed attempt & delays the next one.
+pub struct RetryEmail {
+    pub id: i64,
+    pub delay_secs: i64,
+}
+
+#[async_trait]
+impl Query<()> for RetryEmail {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/retry_email.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/retry_email.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement.query().bind(self.delay_secs).bind(self.id)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.delay_secs).bind(self.id)
+    }
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+}
+
+/// Claims a due email for a lease,
+/// so no other account server sends it meanwhile.
+pub struct ClaimEmail {
+    pub id: i64,
+    pub lease_secs: i64,
+}
+
+#[async_trait]
+impl Query<()> for ClaimEmail {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/claim_email.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/claim_email.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement.query().bind(self.lease_secs).bind(self.id)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.lease_secs).bind(self.id)
+    }
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+}
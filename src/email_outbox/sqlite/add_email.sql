@@ -0,0 +1,11 @@
+INSERT INTO
+    email_outbox (
+        recipient,
+        subject,
+        html,
+        text,
+        create_time,
+        next_attempt
+    )
+VALUES
+    (?, ?, ?, ?, datetime('now'), datetime('now'));
@@ -0,0 +1,7 @@
+UPDATE
+    email_outbox
+SET
+    next_attempt = datetime('now', ? || ' seconds')
+WHERE
+    email_outbox.id = ?
+    AND email_outbox.next_attempt <= datetime('now');
@@ -0,0 +1,7 @@
+UPDATE
+    email_outbox
+SET
+    attempts = attempts + 1,
+    next_attempt = datetime('now', ? || ' seconds')
+WHERE
+    email_outbox.id = ?;
@@ -0,0 +1,290 @@
+use std::{collections::HashMap, path::Path};
+
+use ddnet_accounts_shared::client::{
+    account_token::AccountTokenOperation, credential_auth_token::CredentialAuthTokenOperation,
+};
+
+use crate::email::AccountNotification;
+
+/// Every kind of email the account server sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailKind {
+    CredentialAuthToken(CredentialAuthTokenOperation),
+    AccountToken(AccountTokenOperation),
+    Notification(AccountNotification),
+}
+
+impl EmailKind {
+    const CREDENTIAL_AUTH_TOKENS: &'static str = "credential_auth_tokens";
+    const ACCOUNT_TOKENS: &'static str = "account_tokens";
+    const NOTIFICATIONS: &'static str = "notifications";
+    const GROUPS: [&'static str; 3] = [
+        Self::CREDENTIAL_AUTH_TOKENS,
+        Self::ACCOUNT_TOKENS,
+        Self::NOTIFICATIONS,
+    ];
+
+    pub const ALL: [Self; 9] = [
+        Self::CredentialAuthToken(CredentialAuthTokenOperation::Login),
+        Self::CredentialAuthToken(CredentialAuthTokenOperation::LinkCredential),
+        Self::CredentialAuthToken(CredentialAuthTokenOperation::UnlinkCredential),
+        Self::AccountToken(AccountTokenOperation::LogoutAll),
+        Self::AccountToken(AccountTokenOperation::LinkCredential),
+        Self::AccountToken(AccountTokenOperation::Delete),
+        Self::Notification(AccountNotification::CredentialLinked),
+        Self::Notification(AccountNotification::CredentialUnlinked),
+        Self::Notification(AccountNotification::LogoutAll),
+    ];
+
+    /// The sub directory of the templates directory, that contains the template.
+    const fn group(&self) -> &'static str {
+        match self {
+            Self::CredentialAuthToken(_) => Self::CREDENTIAL_AUTH_TOKENS,
+            Self::AccountToken(_) => Self::ACCOUNT_TOKENS,
+            Self::Notification(_) => Self::NOTIFICATIONS,
+        }
+    }
+
+    /// The file name of the template without extension,
+    /// e.g. `login` for `credential_auth_tokens/login.html`.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::CredentialAuthToken(op) => op.into(),
+            Self::AccountToken(op) => op.into(),
+            Self::Notification(notification) => match notification {
+                AccountNotification::CredentialLinked => "credential_linked",
+                AccountNotification::CredentialUnlinked => "credential_unlinked",
+                AccountNotification::LogoutAll => "logout_all",
+            },
+        }
+    }
+
+    fn key(&self) -> (&'static str, &'static str) {
+        (self.group(), self.name())
+    }
+
+    pub const fn default_subject(&self) -> &'static str {
+        match self {
+            Self::CredentialAuthToken(op) => match op {
+                CredentialAuthTokenOperation::Login => "DDNet Account Login",
+                CredentialAuthTokenOperation::LinkCredential => "DDNet Link E-mail To Account",
+                CredentialAuthTokenOperation::UnlinkCredential => "DDNet Unlink Credential",
+            },
+            Self::AccountToken(op) => match op {
+                AccountTokenOperation::LogoutAll => "DDNet Logout All Sessions",
+                AccountTokenOperation::LinkCredential => "DDNet Link Credential",
+                AccountTokenOperation::Delete => "DDNet Delete Account",
+            },
+            Self::Notification(notification) => match notification {
+                AccountNotification::CredentialLinked => "DDNet Credential Linked",
+                AccountNotification::CredentialUnlinked => "DDNet Credential Unlinked",
+                AccountNotification::LogoutAll => "DDNet All Sessions Logged Out",
+            },
+        }
+    }
+
+    const fn default_html(&self) -> &'static str {
+        match self {
+            Self::CredentialAuthToken(_) | Self::AccountToken(_) => {
+                "<p>Hello %SUBJECT%,</p>\n\
+                <p>Please use the following token to verify your action:</p>\n\
+                <pre>%CODE%</pre>"
+            }
+            Self::Notification(AccountNotification::CredentialLinked) => {
+                "<p>Hello %SUBJECT%,</p>\n\
+                <p>A %CREDENTIAL% credential was linked to your account from %CLIENT_IP%.</p>\n\
+                <p>If this was not you, your account might be compromised.</p>"
+            }
+            Self::Notification(AccountNotification::CredentialUnlinked) => {
+                "<p>Hello %SUBJECT%,</p>\n\
+                <p>A %CREDENTIAL% credential was unlinked from your account from %CLIENT_IP%.</p>\n\
+                <p>If this was not you, your account might be compromised.</p>"
+            }
+            Self::Notification(AccountNotification::LogoutAll) => {
+                "<p>Hello %SUBJECT%,</p>\n\
+                <p>All sessions of your account were logged out from %CLIENT_IP%.</p>\n\
+                <p>If this was not you, your account might be compromised.</p>"
+            }
+        }
+    }
+
+    const fn default_text(&self) -> &'static str {
+        match self {
+            Self::CredentialAuthToken(_) | Self::AccountToken(_) => {
+                "Hello %SUBJECT%,\n\n\
+                Please use the following token to verify your action:\n\n\
+                %CODE%\n"
+            }
+            Self::Notification(AccountNotification::CredentialLinked) => {
+                "Hello %SUBJECT%,\n\n\
+                A %CREDENTIAL% credential was linked to your account from %CLIENT_IP%.\n\
+                If this was not you, your account might be compromised.\n"
+            }
+            Self::Notification(AccountNotification::CredentialUnlinked) => {
+                "Hello %SUBJECT%,\n\n\
+                A %CREDENTIAL% credential was unlinked from your account from %CLIENT_IP%.\n\
+                If this was not you, your account might be compromised.\n"
+            }
+            Self::Notification(AccountNotification::LogoutAll) => {
+                "Hello %SUBJECT%,\n\n\
+                All sessions of your account were logged out from %CLIENT_IP%.\n\
+                If this was not you, your account might be compromised.\n"
+            }
+        }
+    }
+}
+
+/// The parts of a template, every part is optional.
+#[derive(Debug, Default, Clone)]
+struct EmailTemplate {
+    subject: Option<String>,
+    html: Option<String>,
+    text: Option<String>,
+}
+
+type EmailTemplateSet = HashMap<(&'static str, &'static str), EmailTemplate>;
+
+/// A ready to send email.
+#[derive(Debug, Clone)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+/// All email templates of the templates directory:
+/// `<group>/<name>.{subject,html,txt}` for the default language (english)
+/// and `<locale>/<group>/<name>.{subject,html,txt}` for translations.
+///
+/// Every part of a template falls back to the language without region (`pt-br` -> `pt`),
+/// then to the default language & finally to a built-in english template.
+#[derive(Debug, Default)]
+pub struct EmailTemplates {
+    default: EmailTemplateSet,
+    locales: HashMap<String, EmailTemplateSet>,
+    /// The html templates for all token mails from older versions,
+    /// directly inside the config directory.
+    legacy: HashMap<&'static str, String>,
+}
+
+/// `pt_BR` -> `pt-br`
+fn normalize_locale(locale: &str) -> String {
+    locale.trim().to_lowercase().replace('_', "-")
+}
+
+impl EmailTemplates {
+    pub const DIR: &'static str = "templates";
+
+    async fn load_set(dir: &Path) -> EmailTemplateSet {
+        let mut templates = EmailTemplateSet::default();
+        for kind in EmailKind::ALL {
+            let load = |ext: &str| {
+                let path = dir
+                    .join(kind.group())
+                    .join(format!("{}.{ext}", kind.name()));
+                async move { tokio::fs::read_to_string(path).await.ok() }
+            };
+            let template = EmailTemplate {
+                subject: load("subject")
+                    .await
+                    .map(|subject| subject.trim().to_string()),
+                html: load("html").await,
+                text: load("txt").await,
+            };
+            if template.subject.is_some() || template.html.is_some() || template.text.is_some() {
+                templates.insert(kind.key(), template);
+            }
+        }
+        templates
+    }
+
+    /// Loads the templates from the templates directory inside of `config_dir`.
+    /// Missing files are no error, they simply fall back to the built-in templates.
+    pub async fn load(config_dir: &Path) -> Self {
+        let dir = config_dir.join(Self::DIR);
+        let mut res = Self {
+            default: Self::load_set(&dir).await,
+            ..Default::default()
+        };
+
+        if let Ok(mut entries) = tokio::fs::read_dir(&dir).await {
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                if !entry
+                    .file_type()
+                    .await
+                    .is_ok_and(|file_type| file_type.is_dir())
+                {
+                    continue;
+                }
+                let Some(locale) = entry.file_name().to_str().map(normalize_locale) else {
+                    continue;
+                };
+                if EmailKind::GROUPS.contains(&locale.as_str()) {
+                    continue;
+                }
+                let templates = Self::load_set(&entry.path()).await;
+                if !templates.is_empty() {
+                    res.locales.insert(locale, templates);
+                }
+            }
+        }
+
+        for group in [EmailKind::CREDENTIAL_AUTH_TOKENS, EmailKind::ACCOUNT_TOKENS] {
+            if let Ok(html) =
+                tokio::fs::read_to_string(config_dir.join(format!("{group}.html"))).await
+            {
+                res.legacy.insert(group, html);
+            }
+        }
+
+        res
+    }
+
+    /// Renders the email for the given locale,
+    /// replacing all `vars` (e.g. `%CODE%`) in every part.
+    pub fn render(
+        &self,
+        kind: EmailKind,
+        locale: Option<&str>,
+        vars: &[(&str, &str)],
+    ) -> RenderedEmail {
+        let locale = locale.map(normalize_locale);
+        let language = locale
+            .as_deref()
+            .and_then(|locale| locale.split_once('-'))
+            .map(|(language, _)| language);
+        let templates: Vec<&EmailTemplate> = [locale.as_deref(), language]
+            .into_iter()
+            .flatten()
+            .filter_map(|locale| self.locales.get(locale))
+            .chain(std::iter::once(&self.default))
+            .filter_map(|templates| templates.get(&kind.key()))
+            .collect();
+        let part = |get: fn(&EmailTemplate) -> Option<&String>| {
+            templates.iter().find_map(|template| get(template))
+        };
+
+        let replace = |s: &str| {
+            vars.iter()
+                .fold(s.to_string(), |s, (var, value)| s.replace(var, value))
+        };
+        RenderedEmail {
+            subject: replace(
+                part(|template| template.subject.as_ref())
+                    .map(|subject| subject.as_str())
+                    .unwrap_or_else(|| kind.default_subject()),
+            ),
+            html: replace(
+                part(|template| template.html.as_ref())
+                    .or_else(|| self.legacy.get(kind.group()))
+                    .map(|html| html.as_str())
+                    .unwrap_or_else(|| kind.default_html()),
+            ),
+            text: replace(
+                part(|template| template.text.as_ref())
+                    .map(|text| text.as_str())
+                    .unwrap_or_else(|| kind.default_text()),
+            ),
+        }
+    }
+}
@@ -55,8 +55,9 @@ impl FileWatcher {
                             notify::event::RemoveKind::File | notify::event::RemoveKind::Folder
                         )
                     );
-                    // check if the file exists
-                    let file_exists = ev.paths.iter().any(|path| self.file.eq(path));
+                    // check if the file exists,
+                    // if a directory is watched, any file inside counts
+                    let file_exists = ev.paths.iter().any(|path| path.starts_with(&self.file));
                     if file_exists && handle_ev {
                         // if the file exist, make sure the file is not modified for at least 1 second
                         let mut last_modified = None;
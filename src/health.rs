@@ -0,0 +1,160 @@
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use ddnet_account_sql::any::AnyPool;
+use ddnet_accounts_shared::account_server::health::{HealthCheck, HealthReport, HealthStatus};
+use tokio::sync::Mutex;
+
+use crate::shared::Shared;
+
+/// How long a single check may take, before it counts as failed.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+/// Readiness probes are usually sent every few seconds,
+/// the smtp server & steam don't have to be contacted for each of them.
+const CACHE_TIME: Duration = Duration::from_secs(10);
+/// The signing keys are rotated a week before the current certificate expires,
+/// if it expires sooner the rotation failed.
+const CERT_FRESHNESS: Duration = Duration::from_secs(60 * 60 * 24 * 6);
+
+/// The last readiness report.
+/// The lock is held during the checks, so concurrent probes wait for the same checks.
+#[derive(Debug, Default)]
+pub struct HealthCache(Mutex<Option<(Instant, Arc<HealthReport>)>>);
+
+/// Runs a check, its error is only logged.
+/// The report is public, so it only contains the `failed_message`.
+async fn check<F: Future<Output = anyhow::Result<()>>>(
+    name: &str,
+    // the state & message if the check fails
+    failed_status: HealthStatus,
+    failed_message: &str,
+    check: F,
+) -> HealthCheck {
+    let start = Instant::now();
+    let res = tokio::time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .unwrap_or_else(|_| {
+            Err(anyhow::anyhow!(
+                "No response within {}s.",
+                CHECK_TIMEOUT.as_secs()
+            ))
+        });
+    let (status, message) = match res {
+        Ok(()) => (HealthStatus::Ok, None),
+        Err(err) => {
+            log::warn!(target: "health", "{name}: {err:#}");
+            (failed_status, Some(failed_message.to_string()))
+        }
+    };
+    HealthCheck {
+        name: name.to_string(),
+        status,
+        message,
+        duration_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+async fn check_db(pool: &AnyPool) -> anyhow::Result<()> {
+    let mut connection = pool.acquire().await?;
+    connection.acquire().await?;
+    Ok(())
+}
+
+fn check_cert(shared: &Shared) -> HealthCheck {
+    let not_after = shared
+        .signing_keys
+        .read()
+        .current_cert
+        .tbs_certificate
+        .validity
+        .not_after
+        .to_system_time();
+    let remaining = not_after.duration_since(SystemTime::now()).ok();
+    let (status, message) = match remaining {
+        None => (
+            HealthStatus::Failed,
+            Some("The current certificate expired.".to_string()),
+        ),
+        Some(remaining) if remaining < CERT_FRESHNESS => (
+            HealthStatus::Degraded,
+            Some(format!(
+                "The current certificate expires in {}h, the rotation failed.",
+                remaining.as_secs() / 60 / 60
+            )),
+        ),
+        Some(_) => (HealthStatus::Ok, None),
+    };
+    HealthCheck {
+        name: "cert".to_string(),
+        status,
+        message,
+        duration_ms: 0,
+    }
+}
+
+/// Checks all dependencies of the account server.
+/// Without database or valid certificate no request can be handled,
+/// without smtp server or steam only some of them.
+pub async fn check_ready(shared: &Shared, pool: &AnyPool) -> HealthReport {
+    let (db, email, steam) = tokio::join!(
+        check(
+            "database",
+            HealthStatus::Failed,
+            "The database is not reachable.",
+            check_db(pool)
+        ),
+        check(
+            "email",
+            HealthStatus::Degraded,
+            "The email transport is not reachable.",
+            shared.email.test_connection()
+        ),
+        check(
+            "steam",
+            HealthStatus::Degraded,
+            "Steam is not reachable.",
+            shared.steam.test_connection()
+        ),
+    );
+    let checks = vec![db, check_cert(shared), email, steam];
+    HealthReport {
+        status: checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(HealthStatus::Ok),
+        checks,
+    }
+}
+
+pub async fn health_live_request() -> Json<HealthStatus> {
+    Json(HealthStatus::Ok)
+}
+
+/// Only a ready account server responds with `200 OK`,
+/// so load balancers can skip degraded instances.
+pub async fn health_ready_request(shared: Arc<Shared>, pool: AnyPool) -> impl IntoResponse {
+    let report = {
+        let mut cache = shared.health.0.lock().await;
+        match cache
+            .as_ref()
+            .filter(|(time, _)| time.elapsed() < CACHE_TIME)
+        {
+            Some((_, report)) => report.clone(),
+            None => {
+                let report = Arc::new(check_ready(&shared, &pool).await);
+                *cache = Some((Instant::now(), report.clone()));
+                report
+            }
+        }
+    };
+    let status = match report.status {
+        HealthStatus::Ok => StatusCode::OK,
+        HealthStatus::Degraded | HealthStatus::Failed => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status, Json(report.as_ref().clone()))
+}
@@ -0,0 +1,261 @@
+use std::{
+    future::IntoFuture,
+    io::BufReader,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use axum::{extract::ConnectInfo, http::Request, Router};
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto,
+    service::TowerToHyperService,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use socket2::{Domain, Socket, Type};
+use tokio::net::TcpListener;
+use tokio_rustls::{
+    rustls::{
+        pki_types::{CertificateDer, PrivateKeyDer},
+        ServerConfig,
+    },
+    TlsAcceptor,
+};
+use tower::ServiceExt;
+
+use crate::{file_watcher::FileWatcher, shutdown::Shutdown};
+
+/// Certificate & private key in PEM format.
+/// Both files are reloaded on change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsDetails {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+impl TlsDetails {
+    fn load(&self) -> anyhow::Result<TlsAcceptor> {
+        let certs =
+            rustls_pemfile::certs(&mut BufReader::new(std::fs::File::open(&self.cert_path)?))
+                .collect::<Result<Vec<CertificateDer<'static>>, _>>()?;
+        let key: PrivateKeyDer<'static> =
+            rustls_pemfile::private_key(&mut BufReader::new(std::fs::File::open(&self.key_path)?))?
+                .ok_or_else(|| anyhow!("no private key found in {:?}", self.key_path))?;
+
+        let mut config = ServerConfig::builder_with_provider(Arc::new(
+            tokio_rustls::rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+
+    fn watcher(path: &Path) -> FileWatcher {
+        FileWatcher::new(
+            path.parent().unwrap_or_else(|| ".".as_ref()),
+            path.file_name().unwrap_or_default().as_ref(),
+        )
+    }
+}
+
+struct Tls {
+    details: TlsDetails,
+    acceptor: RwLock<TlsAcceptor>,
+}
+
+impl Tls {
+    async fn reload_on_change(&self) {
+        let mut cert = TlsDetails::watcher(&self.details.cert_path);
+        let mut key = TlsDetails::watcher(&self.details.key_path);
+        loop {
+            let changed = tokio::select! {
+                res = cert.wait_for_change() => res,
+                res = key.wait_for_change() => res,
+            };
+            if changed.is_err() {
+                break;
+            }
+            // cert & key are usually replaced together,
+            // a mismatching pair keeps the old acceptor.
+            match self.details.load() {
+                Ok(acceptor) => {
+                    *self.acceptor.write() = acceptor;
+                    log::info!(target: "tls", "reloaded certificate");
+                }
+                Err(err) => log::error!(target: "tls", "could not reload certificate: {err}"),
+            }
+        }
+    }
+}
+
+/// All sockets the account server listens on.
+pub struct HttpListener {
+    listeners: Vec<TcpListener>,
+    tls: Option<Arc<Tls>>,
+}
+
+fn bind_socket(addr: SocketAddr, dual_stack: bool) -> anyhow::Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, None)?;
+    if addr.is_ipv6() {
+        socket.set_only_v6(!dual_stack)?;
+    }
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    Ok(TcpListener::from_std(socket.into())?)
+}
+
+impl HttpListener {
+    /// Binds all addresses, without any address only localhost is used.
+    /// An unspecified IPv6 address (`::`) also accepts IPv4 connections,
+    /// unless IPv4 addresses are bound explicitly.
+    pub fn bind(bind: &[IpAddr], port: u16, tls: Option<&TlsDetails>) -> anyhow::Result<Self> {
+        let bind = if bind.is_empty() {
+            &[IpAddr::V4(Ipv4Addr::LOCALHOST)][..]
+        } else {
+            bind
+        };
+        let dual_stack = bind.iter().all(|addr| addr.is_ipv6());
+        let listeners = bind
+            .iter()
+            .map(|addr| {
+                bind_socket(
+                    SocketAddr::new(*addr, port),
+                    dual_stack && addr.is_unspecified(),
+                )
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let tls = tls
+            .map(|details| {
+                anyhow::Ok(Arc::new(Tls {
+                    acceptor: RwLock::new(details.load()?),
+                    details: details.clone(),
+                }))
+            })
+            .transpose()?;
+
+        Ok(Self { listeners, tls })
+    }
+
+    pub fn local_addrs(&self) -> anyhow::Result<Vec<SocketAddr>> {
+        Ok(self
+            .listeners
+            .iter()
+            .map(|listener| listener.local_addr())
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Serves the app on all sockets.
+    /// After a shutdown no new connections are accepted,
+    /// but the in-flight requests are finished.
+    pub async fn serve(self, app: Router, shutdown: Shutdown) -> anyhow::Result<()> {
+        let Some(tls) = self.tls else {
+            let app = app.into_make_service_with_connect_info::<SocketAddr>();
+            futures::future::try_join_all(self.listeners.into_iter().map(|listener| {
+                let shutdown = shutdown.clone();
+                axum::serve(listener, app.clone())
+                    .with_graceful_shutdown(async move { shutdown.wait().await })
+                    .into_future()
+            }))
+            .await?;
+            return Ok(());
+        };
+
+        let tls_watcher = tls.clone();
+        let reload = tokio::spawn(async move { tls_watcher.reload_on_change().await });
+        let res = futures::future::try_join_all(
+            self.listeners
+                .into_iter()
+                .map(|listener| serve_tls(listener, app.clone(), tls.clone(), shutdown.clone())),
+        )
+        .await;
+        reload.abort();
+        res?;
+        Ok(())
+    }
+}
+
+/// How long to wait before accepting connections again after an error.
+const ACCEPT_ERROR_DELAY: Duration = Duration::from_secs(1);
+/// How long a client has to finish the tls handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+async fn serve_tls(
+    listener: TcpListener,
+    app: Router,
+    tls: Arc<Tls>,
+    shutdown: Shutdown,
+) -> anyhow::Result<()> {
+    let mut connections = tokio::task::JoinSet::new();
+    loop {
+        let res = tokio::select! {
+            res = listener.accept() => res,
+            _ = shutdown.wait() => break,
+        };
+        let (socket, client_addr) = match res {
+            Ok(res) => res,
+            Err(err) => {
+                // e.g. too many open files, like axum for plain tcp
+                // listeners try again later instead of stopping the server
+                log::error!(target: "tls", "accepting a connection failed: {err}");
+                tokio::time::sleep(ACCEPT_ERROR_DELAY).await;
+                continue;
+            }
+        };
+        let acceptor = tls.acceptor.read().clone();
+        let app = app.clone();
+        let shutdown = shutdown.clone();
+        connections.spawn(async move {
+            // clients that stall the handshake must not keep the task alive
+            let res = tokio::select! {
+                res = tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(socket)) => res,
+                _ = shutdown.wait() => return,
+            };
+            let socket = match res {
+                Ok(Ok(socket)) => socket,
+                Ok(Err(err)) => {
+                    log::debug!(target: "tls", "handshake failed: {err}");
+                    return;
+                }
+                Err(_) => {
+                    log::debug!(target: "tls", "handshake timed out");
+                    return;
+                }
+            };
+            // axum can only add the connect info for plain tcp listeners
+            let service = app.map_request(move |mut req: Request<_>| {
+                req.extensions_mut().insert(ConnectInfo(client_addr));
+                req
+            });
+            let builder = auto::Builder::new(TokioExecutor::new());
+            let conn =
+                builder.serve_connection(TokioIo::new(socket), TowerToHyperService::new(service));
+            tokio::pin!(conn);
+            let res = tokio::select! {
+                res = conn.as_mut() => res,
+                _ = shutdown.wait() => {
+                    // finish the in-flight requests, but don't accept new ones
+                    conn.as_mut().graceful_shutdown();
+                    conn.await
+                }
+            };
+            if let Err(err) = res {
+                log::debug!(target: "tls", "connection failed: {err}");
+            }
+        });
+        // forget finished connections
+        while connections.try_join_next().is_some() {}
+    }
+    drop(listener);
+    while connections.join_next().await.is_some() {}
+    Ok(())
+}
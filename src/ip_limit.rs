@@ -1,5 +1,5 @@
 use std::{
-    net::SocketAddr,
+    net::IpAddr,
     path::{Path, PathBuf},
     str::FromStr,
     sync::Arc,
@@ -7,19 +7,18 @@ use std::{
 
 use axum::{
     body::Body,
-    extract::{ConnectInfo, State},
+    extract::State,
     http::Request,
     middleware::Next,
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
 use ddnet_accounts_shared::account_server::{
     errors::AccountServerRequestError, result::AccountServerReqResult,
 };
-use parking_lot::RwLock;
 use reqwest::StatusCode;
 
-use crate::file_watcher::FileWatcher;
+use crate::{client_ip::ClientIp, file_watcher::FileWatcher, shared::Shared};
 
 #[derive(Debug, Default)]
 pub struct IpDenyList {
@@ -28,10 +27,10 @@ pub struct IpDenyList {
 }
 
 impl IpDenyList {
-    pub fn is_banned(&self, addr: SocketAddr) -> bool {
+    pub fn is_banned(&self, addr: IpAddr) -> bool {
         match addr {
-            SocketAddr::V4(ip) => self.ipv4.contains(&ipnet::Ipv4Net::from(*ip.ip())),
-            SocketAddr::V6(ip) => self.ipv6.contains(&ipnet::Ipv6Net::from(*ip.ip())),
+            IpAddr::V4(ip) => self.ipv4.contains(&ipnet::Ipv4Net::from(ip)),
+            IpAddr::V6(ip) => self.ipv6.contains(&ipnet::Ipv6Net::from(ip)),
         }
     }
 
@@ -79,12 +78,13 @@ impl IpDenyList {
 }
 
 pub async fn ip_deny_layer(
-    State(deny_list): State<Arc<RwLock<IpDenyList>>>,
-    ConnectInfo(client_ip): ConnectInfo<SocketAddr>,
+    State(shared): State<Arc<Shared>>,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
     req: Request<Body>,
     next: Next,
 ) -> Result<Response<Body>, StatusCode> {
-    if deny_list.read().is_banned(client_ip) {
+    if shared.ip_ban_list.read().is_banned(client_ip) {
+        shared.metrics.ip_denied.inc();
         Ok(Json(AccountServerReqResult::<(), ()>::Err(
             AccountServerRequestError::VpnBan(
                 "VPN detected. Please deactivate the VPN and try again.".to_string(),
@@ -1,13 +1,13 @@
 pub mod queries;
 
-use std::{str::FromStr, sync::Arc};
+use std::{net::IpAddr, str::FromStr, sync::Arc};
 
-use axum::Json;
+use axum::{Extension, Json};
 use ddnet_account_sql::{any::AnyPool, is_duplicate_entry, query::Query};
 use ddnet_accounts_shared::{
     account_server::{
-        errors::{AccountServerRequestError, Empty},
-        result::AccountServerReqResult,
+        account_events::AccountEventKind, errors::RequestFailure,
+        link_credential::LinkCredentialError, result::AccountServerReqResult,
     },
     client::{
         credential_auth_token::CredentialAuthTokenOperation, link_credential::LinkCredentialRequest,
@@ -16,11 +16,15 @@ use ddnet_accounts_shared::{
 use queries::{UnlinkCredentialEmail, UnlinkCredentialSteam};
 
 use crate::{
+    account_events::add_account_event,
     account_token::queries::{AccountTokenQry, InvalidateAccountToken},
+    client_ip::ClientIp,
+    email::{linked_email, send_notification, AccountNotification},
     login::{
         get_and_invalidate_credential_auth_token,
         queries::{LinkAccountCredentialEmail, LinkAccountCredentialSteam},
     },
+    request_error::request_logic_error,
     shared::Shared,
     types::{AccountTokenType, TokenType},
 };
@@ -28,27 +32,29 @@ use crate::{
 pub async fn link_credential_request(
     shared: Arc<Shared>,
     pool: AnyPool,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
     Json(data): Json<LinkCredentialRequest>,
-) -> Json<AccountServerReqResult<(), Empty>> {
-    Json(link_credential(shared, pool, data).await.map_err(|err| {
-        AccountServerRequestError::Unexpected {
-            target: "link_credential".into(),
-            err: err.to_string(),
-            bt: err.backtrace().to_string(),
-        }
-    }))
+) -> Json<AccountServerReqResult<(), LinkCredentialError>> {
+    Json(
+        link_credential(shared, pool, client_ip, data)
+            .await
+            .map_err(|err| request_logic_error("link_credential", err)),
+    )
 }
 
 pub async fn link_credential(
     shared: Arc<Shared>,
     pool: AnyPool,
+    client_ip: IpAddr,
     data: LinkCredentialRequest,
 ) -> anyhow::Result<()> {
     let mut connection = pool.acquire().await?;
     let mut connection = connection.acquire().await?;
 
-    connection
+    let shared_clone = shared.clone();
+    let (notify, ty) = connection
         .transaction(|mut connection| {
+            let shared = shared_clone;
             Box::pin(async move {
                 // token data
                 let acc_token_qry = AccountTokenQry {
@@ -57,8 +63,9 @@ pub async fn link_credential(
 
                 let row = acc_token_qry
                     .query(&shared.db.account_token_qry_statement)
-                    .fetch_one(&mut connection.con())
-                    .await?;
+                    .fetch_optional(&mut connection.con())
+                    .await?
+                    .ok_or(LinkCredentialError::AccountTokenInvalid)?;
 
                 let token_data = AccountTokenQry::row_data(&row)?;
 
@@ -72,7 +79,7 @@ pub async fn link_credential(
 
                 anyhow::ensure!(
                     token_data.ty == AccountTokenType::LinkCredential,
-                    "Account token was not for logout all operation."
+                    RequestFailure::WrongTokenOp
                 );
                 let account_id = token_data.account_id;
 
@@ -82,15 +89,21 @@ pub async fn link_credential(
                     &mut connection.con(),
                 )
                 .await?
-                .ok_or_else(|| anyhow::anyhow!("Credential auth token is invalid/expired."))?;
+                .ok_or(LinkCredentialError::CredentialAuthTokenInvalid)?;
                 anyhow::ensure!(
                     token_data.op == CredentialAuthTokenOperation::LinkCredential,
-                    "Credential auth token was not for linking a new credential"
+                    RequestFailure::WrongTokenOp
                 );
 
+                // the previous email is notified, since it might be replaced
+                let mut notify = linked_email(&shared, &mut connection.con(), &account_id).await?;
+
                 match token_data.ty {
                     TokenType::Email => {
                         let email = email_address::EmailAddress::from_str(&token_data.identifier)?;
+                        if notify.is_none() {
+                            notify = Some(email.clone());
+                        }
                         // remove the current email, if exists.
                         let qry = UnlinkCredentialEmail {
                             account_id: &account_id,
@@ -113,7 +126,7 @@ pub async fn link_credential(
 
                         anyhow::ensure!(
                             !is_duplicate_entry(&res),
-                            "This email is already used for a different account."
+                            LinkCredentialError::CredentialAlreadyLinked
                         );
                         res?;
                     }
@@ -141,16 +154,39 @@ pub async fn link_credential(
 
                         anyhow::ensure!(
                             !is_duplicate_entry(&res),
-                            "This email is already used for a different account."
+                            LinkCredentialError::CredentialAlreadyLinked
                         );
                         res?;
                     }
                 }
 
-                anyhow::Ok(())
+                add_account_event(
+                    &shared,
+                    &mut connection.con(),
+                    &account_id,
+                    AccountEventKind::LinkCredential,
+                    client_ip,
+                    None,
+                    Some(token_data.ty.into()),
+                )
+                .await?;
+
+                anyhow::Ok((notify, token_data.ty))
             })
         })
         .await?;
 
+    if let Some(to) = notify {
+        send_notification(
+            &shared,
+            &mut connection,
+            &to,
+            AccountNotification::CredentialLinked,
+            Some(ty),
+            client_ip,
+        )
+        .await;
+    }
+
     Ok(())
 }
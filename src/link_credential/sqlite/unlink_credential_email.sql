@@ -0,0 +1,4 @@
+DELETE FROM
+    credential_email
+WHERE
+    account_id = ?;
@@ -0,0 +1,4 @@
+DELETE FROM
+    credential_steam
+WHERE
+    account_id = ?;
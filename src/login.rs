@@ -1,17 +1,21 @@
 pub mod queries;
 
-use std::{str::FromStr, sync::Arc};
+use std::{net::IpAddr, str::FromStr, sync::Arc};
 
-use axum::Json;
+use axum::{Extension, Json};
 use ddnet_account_sql::{
     any::{AnyConnection, AnyPool},
     query::Query,
 };
 use ddnet_accounts_shared::{
     account_server::{
-        errors::AccountServerRequestError, login::LoginError, result::AccountServerReqResult,
+        account_events::AccountEventKind,
+        ban::AccountBan,
+        errors::{AccountServerRequestError, RequestFailure},
+        login::LoginError,
+        result::AccountServerReqResult,
     },
-    client::login::{CredentialAuthToken, LoginRequest},
+    client::login::{CredentialAuthToken, LoginRequest, MAX_DEVICE_LABEL_LEN},
 };
 use ddnet_accounts_types::account_id::AccountId;
 use queries::{
@@ -20,7 +24,11 @@ use queries::{
 };
 
 use crate::{
+    account_events::add_account_event,
+    client_ip::ClientIp,
+    request_error::request_error,
     shared::Shared,
+    sign::account_ban,
     types::{CredentialAuthTokenType, TokenType},
 };
 
@@ -31,9 +39,10 @@ use self::queries::{
 pub async fn login_request(
     shared: Arc<Shared>,
     pool: AnyPool,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
     Json(data): Json<LoginRequest>,
 ) -> Json<AccountServerReqResult<AccountId, LoginError>> {
-    Json(login(shared, pool, data).await)
+    Json(login(shared, pool, client_ip, data).await)
 }
 
 #[derive(Debug, Clone)]
@@ -42,6 +51,8 @@ enum LoginResponse {
     Success(AccountId),
     /// Token invalid, probably timed out
     TokenInvalid,
+    /// The account is banned
+    Banned(AccountBan),
 }
 
 pub async fn get_and_invalidate_credential_auth_token(
@@ -68,6 +79,7 @@ pub async fn get_and_invalidate_credential_auth_token(
 pub async fn login(
     shared: Arc<Shared>,
     pool: AnyPool,
+    client_ip: IpAddr,
     data: LoginRequest,
 ) -> AccountServerReqResult<AccountId, LoginError> {
     let res = async {
@@ -79,6 +91,19 @@ pub async fn login(
             &data.credential_auth_token_signature,
         )?;
 
+        // the label is only informative, so shorten it instead of failing the login
+        let device_label = data
+            .device_label
+            .as_deref()
+            .map(|label| {
+                label
+                    .trim()
+                    .chars()
+                    .take(MAX_DEVICE_LABEL_LEN)
+                    .collect::<String>()
+            })
+            .filter(|label| !label.is_empty());
+
         let mut connection = pool.acquire().await?;
         let mut connection = connection.acquire().await?;
 
@@ -98,7 +123,7 @@ pub async fn login(
                     };
                     anyhow::ensure!(
                         token_data.op == CredentialAuthTokenType::Login,
-                        "Credential auth token was not for loggin in for new credential"
+                        RequestFailure::WrongTokenOp
                     );
 
                     enum Identifier {
@@ -151,7 +176,15 @@ pub async fn login(
                     };
 
                     let account_id = match account_id {
-                        Some(account_id) => account_id,
+                        Some(account_id) => {
+                            // banned accounts can't create new sessions
+                            if let Some(ban) =
+                                account_ban(&shared, &account_id, &mut connection.con()).await?
+                            {
+                                return Ok(LoginResponse::Banned(ban));
+                            }
+                            account_id
+                        }
                         None => {
                             let qry = TryCreateAccount {};
 
@@ -213,12 +246,24 @@ pub async fn login(
                         account_id,
                         hw_id: &data.account_data.hw_id,
                         pub_key: data.account_data.public_key.as_bytes(),
+                        device_label: device_label.as_deref(),
                     };
 
                     qry.query(&shared.db.create_session_statement)
                         .execute(&mut connection.con())
                         .await?;
 
+                    add_account_event(
+                        &shared,
+                        &mut connection.con(),
+                        &account_id,
+                        AccountEventKind::Login,
+                        client_ip,
+                        Some(&data.account_data.hw_id),
+                        Some(token_data.ty.into()),
+                    )
+                    .await?;
+
                     anyhow::Ok(LoginResponse::Success(account_id))
                 })
             })
@@ -226,16 +271,15 @@ pub async fn login(
         anyhow::Ok(res)
     }
     .await
-    .map_err(|err| AccountServerRequestError::Unexpected {
-        target: "login".into(),
-        err: err.to_string(),
-        bt: err.backtrace().to_string(),
-    })?;
+    .map_err(|err| request_error("login", err))?;
 
     match res {
         LoginResponse::Success(account_id) => Ok(account_id),
         LoginResponse::TokenInvalid => Err(AccountServerRequestError::LogicError(
             LoginError::TokenInvalid,
         )),
+        LoginResponse::Banned(ban) => Err(AccountServerRequestError::LogicError(
+            LoginError::Banned(ban),
+        )),
     }
 }
@@ -2,7 +2,10 @@ INSERT INTO
     user_session (
         account_id,
         pub_key,
-        hw_id
+        hw_id,
+        create_time,
+        last_sign_time,
+        device_label
     )
 VALUES
-    (?, ?, ?);
+    (?, ?, ?, UTC_TIMESTAMP(), UTC_TIMESTAMP(), ?);
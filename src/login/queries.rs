@@ -32,12 +32,25 @@ impl Query<CredentialAuthTokenData> for CredentialAuthTokenQry<'_> {
             .prepare(include_str!("mysql/credential_auth_token_data.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/credential_auth_token_data.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query().bind(self.token.as_slice())
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.token.as_slice())
+    }
     fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<CredentialAuthTokenData> {
         Ok(CredentialAuthTokenData {
             ty: TokenType::from_str(
@@ -53,6 +66,21 @@ impl Query<CredentialAuthTokenData> for CredentialAuthTokenQry<'_> {
             )?,
         })
     }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<CredentialAuthTokenData> {
+        Ok(CredentialAuthTokenData {
+            ty: TokenType::from_str(
+                row.try_get("ty")
+                    .map_err(|err| anyhow!("Failed get column ty: {err}"))?,
+            )?,
+            identifier: row
+                .try_get("identifier")
+                .map_err(|err| anyhow!("Failed get column identifier: {err}"))?,
+            op: CredentialAuthTokenOperation::from_str(
+                row.try_get("op")
+                    .map_err(|err| anyhow!("Failed get column op: {err}"))?,
+            )?,
+        })
+    }
 }
 
 pub struct InvalidateCredentialAuthToken<'a> {
@@ -68,15 +96,31 @@ impl Query<()> for InvalidateCredentialAuthToken<'_> {
             .prepare(include_str!("mysql/invalidate_credential_auth_token.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/invalidate_credential_auth_token.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query().bind(self.token.as_slice())
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.token.as_slice())
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
 
 pub struct TryCreateAccount {}
@@ -90,15 +134,31 @@ impl Query<()> for TryCreateAccount {
             .prepare(include_str!("mysql/add_account.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/add_account.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query()
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query()
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
 
 pub struct LinkAccountCredentialEmail<'a> {
@@ -115,6 +175,13 @@ impl Query<()> for LinkAccountCredentialEmail<'_> {
             .prepare(include_str!("mysql/link_credential_email.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/link_credential_email.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
@@ -124,9 +191,21 @@ impl Query<()> for LinkAccountCredentialEmail<'_> {
             .bind(self.account_id)
             .bind(self.email.as_str())
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement
+            .query()
+            .bind(self.account_id)
+            .bind(self.email.as_str())
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
 
 pub struct LinkAccountCredentialSteam<'a> {
@@ -143,15 +222,31 @@ impl Query<()> for LinkAccountCredentialSteam<'_> {
             .prepare(include_str!("mysql/link_credential_steam.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/link_credential_steam.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query().bind(self.account_id).bind(self.steamid64)
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.account_id).bind(self.steamid64)
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
 
 pub struct AccountData {
@@ -169,12 +264,25 @@ impl Query<AccountData> for AccountIdFromLastInsert {
             .prepare(include_str!("mysql/account_id_from_last_insert.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/account_id_from_last_insert.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query()
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query()
+    }
     fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<AccountData> {
         Ok(AccountData {
             account_id: row
@@ -182,6 +290,13 @@ impl Query<AccountData> for AccountIdFromLastInsert {
                 .map_err(|err| anyhow!("Failed get column account id: {err}"))?,
         })
     }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<AccountData> {
+        Ok(AccountData {
+            account_id: row
+                .try_get("account_id")
+                .map_err(|err| anyhow!("Failed get column account id: {err}"))?,
+        })
+    }
 }
 
 pub struct AccountIdFromEmail<'a> {
@@ -197,12 +312,25 @@ impl Query<AccountData> for AccountIdFromEmail<'_> {
             .prepare(include_str!("mysql/account_id_from_email.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/account_id_from_email.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query().bind(self.email.as_str())
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.email.as_str())
+    }
     fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<AccountData> {
         Ok(AccountData {
             account_id: row
@@ -210,6 +338,13 @@ impl Query<AccountData> for AccountIdFromEmail<'_> {
                 .map_err(|err| anyhow!("Failed get column account id: {err}"))?,
         })
     }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<AccountData> {
+        Ok(AccountData {
+            account_id: row
+                .try_get("account_id")
+                .map_err(|err| anyhow!("Failed get column account id: {err}"))?,
+        })
+    }
 }
 
 pub struct AccountIdFromSteam<'a> {
@@ -225,12 +360,25 @@ impl Query<AccountData> for AccountIdFromSteam<'_> {
             .prepare(include_str!("mysql/account_id_from_steam.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/account_id_from_steam.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
     ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
         statement.query().bind(self.steamid64)
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.steamid64)
+    }
     fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<AccountData> {
         Ok(AccountData {
             account_id: row
@@ -238,12 +386,20 @@ impl Query<AccountData> for AccountIdFromSteam<'_> {
                 .map_err(|err| anyhow!("Failed get column account id: {err}"))?,
         })
     }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<AccountData> {
+        Ok(AccountData {
+            account_id: row
+                .try_get("account_id")
+                .map_err(|err| anyhow!("Failed get column account id: {err}"))?,
+        })
+    }
 }
 
 pub struct CreateSession<'a> {
     pub account_id: AccountId,
     pub pub_key: &'a [u8; ed25519_dalek::PUBLIC_KEY_LENGTH],
     pub hw_id: &'a MachineUid,
+    pub device_label: Option<&'a str>,
 }
 
 #[async_trait]
@@ -255,6 +411,13 @@ impl Query<()> for CreateSession<'_> {
             .prepare(include_str!("mysql/add_session.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/add_session.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
@@ -264,8 +427,23 @@ impl Query<()> for CreateSession<'_> {
             .bind(self.account_id)
             .bind(self.pub_key.as_slice())
             .bind(self.hw_id.as_slice())
+            .bind(self.device_label)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement
+            .query()
+            .bind(self.account_id)
+            .bind(self.pub_key.as_slice())
+            .bind(self.hw_id.as_slice())
+            .bind(self.device_label)
     }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
@@ -0,0 +1,6 @@
+SELECT
+    account_id
+FROM
+    credential_email
+WHERE
+    email = ?;
@@ -0,0 +1,2 @@
+SELECT
+    last_insert_rowid() AS account_id;
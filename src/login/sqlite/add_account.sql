@@ -0,0 +1,4 @@
+INSERT INTO
+    account (create_time)
+VALUES
+    (datetime('now'));
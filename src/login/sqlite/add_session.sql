@@ -0,0 +1,11 @@
+INSERT INTO
+    user_session (
+        account_id,
+        pub_key,
+        hw_id,
+        create_time,
+        last_sign_time,
+        device_label
+    )
+VALUES
+    (?, ?, ?, datetime('now'), datetime('now'), ?);
@@ -0,0 +1,4 @@
+DELETE FROM
+    credential_auth_tokens
+WHERE
+    credential_auth_tokens.token = ?;
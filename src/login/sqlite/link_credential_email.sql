@@ -0,0 +1,4 @@
+INSERT INTO
+    credential_email (account_id, email, valid_until)
+VALUES
+    (?, ?, datetime('now', '+1 year'));
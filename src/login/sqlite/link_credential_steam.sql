@@ -0,0 +1,4 @@
+INSERT INTO
+    credential_steam (account_id, steamid64, valid_until)
+VALUES
+    (?, ?, datetime('now', '+1 year'));
@@ -6,13 +6,16 @@ use axum::Json;
 use ddnet_account_sql::{any::AnyPool, query::Query};
 use ddnet_accounts_shared::{
     account_server::{
-        errors::{AccountServerRequestError, Empty},
+        errors::{Empty, RequestFailure},
         result::AccountServerReqResult,
     },
     client::logout::LogoutRequest,
 };
 
-use crate::shared::{Shared, CERT_MAX_AGE_DELTA, CERT_MIN_AGE_DELTA};
+use crate::{
+    request_error::request_error,
+    shared::{Shared, CERT_MAX_AGE_DELTA, CERT_MIN_AGE_DELTA},
+};
 
 use self::queries::RemoveSession;
 
@@ -24,11 +27,7 @@ pub async fn logout_request(
     Json(
         logout(shared, pool, data)
             .await
-            .map_err(|err| AccountServerRequestError::Unexpected {
-                target: "logout".into(),
-                err: err.to_string(),
-                bt: err.backtrace().to_string(),
-            }),
+            .map_err(|err| request_error("logout", err)),
     )
 }
 
@@ -40,7 +39,7 @@ pub async fn logout(shared: Arc<Shared>, pool: AnyPool, data: LogoutRequest) ->
     let delta = now.signed_duration_since(data.time_stamp);
     anyhow::ensure!(
         delta < CERT_MAX_AGE_DELTA && delta > CERT_MIN_AGE_DELTA,
-        "time stamp was not in a valid time frame."
+        RequestFailure::TimestampOutOfWindow
     );
 
     let mut connection = pool.acquire().await?;
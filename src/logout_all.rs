@@ -1,19 +1,23 @@
 pub mod queries;
 
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc};
 
-use axum::Json;
+use axum::{Extension, Json};
 use ddnet_account_sql::{any::AnyPool, query::Query};
 use ddnet_accounts_shared::{
     account_server::{
-        errors::{AccountServerRequestError, Empty},
+        account_events::AccountEventKind, errors::RequestFailure, logout_all::LogoutAllError,
         result::AccountServerReqResult,
     },
     client::logout_all::{IgnoreSession, LogoutAllRequest},
 };
 
 use crate::{
+    account_events::add_account_event,
     account_token::queries::{AccountTokenQry, InvalidateAccountToken},
+    client_ip::ClientIp,
+    email::{linked_email, send_notification, AccountNotification},
+    request_error::request_logic_error,
     shared::{Shared, CERT_MAX_AGE_DELTA, CERT_MIN_AGE_DELTA},
     types::AccountTokenType,
 };
@@ -23,27 +27,29 @@ use self::queries::RemoveSessionsExcept;
 pub async fn logout_all_request(
     shared: Arc<Shared>,
     pool: AnyPool,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
     Json(data): Json<LogoutAllRequest>,
-) -> Json<AccountServerReqResult<(), Empty>> {
-    Json(logout_all(shared, pool, data).await.map_err(|err| {
-        AccountServerRequestError::Unexpected {
-            target: "logout_all".into(),
-            err: err.to_string(),
-            bt: err.backtrace().to_string(),
-        }
-    }))
+) -> Json<AccountServerReqResult<(), LogoutAllError>> {
+    Json(
+        logout_all(shared, pool, client_ip, data)
+            .await
+            .map_err(|err| request_logic_error("logout_all", err)),
+    )
 }
 
 pub async fn logout_all(
     shared: Arc<Shared>,
     pool: AnyPool,
+    client_ip: IpAddr,
     data: LogoutAllRequest,
 ) -> anyhow::Result<()> {
     let mut connection = pool.acquire().await?;
     let mut connection = connection.acquire().await?;
 
-    connection
+    let shared_clone = shared.clone();
+    let notify = connection
         .transaction(|mut connection| {
+            let shared = shared_clone;
             Box::pin(async move {
                 // token data
                 let acc_token_qry = AccountTokenQry {
@@ -52,8 +58,9 @@ pub async fn logout_all(
 
                 let row = acc_token_qry
                     .query(&shared.db.account_token_qry_statement)
-                    .fetch_one(&mut connection.con())
-                    .await?;
+                    .fetch_optional(&mut connection.con())
+                    .await?
+                    .ok_or(LogoutAllError::TokenInvalid)?;
 
                 let token_data = AccountTokenQry::row_data(&row)?;
 
@@ -67,7 +74,7 @@ pub async fn logout_all(
 
                 anyhow::ensure!(
                     token_data.ty == AccountTokenType::LogoutAll,
-                    "Account token was not for logout all operation."
+                    RequestFailure::WrongTokenOp
                 );
                 let account_id = token_data.account_id;
 
@@ -80,7 +87,7 @@ pub async fn logout_all(
                     let delta = now.signed_duration_since(ignore_session.time_stamp);
                     anyhow::ensure!(
                         delta < CERT_MAX_AGE_DELTA && delta > CERT_MIN_AGE_DELTA,
-                        "time stamp was not in a valid time frame."
+                        RequestFailure::TimestampOutOfWindow
                     );
                     anyhow::Ok(ignore_session.account_data)
                 };
@@ -99,10 +106,35 @@ pub async fn logout_all(
                     .execute(&mut connection.con())
                     .await?;
 
-                anyhow::Ok(())
+                add_account_event(
+                    &shared,
+                    &mut connection.con(),
+                    &account_id,
+                    AccountEventKind::LogoutAll,
+                    client_ip,
+                    session_data
+                        .as_ref()
+                        .map(|session_data| &session_data.hw_id),
+                    None,
+                )
+                .await?;
+
+                linked_email(&shared, &mut connection.con(), &account_id).await
             })
         })
         .await?;
 
+    if let Some(to) = notify {
+        send_notification(
+            &shared,
+            &mut connection,
+            &to,
+            AccountNotification::LogoutAll,
+            None,
+            client_ip,
+        )
+        .await;
+    }
+
     Ok(())
 }
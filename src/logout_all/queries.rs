@@ -20,6 +20,13 @@ impl Query<()> for RemoveSessionsExcept<'_> {
             .prepare(include_str!("mysql/rem_sessions_except.sql"))
             .await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/rem_sessions_except.sql"))
+            .await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
@@ -36,7 +43,26 @@ impl Query<()> for RemoveSessionsExcept<'_> {
             .bind(key)
             .bind(hwid)
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        let (key, hwid) = self
+            .session_data
+            .as_ref()
+            .map(|data| (data.public_key.as_bytes().as_slice(), data.hw_id.as_slice()))
+            .unzip();
+        statement
+            .query()
+            .bind(self.account_id)
+            .bind(key)
+            .bind(key)
+            .bind(hwid)
+    }
     fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
         Err(anyhow!("Row data is not supported"))
     }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
 }
@@ -5,24 +5,41 @@
 #![deny(clippy::nursery)]
 #![deny(clippy::all)]
 
+pub(crate) mod account_events;
 pub(crate) mod account_token;
+pub(crate) mod admin;
 mod certs;
+mod check_config;
+pub(crate) mod client_ip;
 pub(crate) mod credential_auth_token;
 pub(crate) mod db;
 pub(crate) mod delete;
 pub(crate) mod email;
 pub(crate) mod login;
 mod logout;
+pub(crate) mod sessions;
+pub(crate) mod settings;
 pub(crate) mod setup;
 pub(crate) mod shared;
+pub(crate) mod shutdown;
 pub(crate) mod sign;
 pub(crate) mod steam;
+pub(crate) mod token_limit;
 pub(crate) mod update;
 
 pub(crate) mod email_limit;
+pub(crate) mod email_outbox;
+pub(crate) mod email_templates;
+pub(crate) mod health;
+pub(crate) mod http_listener;
 pub(crate) mod ip_limit;
 
 pub(crate) mod logout_all;
+pub(crate) mod metrics;
+pub(crate) mod rate_limit;
+pub(crate) mod request_error;
+pub(crate) mod request_log;
+pub(crate) mod secret_verifier;
 
 mod account_info;
 mod file_watcher;
@@ -32,6 +49,10 @@ mod tests;
 mod types;
 mod unlink_credential;
 
+use account_events::{
+    account_events_request,
+    queries::{AccountEvents, AddAccountEvent},
+};
 use account_info::{account_info_request, queries::AccountInfo};
 use account_token::{
     account_token_email, account_token_steam,
@@ -39,26 +60,46 @@ use account_token::{
         AccountTokenQry, AddAccountTokenEmail, AddAccountTokenSteam, InvalidateAccountToken,
     },
 };
+use admin::{
+    prepare_admin_listener, prepare_admin_router,
+    queries::{AddBan, AdminAccountInfo, RemoveAccountTokens, RemoveBan},
+    run_admin, AdminDetails, AdminKey, AdminListen,
+};
 use anyhow::anyhow;
-use axum::{extract::DefaultBodyLimit, response::IntoResponse, Json, Router};
+use axum::{extract::DefaultBodyLimit, Extension, Json, Router};
 use certs::{
     certs_request, generate_key_and_cert, get_certs,
     queries::{AddCert, GetCerts},
-    store_cert, PrivateKeys,
+    store_cert, write_signing_keys, PrivateKeys,
 };
 use clap::{command, parser::ValueSource, Arg, ArgAction};
+use client_ip::{client_ip_layer, ClientIpResolver};
 use credential_auth_token::{
     credential_auth_token_email, credential_auth_token_steam, queries::AddCredentialAuthToken,
 };
 use db::DbConnectionShared;
 use ddnet_account_sql::{any::AnyPool, query::Query};
-use ddnet_accounts_shared::account_server::{
-    errors::AccountServerRequestError, result::AccountServerReqResult,
-};
 use delete::{delete_request, queries::RemoveAccount};
 use either::Either;
-use email::EmailShared;
+use email::{
+    queries::LinkedEmail,
+    transport::{
+        EmailTransport, FileEmailTransport, MemoryEmailTransport, SendmailEmailTransport,
+        SmtpEmailTransport,
+    },
+    EmailShared,
+};
+use email_outbox::{
+    email_outbox_worker,
+    queries::{AddEmail, ClaimEmail, PendingEmails, RemoveEmail, RetryEmail},
+};
+use health::{health_live_request, health_ready_request};
+use http_listener::{HttpListener, TlsDetails};
 use ip_limit::{ip_deny_layer, IpDenyList};
+use lettre::{
+    transport::smtp::authentication::Credentials, AsyncSendmailTransport, AsyncSmtpTransport,
+    Tokio1Executor,
+};
 use link_credential::{
     link_credential_request,
     queries::{UnlinkCredentialEmail, UnlinkCredentialSteam},
@@ -73,38 +114,50 @@ use login::{
 };
 use logout::{logout_request, queries::RemoveSession};
 use logout_all::{logout_all_request, queries::RemoveSessionsExcept};
+use metrics::{
+    metrics_layer, prepare_metrics_listener, prepare_metrics_router, Metrics, MetricsDetails,
+};
 use parking_lot::RwLock;
+use rate_limit::{ip_rate_limit_layer, LimiterSetting};
+use request_log::{request_log_layer, RequestLog};
+use secret_verifier::{HttpSecretVerifier, NoSecretVerifier, SecretVerifierDetails};
 use serde::{Deserialize, Serialize};
+use sessions::{queries::Sessions, session_revoke_request, sessions_request};
+use settings::SettingsSources;
 use shared::Shared;
-use sign::{queries::AuthAttempt, sign_request};
+use shutdown::{shutdown_signal, DRAIN_TIMEOUT};
+use sign::{
+    queries::{AccountBanQry, AuthAttempt, UpdateSignTime},
+    sign_request,
+};
 use sqlx::mysql::MySqlConnectOptions;
 use sqlx::mysql::MySqlPoolOptions;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
 use std::{
-    net::SocketAddr,
+    io::Write,
+    net::IpAddr,
     num::NonZeroU32,
     path::PathBuf,
     sync::Arc,
     time::{Duration, SystemTime},
 };
-use steam::{SteamShared, OFFICIAL_STEAM_AUTH_URL};
-use tokio::net::{TcpListener, TcpSocket};
-use tower::ServiceBuilder;
-use tower_governor::{
-    governor::GovernorConfigBuilder, key_extractor::SmartIpKeyExtractor, GovernorLayer,
-};
+use steam::{SteamParams, SteamShared, OFFICIAL_STEAM_AUTH_URL};
+use token_limit::TokenLimiter;
 use unlink_credential::{
     queries::{UnlinkCredentialByEmail, UnlinkCredentialBySteam},
     unlink_credential_request,
 };
 use update::{
     handle_watchers,
-    queries::{CleanupAccountTokens, CleanupCerts, CleanupCredentialAuthTokens},
+    queries::{
+        CleanupAccountEvents, CleanupAccountTokens, CleanupCerts, CleanupCredentialAuthTokens,
+    },
     update,
 };
 use url::Url;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct DbDetails {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct MySqlDetails {
     host: String,
     port: u16,
     database: String,
@@ -113,23 +166,105 @@ struct DbDetails {
     ca_cert_path: PathBuf,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SqliteDetails {
+    /// Path to the database file, created if missing.
+    path: PathBuf,
+}
+
+/// The database backend, selected by the fields
+/// present in the settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum DbDetails {
+    MySql(MySqlDetails),
+    Sqlite(SqliteDetails),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 struct HttpServerDetails {
     port: u16,
+    /// The addresses to listen on, e.g. `["0.0.0.0", "::"]`.
+    /// Only `["::"]` listens on IPv4 & IPv6 (dual-stack).
+    /// Defaults to localhost, e.g. behind a reverse proxy.
+    #[serde(default)]
+    bind: Vec<IpAddr>,
+    /// Serves https directly, without a reverse proxy.
+    #[serde(default)]
+    tls: Option<TlsDetails>,
+    /// Reverse proxies (in CIDR notation) whose `X-Forwarded-For`
+    /// header is used to find the client ip, e.g. `["127.0.0.1/32"]`.
+    /// Defaults to localhost, if only localhost is bound.
+    #[serde(default)]
+    trusted_proxies: Option<Vec<ipnet::IpNet>>,
+    /// Sends the backtraces of unexpected errors to the clients.
+    /// Only for debugging, they are always logged.
+    #[serde(default)]
+    debug_errors: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct EmailDetails {
+impl HttpServerDetails {
+    /// The configured trusted proxies. Without any, a server that only
+    /// listens on localhost is behind a reverse proxy on the same machine.
+    fn trusted_proxies(&self) -> Vec<ipnet::IpNet> {
+        match &self.trusted_proxies {
+            Some(trusted_proxies) => trusted_proxies.clone(),
+            None if self.bind.iter().all(IpAddr::is_loopback) => vec![
+                ipnet::IpNet::from(IpAddr::V4(std::net::Ipv4Addr::LOCALHOST)),
+                ipnet::IpNet::from(IpAddr::V6(std::net::Ipv6Addr::LOCALHOST)),
+            ],
+            None => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SmtpDetails {
     relay: String,
     relay_port: u16,
     username: String,
     password: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SendmailDetails {
+    /// The sendmail binary, e.g. `/usr/sbin/sendmail`.
+    sendmail_command: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FileEmailDetails {
+    /// Every email is written as `.eml` file into this directory.
+    mail_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct MemoryEmailDetails {
+    /// Must be `true`, the emails are only logged.
+    in_memory: bool,
+}
+
+/// How emails are delivered, selected by the fields
+/// present in the settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum EmailTransportDetails {
+    Smtp(SmtpDetails),
+    Sendmail(SendmailDetails),
+    File(FileEmailDetails),
+    Memory(MemoryEmailDetails),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct EmailDetails {
+    #[serde(flatten)]
+    transport: EmailTransportDetails,
     /// The name of the sender of all emails
     /// e.g. `accounts@mydomain.org`
     email_from: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct SteamDetails {
     auth_url: Option<Url>,
     publisher_auth_key: String,
@@ -137,7 +272,7 @@ struct SteamDetails {
     identify: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct LimiterValues {
     /// time until another attempt is allowed
     time_until_another_attempt: Duration,
@@ -145,7 +280,9 @@ struct LimiterValues {
     initial_request_count: NonZeroU32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+// missing limits fall back to their default values
+#[serde(default)]
 struct LimiterSettings {
     credential_auth_tokens: LimiterValues,
     credential_auth_tokens_secret: LimiterValues,
@@ -158,6 +295,15 @@ struct LimiterSettings {
     logout_all: LimiterValues,
     logout: LimiterValues,
     account_info: LimiterValues,
+    sessions: LimiterValues,
+    session_revoke: LimiterValues,
+    account_events: LimiterValues,
+    /// Limits the tokens sent to the same email (normalized),
+    /// for all token routes together.
+    email_tokens: LimiterValues,
+    /// Limits the tokens issued for the same steamid,
+    /// for all token routes together.
+    steam_tokens: LimiterValues,
 }
 
 impl Default for LimiterSettings {
@@ -231,44 +377,99 @@ impl Default for LimiterSettings {
                 // 3 request total
                 initial_request_count: NonZeroU32::new(3).unwrap(),
             },
+            sessions: LimiterValues {
+                // once per minute
+                time_until_another_attempt: Duration::from_secs(60),
+                // 3 request total
+                initial_request_count: NonZeroU32::new(3).unwrap(),
+            },
+            session_revoke: LimiterValues {
+                // once per hour
+                time_until_another_attempt: Duration::from_secs(60 * 60),
+                // 5 request total
+                initial_request_count: NonZeroU32::new(5).unwrap(),
+            },
+            account_events: LimiterValues {
+                // once per minute
+                time_until_another_attempt: Duration::from_secs(60),
+                // 3 request total
+                initial_request_count: NonZeroU32::new(3).unwrap(),
+            },
+            email_tokens: LimiterValues {
+                // once per 10 minutes
+                time_until_another_attempt: Duration::from_secs(10 * 60),
+                // 5 request total
+                initial_request_count: NonZeroU32::new(5).unwrap(),
+            },
+            steam_tokens: LimiterValues {
+                // once per 10 minutes
+                time_until_another_attempt: Duration::from_secs(10 * 60),
+                // 5 request total
+                initial_request_count: NonZeroU32::new(5).unwrap(),
+            },
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct Details {
     db: DbDetails,
     http: HttpServerDetails,
     email: EmailDetails,
     steam: SteamDetails,
     limitter: LimiterSettings,
+    /// The admin api is disabled if not set.
+    #[serde(default)]
+    admin: Option<AdminDetails>,
+    #[serde(default)]
+    metrics: MetricsDetails,
+    /// Without a web validation process the `*-secret` routes reject all requests.
+    #[serde(default)]
+    secret_verifier: Option<SecretVerifierDetails>,
 }
 
 pub(crate) async fn prepare_db(details: &DbDetails) -> anyhow::Result<AnyPool> {
-    let is_localhost =
-        details.host == "localhost" || details.host == "127.0.0.1" || details.host == "::1";
-
     sqlx::any::install_default_drivers();
-    Ok(AnyPool::MySql(
-        MySqlPoolOptions::new()
-            .max_connections(200)
-            .connect_with(
-                MySqlConnectOptions::new()
-                    .charset("utf8mb4")
-                    .host(&details.host)
-                    .port(details.port)
-                    .database(&details.database)
-                    .username(&details.username)
-                    .password(&details.password)
-                    .ssl_mode(if !is_localhost {
-                        sqlx::mysql::MySqlSslMode::Required
-                    } else {
-                        sqlx::mysql::MySqlSslMode::Preferred
-                    })
-                    .ssl_ca(&details.ca_cert_path),
+    Ok(match details {
+        DbDetails::MySql(details) => {
+            let is_localhost =
+                details.host == "localhost" || details.host == "127.0.0.1" || details.host == "::1";
+
+            AnyPool::MySql(
+                MySqlPoolOptions::new()
+                    .max_connections(200)
+                    .connect_with(
+                        MySqlConnectOptions::new()
+                            .charset("utf8mb4")
+                            .host(&details.host)
+                            .port(details.port)
+                            .database(&details.database)
+                            .username(&details.username)
+                            .password(&details.password)
+                            .ssl_mode(if !is_localhost {
+                                sqlx::mysql::MySqlSslMode::Required
+                            } else {
+                                sqlx::mysql::MySqlSslMode::Preferred
+                            })
+                            .ssl_ca(&details.ca_cert_path),
+                    )
+                    .await?,
             )
-            .await?,
-    ))
+        }
+        DbDetails::Sqlite(details) => AnyPool::Sqlite(
+            SqlitePoolOptions::new()
+                .max_connections(10)
+                .connect_with(
+                    SqliteConnectOptions::new()
+                        .filename(&details.path)
+                        .create_if_missing(true)
+                        .foreign_keys(true)
+                        // allows readers while a transaction is writing
+                        .journal_mode(SqliteJournalMode::Wal),
+                )
+                .await?,
+        ),
+    })
 }
 
 pub(crate) async fn prepare_statements(pool: &AnyPool) -> anyhow::Result<DbConnectionShared> {
@@ -313,6 +514,22 @@ pub(crate) async fn prepare_statements(pool: &AnyPool) -> anyhow::Result<DbConne
     let unlink_credential_by_steam_statement =
         UnlinkCredentialBySteam::prepare(&mut connection).await?;
     let account_info = AccountInfo::prepare(&mut connection).await?;
+    let sessions_statement = Sessions::prepare(&mut connection).await?;
+    let update_sign_time_statement = UpdateSignTime::prepare(&mut connection).await?;
+    let account_ban_statement = AccountBanQry::prepare(&mut connection).await?;
+    let admin_account_info_statement = AdminAccountInfo::prepare(&mut connection).await?;
+    let add_ban_statement = AddBan::prepare(&mut connection).await?;
+    let remove_ban_statement = RemoveBan::prepare(&mut connection).await?;
+    let remove_account_tokens_statement = RemoveAccountTokens::prepare(&mut connection).await?;
+    let add_account_event_statement = AddAccountEvent::prepare(&mut connection).await?;
+    let account_events_statement = AccountEvents::prepare(&mut connection).await?;
+    let cleanup_account_events_statement = CleanupAccountEvents::prepare(&mut connection).await?;
+    let linked_email_statement = LinkedEmail::prepare(&mut connection).await?;
+    let add_email_statement = AddEmail::prepare(&mut connection).await?;
+    let pending_emails_statement = PendingEmails::prepare(&mut connection).await?;
+    let remove_email_statement = RemoveEmail::prepare(&mut connection).await?;
+    let retry_email_statement = RetryEmail::prepare(&mut connection).await?;
+    let claim_email_statement = ClaimEmail::prepare(&mut connection).await?;
 
     Ok(DbConnectionShared {
         credential_auth_token_statement,
@@ -343,22 +560,65 @@ pub(crate) async fn prepare_statements(pool: &AnyPool) -> anyhow::Result<DbConne
         unlink_credential_by_email_statement,
         unlink_credential_by_steam_statement,
         account_info,
+        sessions_statement,
+        update_sign_time_statement,
+        account_ban_statement,
+        admin_account_info_statement,
+        add_ban_statement,
+        remove_ban_statement,
+        remove_account_tokens_statement,
+        add_account_event_statement,
+        account_events_statement,
+        cleanup_account_events_statement,
+        linked_email_statement,
+        add_email_statement,
+        pending_emails_statement,
+        remove_email_statement,
+        retry_email_statement,
+        claim_email_statement,
+    })
+}
+
+pub(crate) fn prepare_email_transport(
+    details: &EmailTransportDetails,
+) -> anyhow::Result<Arc<dyn EmailTransport>> {
+    Ok(match details {
+        EmailTransportDetails::Smtp(details) => Arc::new(SmtpEmailTransport(
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&details.relay)?
+                .port(details.relay_port)
+                .credentials(Credentials::new(
+                    details.username.clone(),
+                    details.password.clone(),
+                ))
+                .build(),
+        )),
+        EmailTransportDetails::Sendmail(details) => {
+            Arc::new(SendmailEmailTransport(AsyncSendmailTransport::<
+                Tokio1Executor,
+            >::new_with_command(
+                details.sendmail_command.as_os_str(),
+            )))
+        }
+        EmailTransportDetails::File(details) => {
+            Arc::new(FileEmailTransport::new(details.mail_dir.clone()))
+        }
+        EmailTransportDetails::Memory(details) => {
+            anyhow::ensure!(
+                details.in_memory,
+                "in_memory must be true, otherwise configure a different email transport."
+            );
+            Arc::new(MemoryEmailTransport::default())
+        }
     })
 }
 
 pub(crate) async fn prepare_email(details: &EmailDetails) -> anyhow::Result<EmailShared> {
-    EmailShared::new(
-        &details.relay,
-        details.relay_port,
-        &details.email_from,
-        &details.username,
-        &details.password,
-    )
-    .await
+    let transport = prepare_email_transport(&details.transport)?;
+    EmailShared::new(&details.email_from, transport).await
 }
 
-pub(crate) fn prepare_steam(details: &SteamDetails) -> anyhow::Result<SteamShared> {
-    SteamShared::new(
+pub(crate) fn prepare_steam_params(details: &SteamDetails) -> SteamParams {
+    SteamParams::new(
         details
             .auth_url
             .clone()
@@ -369,14 +629,18 @@ pub(crate) fn prepare_steam(details: &SteamDetails) -> anyhow::Result<SteamShare
     )
 }
 
+pub(crate) fn prepare_steam(details: &SteamDetails) -> anyhow::Result<SteamShared> {
+    SteamShared::new(prepare_steam_params(details))
+}
+
 pub(crate) async fn prepare_http(
     details: &HttpServerDetails,
     db: DbConnectionShared,
-    email: EmailShared,
-    steam: SteamShared,
+    mut email: EmailShared,
+    mut steam: SteamShared,
     pool: &AnyPool,
     settings: &LimiterSettings,
-) -> anyhow::Result<(TcpListener, Router, Arc<Shared>)> {
+) -> anyhow::Result<(HttpListener, Router, Arc<Shared>)> {
     let keys = tokio::fs::read("signing_keys.json")
         .await
         .map_err(|err| anyhow!(err))
@@ -398,13 +662,17 @@ pub(crate) async fn prepare_http(
             next_cert: cert2,
         };
 
-        tokio::fs::write("signing_keys.json", serde_json::to_vec(&res)?).await?;
+        write_signing_keys(&res).await?;
 
         res
     };
 
     let certs = get_certs(&db, pool).await?;
 
+    let metrics = Arc::new(Metrics::default());
+    email.set_metrics(metrics.clone());
+    steam.set_metrics(metrics.clone());
+
     let shared = Arc::new(Shared {
         db,
         email,
@@ -412,64 +680,23 @@ pub(crate) async fn prepare_http(
         ip_ban_list: Arc::new(RwLock::new(IpDenyList::load_from_file().await)),
         signing_keys: Arc::new(RwLock::new(Arc::new(keys))),
         cert_chain: Arc::new(RwLock::new(Arc::new(certs))),
-        account_tokens_email: Arc::new(RwLock::new(Arc::new(
-            EmailShared::load_email_template("account_tokens.html")
-                .await
-                .unwrap_or_else(|_| {
-                    "<p>Hello %SUBJECT%,</p>\n\
-                    <p>Please use the following token to verify your action:</p>\n\
-                    <pre>%CODE%</pre>"
-                        .to_string()
-                }),
-        ))),
-        credential_auth_tokens_email: Arc::new(RwLock::new(Arc::new(
-            EmailShared::load_email_template("credential_auth_tokens.html")
-                .await
-                .unwrap_or_else(|_| {
-                    "<p>Hello %SUBJECT%,</p>\n\
-                    <p>Please use the following token to verify your action:</p>\n\
-                    <pre>%CODE%</pre>"
-                        .to_string()
-                }),
-        ))),
+        metrics,
+        secret_verifier: RwLock::new(Arc::new(NoSecretVerifier)),
+        token_limiter: TokenLimiter::new(settings)?,
+        rate_limiters: Default::default(),
+        shutdown: Default::default(),
+        health: Default::default(),
     });
 
-    // prepare socket
-    let tcp_socket = TcpSocket::new_v4()?;
-    tcp_socket.set_reuseaddr(true)?;
-    tcp_socket.bind(format!("127.0.0.1:{}", details.port).parse()?)?;
-
-    let listener = tcp_socket.listen(1024)?;
+    // prepare sockets
+    let listener = HttpListener::bind(&details.bind, details.port, details.tls.as_ref())?;
 
     // build http server
-    let layer = |limiter: &LimiterValues| {
-        anyhow::Ok(
-            ServiceBuilder::new().layer(GovernorLayer {
-                config: Arc::new(
-                    GovernorConfigBuilder::default()
-                        .key_extractor(SmartIpKeyExtractor)
-                        .period(limiter.time_until_another_attempt)
-                        .burst_size(limiter.initial_request_count.get())
-                        .error_handler(|err| match err {
-                            tower_governor::GovernorError::TooManyRequests { .. } => {
-                                Json(AccountServerReqResult::<(), ()>::Err(
-                                    AccountServerRequestError::RateLimited(err.to_string()),
-                                ))
-                                .into_response()
-                            }
-                            tower_governor::GovernorError::UnableToExtractKey
-                            | tower_governor::GovernorError::Other { .. } => {
-                                Json(AccountServerReqResult::<(), ()>::Err(
-                                    AccountServerRequestError::Other(err.to_string()),
-                                ))
-                                .into_response()
-                            }
-                        })
-                        .finish()
-                        .ok_or_else(|| anyhow!("Could not create governor config."))?,
-                ),
-            }),
-        )
+    let layer = |setting: LimiterSetting| {
+        anyhow::Ok(axum::middleware::from_fn_with_state(
+            shared.rate_limiters.add(settings, setting)?,
+            ip_rate_limit_layer,
+        ))
     };
 
     // Crendential auth tokens
@@ -482,7 +709,7 @@ pub(crate) async fn prepare_http(
                 credential_auth_token_email(shared_clone, pool_clone, false, payload)
             }),
         )
-        .layer(layer(&settings.credential_auth_tokens)?);
+        .layer(layer(|settings| &settings.credential_auth_tokens)?);
     let shared_clone = shared.clone();
     let pool_clone = pool.clone();
     let token_steam = axum::Router::new()
@@ -492,7 +719,7 @@ pub(crate) async fn prepare_http(
                 credential_auth_token_steam(shared_clone, pool_clone, false, payload)
             }),
         )
-        .layer(layer(&settings.credential_auth_tokens)?);
+        .layer(layer(|settings| &settings.credential_auth_tokens)?);
     let shared_clone = shared.clone();
     let pool_clone = pool.clone();
     let token_email_secret = axum::Router::new()
@@ -502,7 +729,7 @@ pub(crate) async fn prepare_http(
                 credential_auth_token_email(shared_clone, pool_clone, true, payload)
             }),
         )
-        .layer(layer(&settings.credential_auth_tokens_secret)?);
+        .layer(layer(|settings| &settings.credential_auth_tokens_secret)?);
     let shared_clone = shared.clone();
     let pool_clone = pool.clone();
     let token_steam_secret = axum::Router::new()
@@ -512,7 +739,7 @@ pub(crate) async fn prepare_http(
                 credential_auth_token_steam(shared_clone, pool_clone, true, payload)
             }),
         )
-        .layer(layer(&settings.credential_auth_tokens_secret)?);
+        .layer(layer(|settings| &settings.credential_auth_tokens_secret)?);
     let mut app = axum::Router::new();
     app = app.nest(
         "/token",
@@ -531,7 +758,7 @@ pub(crate) async fn prepare_http(
                 account_token_email(shared_clone, pool_clone, false, payload)
             }),
         )
-        .layer(layer(&settings.account_tokens)?);
+        .layer(layer(|settings| &settings.account_tokens)?);
     let shared_clone = shared.clone();
     let pool_clone = pool.clone();
     let account_token_secret_email = axum::Router::new()
@@ -541,7 +768,7 @@ pub(crate) async fn prepare_http(
                 account_token_email(shared_clone, pool_clone, true, payload)
             }),
         )
-        .layer(layer(&settings.account_tokens_secret)?);
+        .layer(layer(|settings| &settings.account_tokens_secret)?);
     let shared_clone = shared.clone();
     let pool_clone = pool.clone();
     let account_token_by_steam = axum::Router::new()
@@ -551,7 +778,7 @@ pub(crate) async fn prepare_http(
                 account_token_steam(shared_clone, pool_clone, false, payload)
             }),
         )
-        .layer(layer(&settings.account_tokens)?);
+        .layer(layer(|settings| &settings.account_tokens)?);
     let shared_clone = shared.clone();
     let pool_clone = pool.clone();
     let account_token_secret_steam = axum::Router::new()
@@ -561,7 +788,7 @@ pub(crate) async fn prepare_http(
                 account_token_steam(shared_clone, pool_clone, true, payload)
             }),
         )
-        .layer(layer(&settings.account_tokens_secret)?);
+        .layer(layer(|settings| &settings.account_tokens_secret)?);
     app = app.nest(
         "/account-token",
         Router::new()
@@ -576,10 +803,10 @@ pub(crate) async fn prepare_http(
     app = app.merge(
         axum::Router::new().route(
             "/login",
-            axum::routing::post(move |payload: Json<_>| {
-                login_request(shared_clone, pool_clone, payload)
+            axum::routing::post(move |client_ip: Extension<_>, payload: Json<_>| {
+                login_request(shared_clone, pool_clone, client_ip, payload)
             })
-            .layer(layer(&settings.login)?),
+            .layer(layer(|settings| &settings.login)?),
         ),
     );
     // Link credential
@@ -588,10 +815,10 @@ pub(crate) async fn prepare_http(
     app = app.merge(
         axum::Router::new().route(
             "/link-credential",
-            axum::routing::post(move |payload: Json<_>| {
-                link_credential_request(shared_clone, pool_clone, payload)
+            axum::routing::post(move |client_ip: Extension<_>, payload: Json<_>| {
+                link_credential_request(shared_clone, pool_clone, client_ip, payload)
             })
-            .layer(layer(&settings.link_credential)?),
+            .layer(layer(|settings| &settings.link_credential)?),
         ),
     );
     // Unlink credential
@@ -600,10 +827,10 @@ pub(crate) async fn prepare_http(
     app = app.merge(
         axum::Router::new().route(
             "/unlink-credential",
-            axum::routing::post(move |qry: Json<_>| {
-                unlink_credential_request(shared_clone, pool_clone, qry)
+            axum::routing::post(move |client_ip: Extension<_>, qry: Json<_>| {
+                unlink_credential_request(shared_clone, pool_clone, client_ip, qry)
             })
-            .layer(layer(&settings.unlink_credential)?),
+            .layer(layer(|settings| &settings.unlink_credential)?),
         ),
     );
     // Delete account
@@ -612,8 +839,10 @@ pub(crate) async fn prepare_http(
     app = app.merge(
         axum::Router::new().route(
             "/delete",
-            axum::routing::post(move |qry: Json<_>| delete_request(shared_clone, pool_clone, qry))
-                .layer(layer(&settings.delete)?),
+            axum::routing::post(move |client_ip: Extension<_>, qry: Json<_>| {
+                delete_request(shared_clone, pool_clone, client_ip, qry)
+            })
+            .layer(layer(|settings| &settings.delete)?),
         ),
     );
     // Logout all
@@ -622,10 +851,10 @@ pub(crate) async fn prepare_http(
     app = app.merge(
         axum::Router::new().route(
             "/logout-all",
-            axum::routing::post(move |qry: Json<_>| {
-                logout_all_request(shared_clone, pool_clone, qry)
+            axum::routing::post(move |client_ip: Extension<_>, qry: Json<_>| {
+                logout_all_request(shared_clone, pool_clone, client_ip, qry)
             })
-            .layer(layer(&settings.logout_all)?),
+            .layer(layer(|settings| &settings.logout_all)?),
         ),
     );
     // Logout
@@ -635,7 +864,7 @@ pub(crate) async fn prepare_http(
         axum::Router::new().route(
             "/logout",
             axum::routing::post(move |qry: Json<_>| logout_request(shared_clone, pool_clone, qry))
-                .layer(layer(&settings.logout)?),
+                .layer(layer(|settings| &settings.logout)?),
         ),
     );
     // account info
@@ -647,7 +876,43 @@ pub(crate) async fn prepare_http(
             axum::routing::post(move |qry: Json<_>| {
                 account_info_request(shared_clone, pool_clone, qry)
             })
-            .layer(layer(&settings.account_info)?),
+            .layer(layer(|settings| &settings.account_info)?),
+        ),
+    );
+    // sessions
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.merge(
+        axum::Router::new().route(
+            "/sessions",
+            axum::routing::post(move |qry: Json<_>| {
+                sessions_request(shared_clone, pool_clone, qry)
+            })
+            .layer(layer(|settings| &settings.sessions)?),
+        ),
+    );
+    // revoke a single session
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.merge(
+        axum::Router::new().route(
+            "/session-revoke",
+            axum::routing::post(move |qry: Json<_>| {
+                session_revoke_request(shared_clone, pool_clone, qry)
+            })
+            .layer(layer(|settings| &settings.session_revoke)?),
+        ),
+    );
+    // account events
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.merge(
+        axum::Router::new().route(
+            "/account-events",
+            axum::routing::post(move |qry: Json<_>| {
+                account_events_request(shared_clone, pool_clone, qry)
+            })
+            .layer(layer(|settings| &settings.account_events)?),
         ),
     );
     let shared_clone = shared.clone();
@@ -664,13 +929,32 @@ pub(crate) async fn prepare_http(
         axum::routing::get(move || certs_request(shared_clone)),
     );
     app = app.route("/ping", axum::routing::get(|| async { Json("pong") }));
+    app = app.route("/health/live", axum::routing::get(health_live_request));
+    let shared_clone = shared.clone();
+    let pool_clone = pool.clone();
+    app = app.route(
+        "/health/ready",
+        axum::routing::get(move || health_ready_request(shared_clone, pool_clone)),
+    );
     // 16 KiB limit should be enough for all requests
     let request_size = DefaultBodyLimit::max(1024 * 16);
     app = app
         .layer(request_size)
         .layer(axum::middleware::from_fn_with_state(
-            shared.ip_ban_list.clone(),
+            shared.clone(),
             ip_deny_layer,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            shared.clone(),
+            metrics_layer,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(RequestLog::new(details.debug_errors)),
+            request_log_layer,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(ClientIpResolver::new(&details.trusted_proxies())),
+            client_ip_layer,
         ));
 
     Ok((listener, app, shared))
@@ -678,15 +962,22 @@ pub(crate) async fn prepare_http(
 
 pub(crate) async fn prepare(
     details: &Details,
-) -> anyhow::Result<(TcpListener, Router, AnyPool, Arc<Shared>)> {
+) -> anyhow::Result<(HttpListener, Router, AnyPool, Arc<Shared>)> {
     // first connect to the database
     let pool = prepare_db(&details.db).await?;
 
     let db = prepare_statements(&pool).await?;
     let email = prepare_email(&details.email).await?;
     let steam = prepare_steam(&details.steam)?;
-    let (listener, app, shared) =
+    let (listener, mut app, shared) =
         prepare_http(&details.http, db, email, steam, &pool, &details.limitter).await?;
+    if let Some(secret_verifier) = &details.secret_verifier {
+        *shared.secret_verifier.write() =
+            Arc::new(HttpSecretVerifier::new(secret_verifier.clone()));
+    }
+    if details.metrics.public {
+        app = app.merge(prepare_metrics_router(&shared));
+    }
 
     Ok((listener, app, pool, shared))
 }
@@ -727,14 +1018,10 @@ pub(crate) async fn generate_new_signing_keys_impl(
                     next_key: key,
                     next_cert: cert,
                 });
-                if let Ok(val) = serde_json::to_vec(new_keys.as_ref()) {
-                    if tokio::fs::write("signing_keys.json", val).await.is_ok() {
-                        *shared.cert_chain.write() = Arc::new(certs);
-                        *shared.signing_keys.write() = new_keys;
-                        next_sleep_time = Either::Right(default_check_key_time);
-                    } else {
-                        next_sleep_time = err_check_key_time;
-                    }
+                if write_signing_keys(&new_keys).await.is_ok() {
+                    *shared.cert_chain.write() = Arc::new(certs);
+                    *shared.signing_keys.write() = new_keys;
+                    next_sleep_time = Either::Right(default_check_key_time);
                 } else {
                     next_sleep_time = err_check_key_time;
                 }
@@ -763,11 +1050,15 @@ pub(crate) async fn generate_new_signing_keys(pool: &AnyPool, shared: &Arc<Share
     }
 }
 
-async fn regenerate_signing_keys_and_certs(pool: AnyPool, shared: Arc<Shared>) -> ! {
+async fn regenerate_signing_keys_and_certs(pool: AnyPool, shared: Arc<Shared>) {
     loop {
+        // never interrupted by a shutdown, so the keys are always completely rotated
         let next_sleep_time = generate_new_signing_keys(&pool, &shared).await;
 
-        tokio::time::sleep(next_sleep_time).await;
+        tokio::select! {
+            _ = tokio::time::sleep(next_sleep_time) => {}
+            _ = shared.shutdown.wait() => return,
+        }
 
         // get latest certs
         if let Ok(certs) = get_certs(&shared.db, &pool).await {
@@ -776,34 +1067,56 @@ async fn regenerate_signing_keys_and_certs(pool: AnyPool, shared: Arc<Shared>) -
     }
 }
 
-// https://github.com/tokio-rs/tokio/issues/5616
-#[allow(clippy::redundant_pub_crate)]
+/// Serves the http server until a shutdown is triggered (see [`Shared::shutdown`]),
+/// then finishes the in-flight requests (for at most [`DRAIN_TIMEOUT`])
+/// & waits for all background tasks to stop.
 pub(crate) async fn run(
-    listener: TcpListener,
+    listener: HttpListener,
     app: Router,
     pool: AnyPool,
     shared: Arc<Shared>,
     handle_updates: bool,
 ) -> anyhow::Result<()> {
-    let pool_clone = pool.clone();
-    let shared_clone = shared.clone();
-    let shared_watchers = shared.clone();
-    let app = app.into_make_service_with_connect_info::<SocketAddr>();
-    tokio::select!(
-        err = async move { axum::serve(listener, app).await } => {
-           err?;
-        },
-        _ = async move {
-            regenerate_signing_keys_and_certs(pool, shared).await
-        }, if handle_updates => {}
-        _ = async move {
-            update(pool_clone, shared_clone).await
-        }, if handle_updates => {}
-        _ = async move {
-            handle_watchers(shared_watchers).await
-        }, if handle_updates => {}
-    );
-    Ok(())
+    let shutdown = shared.shutdown.clone();
+    let mut server = tokio::spawn(listener.serve(app, shutdown.clone()));
+
+    let mut tasks = Vec::new();
+    if handle_updates {
+        tasks.push(tokio::spawn(regenerate_signing_keys_and_certs(
+            pool.clone(),
+            shared.clone(),
+        )));
+        tasks.push(tokio::spawn(update(pool.clone(), shared.clone())));
+        tasks.push(tokio::spawn(handle_watchers(shared.clone())));
+    }
+    // without it no emails are sent, so it always runs
+    tasks.push(tokio::spawn(email_outbox_worker(pool, shared.clone())));
+
+    let res = tokio::select! {
+        // the server only stops by itself on errors
+        res = &mut server => res,
+        _ = shutdown.wait() => {
+            log::info!("shutting down, finishing in-flight requests");
+            let res = tokio::time::timeout(DRAIN_TIMEOUT, &mut server).await;
+            if res.is_err() {
+                log::warn!(
+                    "in-flight requests did not finish within {}s, dropping them",
+                    DRAIN_TIMEOUT.as_secs()
+                );
+                server.abort();
+            }
+            res.unwrap_or(Ok(Ok(())))
+        }
+    };
+
+    // also stops the background tasks, if the server failed
+    shutdown.trigger();
+    for task in tasks {
+        if let Err(err) = task.await {
+            log::error!("a background task failed: {err}");
+        }
+    }
+    res.map_err(|err| anyhow!(err))?
 }
 
 #[tokio::main]
@@ -815,45 +1128,97 @@ async fn main() {
             std::env::set_var("RUST_LOG", "info")
         };
     }
-    env_logger::init();
+    env_logger::Builder::from_default_env()
+        .format(|buf, record| {
+            // the request log is already structured
+            if record.target() == request_log::LOG_TARGET {
+                return writeln!(buf, "{}", record.args());
+            }
+            let level = buf.default_level_style(record.level());
+            writeln!(
+                buf,
+                "[{} {level}{}{level:#} {}] {}",
+                buf.timestamp(),
+                record.level(),
+                record.target(),
+                record.args()
+            )
+        })
+        .init();
 
     let mut cmd = command!()
-        .about("The account server using http & mysql.")
+        .about("The account server using http & mysql or sqlite.")
         .arg(
             Arg::new("setup")
                 .long("setup")
-                .help("Setup the account server, e.g. fill the mysql tables.")
+                .help("Setup the account server or migrate an existing setup to the latest version, e.g. fill the database tables.")
                 .required(false)
                 .action(ArgAction::SetTrue),
         )
         .arg(
             Arg::new("cleanup")
                 .long("cleanup")
-                .help("Cleanup the account server, e.g. remove the mysql tables.")
+                .help("Cleanup the account server, e.g. remove the database tables.")
                 .required(false)
                 .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("check-config")
+                .long("check-config")
+                .help("Check the settings, the database, the email transport, the steam url & signing_keys.json without starting the http server.")
+                .required(false)
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .help("The settings file, toml or json. Defaults to settings.toml or settings.json.")
+                .required(false)
+                .value_parser(clap::value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("set")
+                .long("set")
+                .help("Override a setting, e.g. `--set http.port=8080`. Takes precedence over the settings file & ACCOUNT_* environment variables.")
+                .required(false)
+                .value_name("PATH=VALUE")
+                .action(ArgAction::Append),
         );
     cmd.build();
     let m = cmd.get_matches();
 
     let print_settings_err = || {
         log::error!(
-            "a settings.json looks like this\n{}",
+            "a settings.json (or the same as settings.toml) looks like this\n{}",
             serde_json::to_string_pretty(&Details {
-                db: DbDetails {
+                db: DbDetails::MySql(MySqlDetails {
                     host: "localhost".to_string(),
                     port: 3306,
                     database: "ddnet_accounts".to_string(),
                     username: "user".to_string(),
                     password: "password".to_string(),
                     ca_cert_path: "/etc/mysql/ssl/ca-cert.pem".into()
+                }),
+                http: HttpServerDetails {
+                    port: 443,
+                    bind: vec![
+                        IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED),
+                        IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED),
+                    ],
+                    tls: Some(TlsDetails {
+                        cert_path: "/etc/ddnet-accounts/fullchain.pem".into(),
+                        key_path: "/etc/ddnet-accounts/privkey.pem".into(),
+                    }),
+                    trusted_proxies: None,
+                    debug_errors: false,
                 },
-                http: HttpServerDetails { port: 443 },
                 email: EmailDetails {
-                    relay: "emails.localhost".to_string(),
-                    relay_port: 465,
-                    username: "account".to_string(),
-                    password: "email-password".to_string(),
+                    transport: EmailTransportDetails::Smtp(SmtpDetails {
+                        relay: "emails.localhost".to_string(),
+                        relay_port: 465,
+                        username: "account".to_string(),
+                        password: "email-password".to_string(),
+                    }),
                     email_from: "account@localhost".to_string(),
                 },
                 steam: SteamDetails {
@@ -862,27 +1227,68 @@ async fn main() {
                     app_id: 123,
                     identify: None
                 },
-                limitter: Default::default()
+                limitter: Default::default(),
+                admin: Some(AdminDetails {
+                    listen: AdminListen::Port { port: 8443 },
+                    keys: vec![AdminKey {
+                        name: "admin".to_string(),
+                        key: "a-long-random-secret".to_string(),
+                    }],
+                }),
+                metrics: MetricsDetails {
+                    port: Some(9100),
+                    public: false,
+                },
+                secret_verifier: Some(SecretVerifierDetails {
+                    verify_url: "https://api.hcaptcha.com/siteverify".try_into().unwrap(),
+                    secret: "verify-secret".to_string(),
+                    challenge_url: "https://accounts.localhost/challenge".try_into().unwrap(),
+                    require_challenge: false,
+                }),
+            })
+            .unwrap()
+        );
+        log::error!(
+            "alternatively the db can be a sqlite database:\n{}",
+            serde_json::to_string_pretty(&DbDetails::Sqlite(SqliteDetails {
+                path: "ddnet_accounts.sqlite".into()
+            }))
+            .unwrap()
+        );
+        log::error!(
+            "and emails can be written to a directory instead of a smtp relay, e.g. for development:\n{}",
+            serde_json::to_string_pretty(&EmailDetails {
+                transport: EmailTransportDetails::File(FileEmailDetails {
+                    mail_dir: "mails".into()
+                }),
+                email_from: "account@localhost".to_string(),
             })
             .unwrap()
         )
     };
 
-    let Ok(cfg) = tokio::fs::read("settings.json").await else {
-        log::error!("no settings.json found, please create one.");
-        print_settings_err();
-
-        panic!("failed to find settings.json, see log for more information");
-    };
-
-    let Ok(details) = serde_json::from_slice::<Details>(&cfg) else {
-        log::error!("settings.json was invalid.");
-        print_settings_err();
-
-        panic!("settings were not a valid json file, see log for more information");
+    let sources = SettingsSources::new(
+        m.get_one::<PathBuf>("config").cloned(),
+        m.get_many::<String>("set")
+            .map(|sets| sets.cloned().collect())
+            .unwrap_or_default(),
+    );
+    let details = match sources.load().await {
+        Ok(details) => details,
+        Err(err) => {
+            log::error!("{err}");
+            print_settings_err();
+            std::process::exit(1);
+        }
     };
 
-    if m.value_source("setup")
+    if m.value_source("check-config")
+        .is_some_and(|s| matches!(s, ValueSource::CommandLine))
+    {
+        let ok = check_config::check_config(&details).await;
+        std::process::exit(if ok { 0 } else { 1 });
+    } else if m
+        .value_source("setup")
         .is_some_and(|s| matches!(s, ValueSource::CommandLine))
     {
         let pool = prepare_db(&details.db).await.unwrap();
@@ -895,6 +1301,45 @@ async fn main() {
         setup::delete(&pool).await.unwrap();
     } else {
         let (listener, app, pool, shared) = prepare(&details).await.unwrap();
+        for addr in listener.local_addrs().unwrap() {
+            log::info!("listening on {addr}");
+        }
+        let shutdown = shared.shutdown.clone();
+        tokio::spawn(async move {
+            shutdown_signal().await;
+            shutdown.trigger();
+        });
+        let mut tasks = vec![tokio::spawn(settings::watch_settings(
+            shared.clone(),
+            sources,
+            details.clone(),
+        ))];
+        if let Some(admin) = &details.admin {
+            let admin_listener = prepare_admin_listener(&admin.listen).await.unwrap();
+            let admin_app = prepare_admin_router(admin, &shared, &pool);
+            let shutdown = shared.shutdown.clone();
+            tasks.push(tokio::spawn(async move {
+                if let Err(err) = run_admin(admin_listener, admin_app, shutdown).await {
+                    log::error!(target: "admin", "admin api stopped: {err}");
+                }
+            }));
+        }
+        if let Some(port) = details.metrics.port {
+            let metrics_listener = prepare_metrics_listener(port).await.unwrap();
+            let metrics_app = prepare_metrics_router(&shared);
+            let shutdown = shared.shutdown.clone();
+            tasks.push(tokio::spawn(async move {
+                if let Err(err) = axum::serve(metrics_listener, metrics_app)
+                    .with_graceful_shutdown(async move { shutdown.wait().await })
+                    .await
+                {
+                    log::error!(target: "metrics", "metrics server stopped: {err}");
+                }
+            }));
+        }
         run(listener, app, pool, shared, true).await.unwrap();
+        // the in-flight requests of the admin api & metrics had the same time to finish,
+        // the settings watcher stops on the shutdown right away
+        let _ = tokio::time::timeout(DRAIN_TIMEOUT, futures::future::join_all(tasks)).await;
     }
 }
@@ -0,0 +1,219 @@
+use std::{sync::Arc, time::Instant};
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{header::CONTENT_TYPE, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, TcpSocket};
+
+use crate::shared::Shared;
+
+/// Where the `/metrics` endpoint is served.
+/// Without any of them the metrics are not served.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetricsDetails {
+    /// If set, the metrics are served on a separate localhost port.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// If `true`, the metrics are also served by the public http server,
+    /// so everyone can see them.
+    #[serde(default)]
+    pub public: bool,
+}
+
+/// Marks a response that was generated by a rate limiter.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimited;
+
+/// All prometheus metrics of the account server.
+#[derive(Debug)]
+pub struct Metrics {
+    registry: Registry,
+
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    rate_limited: IntCounterVec,
+    pub ip_denied: IntCounter,
+    pub emails: IntCounterVec,
+    pub steam_verifications: IntCounterVec,
+    signing_key_age: IntGauge,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let http_requests = IntCounterVec::new(
+            Opts::new(
+                "account_server_http_requests_total",
+                "Handled http requests by route and status code.",
+            ),
+            &["route", "status"],
+        )
+        .unwrap();
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "account_server_http_request_duration_seconds",
+                "Time spent handling http requests by route.",
+            ),
+            &["route"],
+        )
+        .unwrap();
+        let rate_limited = IntCounterVec::new(
+            Opts::new(
+                "account_server_rate_limited_total",
+                "Requests rejected by a rate limiter by route.",
+            ),
+            &["route"],
+        )
+        .unwrap();
+        let ip_denied = IntCounter::new(
+            "account_server_ip_denied_total",
+            "Requests rejected by the ip deny list.",
+        )
+        .unwrap();
+        let emails = IntCounterVec::new(
+            Opts::new(
+                "account_server_emails_total",
+                "Sent emails by outcome (`ok` or `err`).",
+            ),
+            &["result"],
+        )
+        .unwrap();
+        let steam_verifications = IntCounterVec::new(
+            Opts::new(
+                "account_server_steam_verifications_total",
+                "Steam ticket verifications by outcome (`ok` or `err`).",
+            ),
+            &["result"],
+        )
+        .unwrap();
+        let signing_key_age = IntGauge::new(
+            "account_server_signing_key_age_seconds",
+            "Seconds since the current signing certificate became valid.",
+        )
+        .unwrap();
+
+        let registry = Registry::new();
+        registry.register(Box::new(http_requests.clone())).unwrap();
+        registry
+            .register(Box::new(http_request_duration.clone()))
+            .unwrap();
+        registry.register(Box::new(rate_limited.clone())).unwrap();
+        registry.register(Box::new(ip_denied.clone())).unwrap();
+        registry.register(Box::new(emails.clone())).unwrap();
+        registry
+            .register(Box::new(steam_verifications.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(signing_key_age.clone()))
+            .unwrap();
+
+        Self {
+            registry,
+
+            http_requests,
+            http_request_duration,
+            rate_limited,
+            ip_denied,
+            emails,
+            steam_verifications,
+            signing_key_age,
+        }
+    }
+}
+
+impl Metrics {
+    /// Counts the outcome of an operation, e.g. a sent email.
+    pub fn count_result<T, E>(counter: &IntCounterVec, res: &Result<T, E>) {
+        counter
+            .with_label_values(&[if res.is_ok() { "ok" } else { "err" }])
+            .inc();
+    }
+
+    /// Encodes all metrics in the prometheus text format.
+    pub fn encode(&self, shared: &Shared) -> anyhow::Result<Vec<u8>> {
+        let not_before = shared
+            .signing_keys
+            .read()
+            .current_cert
+            .tbs_certificate
+            .validity
+            .not_before
+            .to_system_time();
+        self.signing_key_age.set(
+            not_before
+                .elapsed()
+                .map(|age| age.as_secs() as i64)
+                .unwrap_or_default(),
+        );
+
+        let mut res = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut res)?;
+        Ok(res)
+    }
+}
+
+/// Records the request count & duration per route.
+pub async fn metrics_layer(
+    State(shared): State<Arc<Shared>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        // don't create a label for every path someone tries
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let start = Instant::now();
+    let res = next.run(req).await;
+
+    let metrics = &shared.metrics;
+    metrics
+        .http_request_duration
+        .with_label_values(&[&route])
+        .observe(start.elapsed().as_secs_f64());
+    metrics
+        .http_requests
+        .with_label_values(&[&route, res.status().as_str()])
+        .inc();
+    if res.extensions().get::<RateLimited>().is_some() {
+        metrics.rate_limited.with_label_values(&[&route]).inc();
+    }
+    res
+}
+
+async fn metrics_request(shared: Arc<Shared>) -> Response<Body> {
+    match shared.metrics.encode(&shared) {
+        Ok(res) => ([(CONTENT_TYPE, prometheus::TEXT_FORMAT)], res).into_response(),
+        Err(err) => {
+            log::error!(target: "metrics", "{err}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// A router that only serves `/metrics`.
+pub fn prepare_metrics_router(shared: &Arc<Shared>) -> Router {
+    let shared = shared.clone();
+    Router::new().route(
+        "/metrics",
+        axum::routing::get(move || metrics_request(shared)),
+    )
+}
+
+pub async fn prepare_metrics_listener(port: u16) -> anyhow::Result<TcpListener> {
+    let tcp_socket = TcpSocket::new_v4()?;
+    tcp_socket.set_reuseaddr(true)?;
+    tcp_socket.bind(format!("127.0.0.1:{port}").parse()?)?;
+    Ok(tcp_socket.listen(1024)?)
+}
@@ -0,0 +1,125 @@
+use std::{hash::Hash, net::IpAddr, sync::Arc, time::Duration};
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use ddnet_accounts_shared::account_server::{
+    errors::AccountServerRequestError, result::AccountServerReqResult,
+};
+use governor::{clock::Clock, DefaultKeyedRateLimiter, Quota, RateLimiter};
+use parking_lot::{Mutex, RwLock};
+
+use crate::{client_ip::ClientIp, metrics::RateLimited, LimiterSettings, LimiterValues};
+
+fn quota(values: &LimiterValues) -> anyhow::Result<Quota> {
+    Ok(Quota::with_period(values.time_until_another_attempt)
+        .ok_or_else(|| anyhow::anyhow!("The period of a limiter must not be zero."))?
+        .allow_burst(values.initial_request_count))
+}
+
+/// A keyed rate limiter, whose limits can be changed at runtime.
+#[derive(Debug)]
+pub struct KeyedLimiter<K: Hash + Eq + Clone> {
+    limiter: RwLock<(LimiterValues, Arc<DefaultKeyedRateLimiter<K>>)>,
+}
+
+impl<K: Hash + Eq + Clone> KeyedLimiter<K> {
+    pub fn new(values: &LimiterValues) -> anyhow::Result<Self> {
+        Ok(Self {
+            limiter: RwLock::new((values.clone(), Arc::new(RateLimiter::keyed(quota(values)?)))),
+        })
+    }
+
+    /// Returns the time to wait, if the key is limited.
+    pub fn check(&self, key: &K) -> Result<(), Duration> {
+        let limiter = self.limiter.read().1.clone();
+        limiter.check_key(key).map_err(|not_until| {
+            not_until.wait_time_from(governor::clock::DefaultClock::default().now())
+        })
+    }
+
+    /// Replaces the limits. All keys start fresh with the new limits,
+    /// unless the limits did not change at all.
+    pub fn update(&self, values: &LimiterValues) -> anyhow::Result<()> {
+        if self.limiter.read().0 == *values {
+            return Ok(());
+        }
+        let limiter = Arc::new(RateLimiter::keyed(quota(values)?));
+        *self.limiter.write() = (values.clone(), limiter);
+        Ok(())
+    }
+
+    /// Forgets keys that would not be limited anymore anyway.
+    pub fn retain_recent(&self) {
+        self.limiter.read().1.retain_recent();
+    }
+}
+
+/// Rate limits a route by the ip resolved by [`crate::client_ip::client_ip_layer`].
+pub type IpRateLimiter = KeyedLimiter<IpAddr>;
+
+pub async fn ip_rate_limit_layer(
+    State(limiter): State<Arc<IpRateLimiter>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(ClientIp(ip)) = req.extensions().get::<ClientIp>().copied() else {
+        return Json(AccountServerReqResult::<(), ()>::Err(
+            AccountServerRequestError::Other("Unable To Extract Key!".to_string()),
+        ))
+        .into_response();
+    };
+    if let Err(wait) = limiter.check(&ip) {
+        let mut res = Json(AccountServerReqResult::<(), ()>::Err(
+            AccountServerRequestError::RateLimited(format!(
+                "Too Many Requests! Wait for {}s",
+                wait.as_secs()
+            )),
+        ))
+        .into_response();
+        // counted per route by the metrics layer
+        res.extensions_mut().insert(RateLimited);
+        return res;
+    }
+    next.run(req).await
+}
+
+/// Which limit of the [`LimiterSettings`] a route uses.
+pub type LimiterSetting = fn(&LimiterSettings) -> &LimiterValues;
+
+/// The ip rate limiters of all routes,
+/// so they can be updated when the settings change.
+#[derive(Debug, Default)]
+pub struct RouteRateLimiters {
+    limiters: Mutex<Vec<(LimiterSetting, Arc<IpRateLimiter>)>>,
+}
+
+impl RouteRateLimiters {
+    /// Creates the limiter of a route.
+    pub fn add(
+        &self,
+        settings: &LimiterSettings,
+        setting: LimiterSetting,
+    ) -> anyhow::Result<Arc<IpRateLimiter>> {
+        let limiter = Arc::new(IpRateLimiter::new(setting(settings))?);
+        self.limiters.lock().push((setting, limiter.clone()));
+        Ok(limiter)
+    }
+
+    pub fn update(&self, settings: &LimiterSettings) -> anyhow::Result<()> {
+        for (setting, limiter) in self.limiters.lock().iter() {
+            limiter.update(setting(settings))?;
+        }
+        Ok(())
+    }
+
+    /// Forgets ips that would not be limited anymore anyway.
+    pub fn retain_recent(&self) {
+        for (_, limiter) in self.limiters.lock().iter() {
+            limiter.retain_recent();
+        }
+    }
+}
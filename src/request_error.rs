@@ -0,0 +1,53 @@
+use ddnet_accounts_shared::account_server::errors::{AccountServerRequestError, RequestFailure};
+use rand::RngCore;
+
+use crate::{request_log::log_unexpected_error, token_limit::TokenRateLimited};
+
+/// The text of unexpected errors, if the details are not sent to the client.
+pub const UNEXPECTED_ERROR: &str = "An unexpected error occurred on the account server.";
+
+/// Turns the error of a request into the error that is sent to the client.
+///
+/// Known failures (a [`RequestFailure`] or [`TokenRateLimited`]) are typed,
+/// all other errors are unexpected & get an incident id.
+/// Their details are logged (see [`log_unexpected_error`]) & only sent
+/// to the client if the http server debugs errors.
+pub fn request_error<E>(target: &str, err: anyhow::Error) -> AccountServerRequestError<E> {
+    let err = match err.downcast::<RequestFailure>() {
+        Ok(failure) => return AccountServerRequestError::Failure(failure),
+        Err(err) => err,
+    };
+    match err.downcast::<TokenRateLimited>() {
+        Ok(err) => AccountServerRequestError::RateLimited(err.to_string()),
+        Err(err) => {
+            let mut incident = [0; 8];
+            rand::rngs::OsRng.fill_bytes(&mut incident);
+            let incident = hex::encode(incident);
+            let err_str = format!("{err:#}");
+            let bt = err.backtrace().to_string();
+            let (err, bt) = if log_unexpected_error(&incident, target, &err_str, &bt) {
+                (err_str, bt)
+            } else {
+                (UNEXPECTED_ERROR.to_string(), String::new())
+            };
+            AccountServerRequestError::Unexpected {
+                target: target.into(),
+                err,
+                bt,
+                incident: Some(incident),
+            }
+        }
+    }
+}
+
+/// Like [`request_error`], but logic errors of the operation (`E`)
+/// are sent as [`AccountServerRequestError::LogicError`].
+pub fn request_logic_error<E>(target: &str, err: anyhow::Error) -> AccountServerRequestError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    match err.downcast::<E>() {
+        Ok(err) => AccountServerRequestError::LogicError(err),
+        Err(err) => request_error(target, err),
+    }
+}
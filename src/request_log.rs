@@ -0,0 +1,170 @@
+use std::{cell::RefCell, sync::Arc, time::Instant};
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use rand::RngCore;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::client_ip::ClientIp;
+
+/// The log target of the request log, its messages are JSON objects.
+pub const LOG_TARGET: &str = "request";
+/// The header that contains the id of a request.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// The id of a request, added to the request extensions by [`request_log_layer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// State of the [`request_log_layer`].
+#[derive(Debug)]
+pub struct RequestLog {
+    /// Client ips are only logged hashed,
+    /// the salt is regenerated on every start.
+    ip_salt: [u8; 16],
+    /// Send the details of unexpected errors to the client.
+    debug_errors: bool,
+}
+
+impl RequestLog {
+    pub fn new(debug_errors: bool) -> Self {
+        let mut ip_salt = [0; 16];
+        rand::rngs::OsRng.fill_bytes(&mut ip_salt);
+        Self {
+            ip_salt,
+            debug_errors,
+        }
+    }
+
+    fn hash_ip(&self, ip: &ClientIp) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.ip_salt);
+        hasher.update(ip.0.to_string());
+        hex::encode(&hasher.finalize()[..8])
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RequestLogEntry<'a> {
+    time: chrono::DateTime<chrono::Utc>,
+    request_id: &'a str,
+    method: &'a str,
+    route: &'a str,
+    status: u16,
+    latency_ms: f64,
+    client: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    incident: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct UnexpectedErrorLogEntry<'a> {
+    time: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<&'a str>,
+    incident: &'a str,
+    target: &'a str,
+    error: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    backtrace: &'a str,
+}
+
+/// The request that is handled by the current task.
+#[derive(Debug)]
+struct CurrentRequest {
+    request_id: String,
+    debug_errors: bool,
+    /// The incident id of an unexpected error of the request.
+    incident: RefCell<Option<String>>,
+}
+
+tokio::task_local! {
+    static CURRENT_REQUEST: CurrentRequest;
+}
+
+/// Logs an unexpected error, together with the id of the current request
+/// if it is handled inside of the [`request_log_layer`].
+///
+/// Returns `true` if the details of the error may be sent to the client.
+pub fn log_unexpected_error(incident: &str, target: &str, error: &str, backtrace: &str) -> bool {
+    let (request_id, debug_errors) = CURRENT_REQUEST
+        .try_with(|req| {
+            *req.incident.borrow_mut() = Some(incident.to_string());
+            (Some(req.request_id.clone()), req.debug_errors)
+        })
+        .unwrap_or_default();
+    let entry = UnexpectedErrorLogEntry {
+        time: chrono::Utc::now(),
+        request_id: request_id.as_deref(),
+        incident,
+        target,
+        error,
+        backtrace,
+    };
+    log::error!(target: LOG_TARGET, "{}", serde_json::to_string(&entry).unwrap());
+    debug_errors
+}
+
+/// Assigns an id to every request, logs the request as JSON
+/// & returns the id to the client in the `X-Request-Id` header.
+/// Unexpected errors are logged by [`crate::request_error::request_error`]
+/// with the id of the request, the request's entry contains their incident id.
+pub async fn request_log_layer(
+    State(log): State<Arc<RequestLog>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let mut id = [0; 16];
+    rand::rngs::OsRng.fill_bytes(&mut id);
+    let request_id = hex::encode(id);
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let method = req.method().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let client = req.extensions().get::<ClientIp>().map(|ip| log.hash_ip(ip));
+
+    let start = Instant::now();
+    let current = CurrentRequest {
+        request_id: request_id.clone(),
+        debug_errors: log.debug_errors,
+        incident: Default::default(),
+    };
+    let (incident, mut res) = CURRENT_REQUEST
+        .scope(current, async move {
+            let res = next.run(req).await;
+            (CURRENT_REQUEST.with(|req| req.incident.take()), res)
+        })
+        .await;
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let entry = RequestLogEntry {
+        time: chrono::Utc::now(),
+        request_id: &request_id,
+        method: &method,
+        route: &route,
+        status: res.status().as_u16(),
+        latency_ms,
+        client,
+        incident,
+    };
+    if entry.incident.is_some() || res.status().is_server_error() {
+        log::error!(target: LOG_TARGET, "{}", serde_json::to_string(&entry).unwrap());
+    } else {
+        log::info!(target: LOG_TARGET, "{}", serde_json::to_string(&entry).unwrap());
+    }
+
+    if let Ok(request_id) = HeaderValue::from_str(&request_id) {
+        res.headers_mut().insert(REQUEST_ID_HEADER, request_id);
+    }
+    res
+}
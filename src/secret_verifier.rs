@@ -0,0 +1,162 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use ddnet_accounts_shared::{
+    account_server::errors::AccountServerRequestError, client::credential_auth_token::SecretKey,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{request_error::request_error, shared::Shared};
+
+/// Verifies the secret keys that clients obtain through a
+/// web validation process (e.g. captchas) for the `*-secret` routes.
+#[async_trait]
+pub trait SecretVerifier: Debug + Sync + Send {
+    /// Returns the url of the web validation process, if requests
+    /// without a secret key have to go through it first.
+    fn challenge_needed(&self) -> Option<Url>;
+
+    /// Whether the secret key was generated by the web validation process.
+    async fn verify(&self, secret_key: &SecretKey) -> anyhow::Result<bool>;
+}
+
+/// Used if no web validation process is configured,
+/// no secret key is valid.
+#[derive(Debug)]
+pub struct NoSecretVerifier;
+
+#[async_trait]
+impl SecretVerifier for NoSecretVerifier {
+    fn challenge_needed(&self) -> Option<Url> {
+        None
+    }
+
+    async fn verify(&self, _secret_key: &SecretKey) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretVerifierDetails {
+    /// The url that verifies a secret key, see [`HttpSecretVerifier`].
+    /// Captcha apis can't be used directly, their tokens don't fit into a
+    /// secret key. The page behind `challenge_url` has to verify them & hand
+    /// out secret keys that this url knows.
+    pub verify_url: Url,
+    /// The secret of the account server for the verify url.
+    pub secret: String,
+    /// The web page where clients solve the challenge to obtain a secret key.
+    pub challenge_url: Url,
+    /// If `true` requests to the routes without secret key
+    /// are told to go through the challenge first.
+    #[serde(default)]
+    pub require_challenge: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SiteVerifyResponse {
+    success: bool,
+    #[serde(default, rename = "error-codes")]
+    error_codes: Vec<String>,
+}
+
+/// Verifies secret keys against an http api.
+///
+/// The api gets a form encoded `POST` with the fields `secret`
+/// (the configured secret) & `response` (the 32 byte secret key in hex)
+/// and answers with json: `{"success": true}` if the key is valid,
+/// otherwise `{"success": false, "error-codes": ["..."]}`.
+#[derive(Debug)]
+pub struct HttpSecretVerifier {
+    http: reqwest::Client,
+    details: SecretVerifierDetails,
+}
+
+impl HttpSecretVerifier {
+    pub fn new(details: SecretVerifierDetails) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            details,
+        }
+    }
+}
+
+#[async_trait]
+impl SecretVerifier for HttpSecretVerifier {
+    fn challenge_needed(&self) -> Option<Url> {
+        self.details
+            .require_challenge
+            .then(|| self.details.challenge_url.clone())
+    }
+
+    async fn verify(&self, secret_key: &SecretKey) -> anyhow::Result<bool> {
+        let res = self
+            .http
+            .post(self.details.verify_url.clone())
+            .form(&[
+                ("secret", self.details.secret.as_str()),
+                ("response", &hex::encode(secret_key)),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        let res: SiteVerifyResponse = serde_json::from_slice(&res)?;
+        if !res.success && !res.error_codes.is_empty() {
+            log::info!(
+                target: "secret_verifier",
+                "secret key was rejected: {}",
+                res.error_codes.join(", ")
+            );
+        }
+        Ok(res.success)
+    }
+}
+
+/// Accepts a fixed list of secret keys, for tests.
+#[cfg(test)]
+#[derive(Debug)]
+pub struct SecretVerifierStub {
+    pub valid_keys: Vec<SecretKey>,
+    pub challenge_url: Option<Url>,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl SecretVerifier for SecretVerifierStub {
+    fn challenge_needed(&self) -> Option<Url> {
+        self.challenge_url.clone()
+    }
+
+    async fn verify(&self, secret_key: &SecretKey) -> anyhow::Result<bool> {
+        Ok(self.valid_keys.contains(secret_key))
+    }
+}
+
+/// Checks the secret key of a token request.
+/// Returns the url of the web validation process, if the client has to go through it first.
+pub async fn check_secret_key<E>(
+    shared: &Shared,
+    requires_secret: bool,
+    secret_key: Option<&SecretKey>,
+) -> Result<Option<Url>, AccountServerRequestError<E>> {
+    let verifier = shared.secret_verifier.read().clone();
+    if !requires_secret {
+        return Ok(verifier.challenge_needed());
+    }
+
+    let Some(secret_key) = secret_key else {
+        return Err(AccountServerRequestError::Other(
+            "This function is only for requests with a secret verification token.".to_string(),
+        ));
+    };
+    match verifier.verify(secret_key).await {
+        Ok(true) => Ok(None),
+        Ok(false) => Err(AccountServerRequestError::Other(
+            "The secret verification token is invalid or expired.".to_string(),
+        )),
+        Err(err) => Err(request_error("secret_verifier", err)),
+    }
+}
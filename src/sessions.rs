@@ -0,0 +1,168 @@
+pub mod queries;
+
+use std::sync::Arc;
+
+use axum::Json;
+use ddnet_account_sql::{any::AnyPool, query::Query};
+use ddnet_accounts_shared::{
+    account_server::{
+        errors::{Empty, RequestFailure},
+        result::AccountServerReqResult,
+        sessions::{session_fingerprint, SessionInfo, SessionRevokeError, SessionsResponse},
+    },
+    client::{
+        machine_id::MachineUid,
+        sessions::{session_revoke_signed_data, SessionRevokeRequest, SessionsRequest},
+    },
+};
+use ed25519_dalek::VerifyingKey;
+
+use crate::{
+    logout::queries::RemoveSession,
+    request_error::{request_error, request_logic_error},
+    shared::{Shared, CERT_MAX_AGE_DELTA, CERT_MIN_AGE_DELTA},
+};
+
+use self::queries::{SessionData, Sessions};
+
+pub async fn sessions_request(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Json(data): Json<SessionsRequest>,
+) -> Json<AccountServerReqResult<SessionsResponse, Empty>> {
+    Json(
+        sessions(shared, pool, data)
+            .await
+            .map_err(|err| request_error("sessions", err)),
+    )
+}
+
+pub async fn sessions(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    data: SessionsRequest,
+) -> anyhow::Result<SessionsResponse> {
+    data.account_data
+        .public_key
+        .verify_strict(data.time_stamp.to_string().as_bytes(), &data.signature)?;
+    let now = chrono::Utc::now();
+    let delta = now.signed_duration_since(data.time_stamp);
+    anyhow::ensure!(
+        delta < CERT_MAX_AGE_DELTA && delta > CERT_MIN_AGE_DELTA,
+        RequestFailure::TimestampOutOfWindow
+    );
+
+    let mut connection = pool.acquire().await?;
+    let mut connection = connection.acquire().await?;
+
+    let qry = Sessions {
+        session_pub_key: data.account_data.public_key.as_bytes(),
+        session_hw_id: &data.account_data.hw_id,
+    };
+    let rows = qry
+        .query(&shared.db.sessions_statement)
+        .fetch_all(&mut connection)
+        .await?;
+
+    let mut sessions = Vec::new();
+    for row in rows {
+        let session = Sessions::row_data(&row)?;
+        let (pub_key, hw_id) = session_keys(&session)?;
+        sessions.push(SessionInfo {
+            fingerprint: session_fingerprint(&pub_key),
+            create_time: session.create_time,
+            last_sign_time: session.last_sign_time,
+            device_label: session.device_label,
+            is_current: pub_key == data.account_data.public_key && hw_id == data.account_data.hw_id,
+        });
+    }
+
+    Ok(SessionsResponse { sessions })
+}
+
+pub async fn session_revoke_request(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    Json(data): Json<SessionRevokeRequest>,
+) -> Json<AccountServerReqResult<(), SessionRevokeError>> {
+    Json(
+        session_revoke(shared, pool, data)
+            .await
+            .map_err(|err| request_logic_error("session_revoke", err)),
+    )
+}
+
+pub async fn session_revoke(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    data: SessionRevokeRequest,
+) -> anyhow::Result<()> {
+    data.account_data.public_key.verify_strict(
+        session_revoke_signed_data(&data.time_stamp, &data.fingerprint).as_bytes(),
+        &data.signature,
+    )?;
+    let now = chrono::Utc::now();
+    let delta = now.signed_duration_since(data.time_stamp);
+    anyhow::ensure!(
+        delta < CERT_MAX_AGE_DELTA && delta > CERT_MIN_AGE_DELTA,
+        RequestFailure::TimestampOutOfWindow
+    );
+
+    let mut connection = pool.acquire().await?;
+    let mut connection = connection.acquire().await?;
+
+    connection
+        .transaction(|mut connection| {
+            Box::pin(async move {
+                // only sessions of the same account can be revoked
+                let qry = Sessions {
+                    session_pub_key: data.account_data.public_key.as_bytes(),
+                    session_hw_id: &data.account_data.hw_id,
+                };
+                let rows = qry
+                    .query(&shared.db.sessions_statement)
+                    .fetch_all(&mut connection.con())
+                    .await?;
+
+                let mut revoke_session = None;
+                for row in rows {
+                    let session = Sessions::row_data(&row)?;
+                    let (pub_key, hw_id) = session_keys(&session)?;
+                    if session_fingerprint(&pub_key) == data.fingerprint {
+                        revoke_session = Some((pub_key, hw_id));
+                        break;
+                    }
+                }
+                let Some((pub_key, hw_id)) = revoke_session else {
+                    return Err(SessionRevokeError::SessionNotFound.into());
+                };
+
+                let qry = RemoveSession {
+                    pub_key: pub_key.as_bytes(),
+                    hw_id: &hw_id,
+                };
+                qry.query(&shared.db.logout_statement)
+                    .execute(&mut connection.con())
+                    .await?;
+
+                anyhow::Ok(())
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+fn session_keys(session: &SessionData) -> anyhow::Result<(VerifyingKey, MachineUid)> {
+    let pub_key: [u8; 32] = session
+        .pub_key
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Session public key has an invalid length."))?;
+    let hw_id: MachineUid = session
+        .hw_id
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Session hardware id has an invalid length."))?;
+    Ok((VerifyingKey::from_bytes(&pub_key)?, hw_id))
+}
@@ -0,0 +1,12 @@
+SELECT
+    user_session.pub_key,
+    user_session.hw_id,
+    user_session.create_time,
+    user_session.last_sign_time,
+    user_session.device_label
+FROM
+    user_session
+    INNER JOIN user_session AS cur_session ON cur_session.account_id = user_session.account_id
+WHERE
+    cur_session.pub_key = ?
+    AND cur_session.hw_id = ?;
@@ -0,0 +1,95 @@
+use anyhow::anyhow;
+use axum::async_trait;
+use ddnet_account_sql::query::Query;
+use ddnet_accounts_shared::client::machine_id::MachineUid;
+use sqlx::Executor;
+use sqlx::Row;
+use sqlx::Statement;
+
+/// All sessions of the account the given session belongs to.
+pub struct Sessions<'a> {
+    pub session_pub_key: &'a [u8; 32],
+    pub session_hw_id: &'a MachineUid,
+}
+
+pub struct SessionData {
+    pub pub_key: Vec<u8>,
+    pub hw_id: Vec<u8>,
+    pub create_time: sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    pub last_sign_time: sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    pub device_label: Option<String>,
+}
+
+#[async_trait]
+impl Query<SessionData> for Sessions<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/sessions.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/sessions.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement
+            .query()
+            .bind(self.session_pub_key.as_slice())
+            .bind(self.session_hw_id.as_slice())
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement
+            .query()
+            .bind(self.session_pub_key.as_slice())
+            .bind(self.session_hw_id.as_slice())
+    }
+    fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<SessionData> {
+        Ok(SessionData {
+            pub_key: row
+                .try_get("pub_key")
+                .map_err(|err| anyhow!("Failed get column pub_key: {err}"))?,
+            hw_id: row
+                .try_get("hw_id")
+                .map_err(|err| anyhow!("Failed get column hw_id: {err}"))?,
+            create_time: row
+                .try_get("create_time")
+                .map_err(|err| anyhow!("Failed get column create_time: {err}"))?,
+            last_sign_time: row
+                .try_get("last_sign_time")
+                .map_err(|err| anyhow!("Failed get column last_sign_time: {err}"))?,
+            device_label: row
+                .try_get("device_label")
+                .map_err(|err| anyhow!("Failed get column device_label: {err}"))?,
+        })
+    }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<SessionData> {
+        Ok(SessionData {
+            pub_key: row
+                .try_get("pub_key")
+                .map_err(|err| anyhow!("Failed get column pub_key: {err}"))?,
+            hw_id: row
+                .try_get("hw_id")
+                .map_err(|err| anyhow!("Failed get column hw_id: {err}"))?,
+            create_time: row
+                .try_get("create_time")
+                .map_err(|err| anyhow!("Failed get column create_time: {err}"))?,
+            last_sign_time: row
+                .try_get("last_sign_time")
+                .map_err(|err| anyhow!("Failed get column last_sign_time: {err}"))?,
+            device_label: row
+                .try_get("device_label")
+                .map_err(|err| anyhow!("Failed get column device_label: {err}"))?,
+        })
+    }
+}
@@ -0,0 +1,346 @@
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+use anyhow::anyhow;
+use serde_json::Value;
+
+use crate::{
+    file_watcher::FileWatcher,
+    prepare_email_transport, prepare_steam_params,
+    secret_verifier::{HttpSecretVerifier, NoSecretVerifier, SecretVerifier},
+    shared::Shared,
+    Details, LimiterSettings, LimiterValues,
+};
+
+/// The settings files that are used, if none is given explicitly.
+pub const DEFAULT_FILES: [&str; 2] = ["settings.toml", "settings.json"];
+/// All environment variables with this prefix override a setting,
+/// e.g. `ACCOUNT_DB_PASSWORD` or `ACCOUNT_DB__PASSWORD`.
+pub const ENV_PREFIX: &str = "ACCOUNT_";
+
+/// Where the settings come from, later sources override earlier ones:
+/// the settings file (toml or json), then environment variables,
+/// then the `--set` flags of the command line.
+#[derive(Debug, Clone)]
+pub struct SettingsSources {
+    pub file: PathBuf,
+    /// `path.to.setting=value`, e.g. `http.port=8080`
+    pub overrides: Vec<String>,
+}
+
+/// Parses a value from the environment or command line.
+/// Strings stay strings, everything else (numbers, bools, arrays)
+/// is parsed as json.
+fn parse_value(existing: Option<&Value>, value: &str) -> Value {
+    match existing {
+        None | Some(Value::String(_)) => Value::String(value.to_string()),
+        Some(_) => serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string())),
+    }
+}
+
+/// The path of the setting of an environment variable (without prefix).
+/// `__` separates the entries, e.g. `EMAIL__EMAIL_FROM` -> `email.email_from`.
+/// Without `__` the name is matched against the existing entries, since names
+/// can contain underscores themselves, e.g. `DB_PASSWORD` -> `db.password`.
+/// Returns `None` if the name matches no entry.
+fn env_var_path(settings: &Value, name: &str) -> Option<Vec<String>> {
+    let name = name.to_lowercase();
+    if name.contains("__") {
+        return Some(name.split("__").map(str::to_string).collect());
+    }
+
+    let mut path = Vec::new();
+    let mut cur = settings;
+    let mut rest = name.as_str();
+    while let Value::Object(entries) = cur {
+        if entries.contains_key(rest) {
+            break;
+        }
+        let Some(key) = entries
+            .iter()
+            .filter(|(key, entry)| {
+                rest.starts_with(key.as_str())
+                    && rest[key.len()..].starts_with('_')
+                    && entry.is_object()
+            })
+            .map(|(key, _)| key)
+            .max_by_key(|key| key.len())
+        else {
+            break;
+        };
+        path.push(key.clone());
+        cur = &entries[key.as_str()];
+        rest = &rest[key.len() + 1..];
+    }
+    // unrelated variables with the prefix don't create new entries
+    if path.is_empty() && settings.get(rest).is_none() {
+        return None;
+    }
+    path.push(rest.to_string());
+    Some(path)
+}
+
+/// Whether the serialized settings contain the setting of the path.
+fn has_setting(settings: &Value, path: &[String]) -> bool {
+    path.iter()
+        .try_fold(settings, |cur, name| cur.get(name))
+        .is_some()
+}
+
+/// Sets a value by its path, e.g. `db.password`, creating missing entries.
+fn apply_override(settings: &mut Value, path: &str, value: &str) -> anyhow::Result<()> {
+    let mut cur = settings;
+    let mut names = path.split('.').peekable();
+    while let Some(name) = names.next() {
+        if cur.is_null() {
+            *cur = Value::Object(Default::default());
+        }
+        let Value::Object(entries) = cur else {
+            return Err(anyhow!("`{path}` is not inside of an entry."));
+        };
+        if names.peek().is_none() {
+            let value = parse_value(entries.get(name), value);
+            entries.insert(name.to_string(), value);
+            return Ok(());
+        }
+        cur = entries.entry(name).or_insert(Value::Null);
+    }
+    Err(anyhow!("`{path}` is empty."))
+}
+
+/// Checks what can't be expressed by the types of the settings.
+fn validate(details: &Details) -> anyhow::Result<()> {
+    let mut errors = Vec::new();
+    if details
+        .email
+        .email_from
+        .parse::<lettre::message::Mailbox>()
+        .is_err()
+    {
+        errors.push(format!(
+            "`email.email_from` is not a valid email address: {}",
+            details.email.email_from
+        ));
+    }
+    let limits: HashMap<String, LimiterValues> =
+        serde_json::from_value(serde_json::to_value(&details.limitter)?)?;
+    for (name, limit) in limits {
+        if limit.time_until_another_attempt.is_zero() {
+            errors.push(format!(
+                "`limitter.{name}.time_until_another_attempt` must not be zero."
+            ));
+        }
+    }
+    if let Some(admin) = &details.admin {
+        if admin.keys.is_empty() {
+            errors.push("`admin.keys` must contain at least one key.".to_string());
+        }
+        for key in admin.keys.iter().filter(|key| key.key.is_empty()) {
+            errors.push(format!(
+                "The admin key of `{}` must not be empty.",
+                key.name
+            ));
+        }
+    }
+
+    anyhow::ensure!(errors.is_empty(), "{}", errors.join("\n"));
+    Ok(())
+}
+
+impl SettingsSources {
+    /// Without an explicit file, the first existing of the [`DEFAULT_FILES`] is used.
+    pub fn new(file: Option<PathBuf>, overrides: Vec<String>) -> Self {
+        let file = file.unwrap_or_else(|| {
+            DEFAULT_FILES
+                .iter()
+                .map(PathBuf::from)
+                .find(|file| file.exists())
+                .unwrap_or_else(|| DEFAULT_FILES[1].into())
+        });
+        Self { file, overrides }
+    }
+
+    async fn load_file(&self) -> anyhow::Result<Value> {
+        let cfg = tokio::fs::read_to_string(&self.file)
+            .await
+            .map_err(|err| anyhow!("Could not read {}: {err}", self.file.display()))?;
+        let is_toml = self
+            .file
+            .extension()
+            .is_some_and(|extension| extension == "toml");
+        if is_toml {
+            toml::from_str(&cfg).map_err(|err| anyhow!("{} is invalid: {err}", self.file.display()))
+        } else {
+            serde_json::from_str(&cfg)
+                .map_err(|err| anyhow!("{} is invalid: {err}", self.file.display()))
+        }
+    }
+
+    /// Loads the settings from all sources & validates them.
+    pub async fn load(&self) -> anyhow::Result<Details> {
+        self.load_with_env(std::env::vars().collect()).await
+    }
+
+    pub async fn load_with_env(&self, env: Vec<(String, String)>) -> anyhow::Result<Details> {
+        let mut settings = self.load_file().await?;
+        let mut env_settings = Vec::new();
+        for (name, value) in &env {
+            let Some(setting) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let applied = env_var_path(&settings, setting).and_then(|path| {
+                apply_override(&mut settings, &path.join("."), value)
+                    .ok()
+                    .map(|()| path)
+            });
+            env_settings.push((name, applied));
+        }
+        for set in &self.overrides {
+            let (path, value) = set
+                .split_once('=')
+                .ok_or_else(|| anyhow!("`{set}` must look like `path.to.setting=value`."))?;
+            apply_override(&mut settings, path, value)?;
+        }
+        let details: Details = serde_json::from_value(settings)
+            .map_err(|err| anyhow!("The settings are invalid: {err}"))?;
+        validate(&details)?;
+
+        // e.g. typos or variables of other programs
+        let known = serde_json::to_value(&details)?;
+        for (name, path) in env_settings {
+            if !path.is_some_and(|path| has_setting(&known, &path)) {
+                log::warn!(
+                    target: "settings",
+                    "The environment variable {name} doesn't match a setting, it is ignored."
+                );
+            }
+        }
+        Ok(details)
+    }
+}
+
+fn apply_limits(shared: &Shared, settings: &LimiterSettings) -> anyhow::Result<()> {
+    shared.token_limiter.update(settings)?;
+    shared.rate_limiters.update(settings)
+}
+
+/// Applies the changed settings to the running account server.
+///
+/// Settings that can only be changed by a restart, e.g. the database, keep their
+/// current value & are reported in the log. Returns the settings that are in use afterwards.
+pub async fn apply_settings(shared: &Shared, current: &Details, new: Details) -> Details {
+    let mut applied = new;
+
+    let requires_restart = |name: &str| {
+        log::warn!(
+            target: "settings",
+            "Changing `{name}` requires a restart of the account server, \
+            the old value stays in use until then."
+        );
+    };
+    if applied.db != current.db {
+        requires_restart("db");
+        applied.db = current.db.clone();
+    }
+    if applied.http != current.http {
+        requires_restart("http");
+        applied.http = current.http.clone();
+    }
+    if applied.admin != current.admin {
+        requires_restart("admin");
+        applied.admin = current.admin.clone();
+    }
+    if applied.metrics != current.metrics {
+        requires_restart("metrics");
+        applied.metrics = current.metrics.clone();
+    }
+
+    if applied.limitter != current.limitter {
+        match apply_limits(shared, &applied.limitter) {
+            Ok(()) => log::info!(target: "settings", "Applied the new `limitter` settings."),
+            Err(err) => {
+                log::error!(
+                    target: "settings",
+                    "The new `limitter` settings are invalid, keeping the old ones: {err}"
+                );
+                // undo the limiters that were already updated
+                let _ = apply_limits(shared, &current.limitter);
+                applied.limitter = current.limitter.clone();
+            }
+        }
+    }
+
+    if applied.email != current.email {
+        let res = match prepare_email_transport(&applied.email.transport) {
+            Ok(transport) => {
+                shared
+                    .email
+                    .set_transport(&applied.email.email_from, transport)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        match res {
+            Ok(()) => log::info!(target: "settings", "Applied the new `email` settings."),
+            Err(err) => {
+                log::error!(
+                    target: "settings",
+                    "The new `email` settings are not usable, keeping the old ones: {err}"
+                );
+                applied.email = current.email.clone();
+            }
+        }
+    }
+
+    if applied.steam != current.steam {
+        shared
+            .steam
+            .set_params(prepare_steam_params(&applied.steam));
+        log::info!(target: "settings", "Applied the new `steam` settings.");
+    }
+
+    if applied.secret_verifier != current.secret_verifier {
+        let secret_verifier: Arc<dyn SecretVerifier> = match &applied.secret_verifier {
+            Some(details) => Arc::new(HttpSecretVerifier::new(details.clone())),
+            None => Arc::new(NoSecretVerifier),
+        };
+        *shared.secret_verifier.write() = secret_verifier;
+        log::info!(target: "settings", "Applied the new `secret_verifier` settings.");
+    }
+
+    applied
+}
+
+/// Applies all changes of the settings file while the account server is running.
+/// Invalid settings are ignored, the current settings stay in use.
+/// Stops once a shutdown is triggered (see [`Shared::shutdown`]).
+pub async fn watch_settings(shared: Arc<Shared>, sources: SettingsSources, mut details: Details) {
+    let dir = sources
+        .file
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| ".".as_ref());
+    let Some(file) = sources.file.file_name() else {
+        return;
+    };
+    let mut watcher = FileWatcher::new(dir, file.as_ref());
+    loop {
+        let changed = tokio::select! {
+            res = watcher.wait_for_change() => res.is_ok(),
+            _ = shared.shutdown.wait() => false,
+        };
+        if !changed || shared.shutdown.is_triggered() {
+            break;
+        }
+        match sources.load().await {
+            Ok(new) => {
+                details = apply_settings(&shared, &details, new).await;
+            }
+            Err(err) => {
+                log::error!(
+                    target: "settings",
+                    "Keeping the current settings: {err}"
+                );
+            }
+        }
+    }
+}
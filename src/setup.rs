@@ -1,115 +1,693 @@
 use ddnet_account_sql::any::AnyConnection;
 use ddnet_account_sql::any::AnyPool;
-use ddnet_account_sql::version::get_version;
-use ddnet_account_sql::version::set_version;
-use sqlx::Executor;
-use sqlx::Statement;
+use ddnet_account_sql::version::Migrations;
+use futures::future::BoxFuture;
 
 const VERSION_NAME: &str = "account-server";
 
-async fn setup_version1_mysql(con: &mut sqlx::mysql::MySqlConnection) -> anyhow::Result<()> {
-    // first create all statements (syntax check)
-    let account = con.prepare(include_str!("setup/mysql/account.sql")).await?;
-    let credential_email = con
-        .prepare(include_str!("setup/mysql/credential_email.sql"))
-        .await?;
-    let credential_steam = con
-        .prepare(include_str!("setup/mysql/credential_steam.sql"))
-        .await?;
-    let credential_auth_tokens = con
-        .prepare(include_str!("setup/mysql/credential_auth_tokens.sql"))
-        .await?;
-    let account_tokens = con
-        .prepare(include_str!("setup/mysql/account_tokens.sql"))
-        .await?;
-    let session = con.prepare(include_str!("setup/mysql/session.sql")).await?;
-    let certs = con.prepare(include_str!("setup/mysql/certs.sql")).await?;
-
-    // afterwards actually create tables
-    account.query().execute(&mut *con).await?;
-    credential_email.query().execute(&mut *con).await?;
-    credential_steam.query().execute(&mut *con).await?;
-    credential_auth_tokens.query().execute(&mut *con).await?;
-    account_tokens.query().execute(&mut *con).await?;
-    session.query().execute(&mut *con).await?;
-    certs.query().execute(&mut *con).await?;
-
-    set_version(&mut AnyConnection::MySql(&mut *con), VERSION_NAME, 1).await?;
+mod mysql {
+    use ddnet_account_sql::any::AnyConnection;
+    use ddnet_account_sql::version::set_version;
+    use sqlx::Executor;
+    use sqlx::Statement;
 
-    Ok(())
+    use super::VERSION_NAME;
+
+    pub(super) async fn setup_version1(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        // first create all statements (syntax check)
+        let account = con.prepare(include_str!("setup/mysql/account.sql")).await?;
+        let credential_email = con
+            .prepare(include_str!("setup/mysql/credential_email.sql"))
+            .await?;
+        let credential_steam = con
+            .prepare(include_str!("setup/mysql/credential_steam.sql"))
+            .await?;
+        let credential_auth_tokens = con
+            .prepare(include_str!("setup/mysql/credential_auth_tokens.sql"))
+            .await?;
+        let account_tokens = con
+            .prepare(include_str!("setup/mysql/account_tokens.sql"))
+            .await?;
+        let session = con.prepare(include_str!("setup/mysql/session.sql")).await?;
+        let certs = con.prepare(include_str!("setup/mysql/certs.sql")).await?;
+
+        // afterwards actually create tables
+        account.query().execute(&mut *con).await?;
+        credential_email.query().execute(&mut *con).await?;
+        credential_steam.query().execute(&mut *con).await?;
+        credential_auth_tokens.query().execute(&mut *con).await?;
+        account_tokens.query().execute(&mut *con).await?;
+        session.query().execute(&mut *con).await?;
+        certs.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version2(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let session = con
+            .prepare(include_str!("setup/mysql/version2/session.sql"))
+            .await?;
+        session.query().execute(&mut *con).await?;
+
+        // the new columns must exist to prepare this statement
+        let session_init_times = con
+            .prepare(include_str!("setup/mysql/version2/session_init_times.sql"))
+            .await?;
+        session_init_times.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version2(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let session = con
+            .prepare(include_str!("setup/mysql/version2/delete/session.sql"))
+            .await?;
+        session.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version3(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let account_ban = con
+            .prepare(include_str!("setup/mysql/version3/account_ban.sql"))
+            .await?;
+        account_ban.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version3(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let account_ban = con
+            .prepare(include_str!("setup/mysql/version3/delete/account_ban.sql"))
+            .await?;
+        account_ban.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version4(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let account_ban = con
+            .prepare(include_str!("setup/mysql/version4/account_ban.sql"))
+            .await?;
+        account_ban.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version4(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let account_ban = con
+            .prepare(include_str!("setup/mysql/version4/delete/account_ban.sql"))
+            .await?;
+        account_ban.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version5(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let account_events = con
+            .prepare(include_str!("setup/mysql/version5/account_events.sql"))
+            .await?;
+        account_events.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version5(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let account_events = con
+            .prepare(include_str!(
+                "setup/mysql/version5/delete/account_events.sql"
+            ))
+            .await?;
+        account_events.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version6(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let email_outbox = con
+            .prepare(include_str!("setup/mysql/version6/email_outbox.sql"))
+            .await?;
+        email_outbox.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version6(
+        con: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<()> {
+        let email_outbox = con
+            .prepare(include_str!("setup/mysql/version6/delete/email_outbox.sql"))
+            .await?;
+        email_outbox.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete(con: &mut sqlx::mysql::MySqlConnection) -> anyhow::Result<()> {
+        // tables of later versions reference the ones of the first version,
+        // so they are dropped first (and might not exist yet).
+        let _ = delete_version6(&mut *con).await;
+        let _ = delete_version5(&mut *con).await;
+        let _ = delete_version3(&mut *con).await;
+
+        // first create all statements (syntax check)
+        // delete in reverse order to creating
+        let session = con
+            .prepare(include_str!("setup/mysql/delete/session.sql"))
+            .await?;
+        let credential_auth_tokens = con
+            .prepare(include_str!(
+                "setup/mysql/delete/credential_auth_tokens.sql"
+            ))
+            .await?;
+        let account_tokens = con
+            .prepare(include_str!("setup/mysql/delete/account_tokens.sql"))
+            .await?;
+        let credential_steam = con
+            .prepare(include_str!("setup/mysql/delete/credential_steam.sql"))
+            .await?;
+        let credential_email = con
+            .prepare(include_str!("setup/mysql/delete/credential_email.sql"))
+            .await?;
+        let account = con
+            .prepare(include_str!("setup/mysql/delete/account.sql"))
+            .await?;
+        let certs = con
+            .prepare(include_str!("setup/mysql/delete/certs.sql"))
+            .await?;
+
+        // afterwards actually drop tables
+        let session = session.query().execute(&mut *con).await;
+        let credential_auth_tokens = credential_auth_tokens.query().execute(&mut *con).await;
+        let account_tokens = account_tokens.query().execute(&mut *con).await;
+        let credential_steam = credential_steam.query().execute(&mut *con).await;
+        let credential_email = credential_email.query().execute(&mut *con).await;
+        let account = account.query().execute(&mut *con).await;
+        let certs = certs.query().execute(&mut *con).await;
+
+        let _ = set_version(&mut AnyConnection::MySql(&mut *con), VERSION_NAME, 0).await;
+
+        // handle errors at once
+        session
+            .and(credential_auth_tokens)
+            .and(account_tokens)
+            .and(credential_steam)
+            .and(credential_email)
+            .and(account)
+            .and(certs)?;
+
+        Ok(())
+    }
 }
 
-pub async fn setup_version1(con: &mut AnyConnection<'_>) -> anyhow::Result<()> {
-    match con {
-        AnyConnection::MySql(con) => setup_version1_mysql(con).await,
+mod sqlite {
+    use ddnet_account_sql::any::AnyConnection;
+    use ddnet_account_sql::version::set_version;
+    use sqlx::Executor;
+    use sqlx::Statement;
+
+    use super::VERSION_NAME;
+
+    pub(super) async fn setup_version1(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        // first create all statements (syntax check)
+        let account = con
+            .prepare(include_str!("setup/sqlite/account.sql"))
+            .await?;
+        let credential_email = con
+            .prepare(include_str!("setup/sqlite/credential_email.sql"))
+            .await?;
+        let credential_steam = con
+            .prepare(include_str!("setup/sqlite/credential_steam.sql"))
+            .await?;
+        let credential_auth_tokens = con
+            .prepare(include_str!("setup/sqlite/credential_auth_tokens.sql"))
+            .await?;
+        let account_tokens = con
+            .prepare(include_str!("setup/sqlite/account_tokens.sql"))
+            .await?;
+        let session = con
+            .prepare(include_str!("setup/sqlite/session.sql"))
+            .await?;
+        let certs = con.prepare(include_str!("setup/sqlite/certs.sql")).await?;
+
+        // afterwards actually create tables
+        account.query().execute(&mut *con).await?;
+        credential_email.query().execute(&mut *con).await?;
+        credential_steam.query().execute(&mut *con).await?;
+        credential_auth_tokens.query().execute(&mut *con).await?;
+        account_tokens.query().execute(&mut *con).await?;
+        session.query().execute(&mut *con).await?;
+        certs.query().execute(&mut *con).await?;
+
+        // indices can only be prepared after their table exists
+        let credential_auth_tokens_ty_identifier = con
+            .prepare(include_str!(
+                "setup/sqlite/credential_auth_tokens_ty_identifier.sql"
+            ))
+            .await?;
+        let credential_auth_tokens_identifier = con
+            .prepare(include_str!(
+                "setup/sqlite/credential_auth_tokens_identifier.sql"
+            ))
+            .await?;
+        credential_auth_tokens_ty_identifier
+            .query()
+            .execute(&mut *con)
+            .await?;
+        credential_auth_tokens_identifier
+            .query()
+            .execute(&mut *con)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version2(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        // first create all statements (syntax check)
+        let session_create_time = con
+            .prepare(include_str!(
+                "setup/sqlite/version2/session_create_time.sql"
+            ))
+            .await?;
+        let session_last_sign_time = con
+            .prepare(include_str!(
+                "setup/sqlite/version2/session_last_sign_time.sql"
+            ))
+            .await?;
+        let session_device_label = con
+            .prepare(include_str!(
+                "setup/sqlite/version2/session_device_label.sql"
+            ))
+            .await?;
+
+        // afterwards actually alter the tables
+        session_create_time.query().execute(&mut *con).await?;
+        session_last_sign_time.query().execute(&mut *con).await?;
+        session_device_label.query().execute(&mut *con).await?;
+
+        // the new columns must exist to prepare this statement
+        let session_init_times = con
+            .prepare(include_str!("setup/sqlite/version2/session_init_times.sql"))
+            .await?;
+        session_init_times.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version2(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        // first create all statements (syntax check)
+        let session_device_label = con
+            .prepare(include_str!(
+                "setup/sqlite/version2/delete/session_device_label.sql"
+            ))
+            .await?;
+        let session_last_sign_time = con
+            .prepare(include_str!(
+                "setup/sqlite/version2/delete/session_last_sign_time.sql"
+            ))
+            .await?;
+        let session_create_time = con
+            .prepare(include_str!(
+                "setup/sqlite/version2/delete/session_create_time.sql"
+            ))
+            .await?;
+
+        // afterwards actually alter the tables
+        session_device_label.query().execute(&mut *con).await?;
+        session_last_sign_time.query().execute(&mut *con).await?;
+        session_create_time.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version3(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        let account_ban = con
+            .prepare(include_str!("setup/sqlite/version3/account_ban.sql"))
+            .await?;
+        account_ban.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version3(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        let account_ban = con
+            .prepare(include_str!("setup/sqlite/version3/delete/account_ban.sql"))
+            .await?;
+        account_ban.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version4(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        // first create all statements (syntax check)
+        let account_ban_reason = con
+            .prepare(include_str!("setup/sqlite/version4/account_ban_reason.sql"))
+            .await?;
+        let account_ban_issuer = con
+            .prepare(include_str!("setup/sqlite/version4/account_ban_issuer.sql"))
+            .await?;
+        let account_ban_expire_time = con
+            .prepare(include_str!(
+                "setup/sqlite/version4/account_ban_expire_time.sql"
+            ))
+            .await?;
+
+        // afterwards actually alter the tables
+        account_ban_reason.query().execute(&mut *con).await?;
+        account_ban_issuer.query().execute(&mut *con).await?;
+        account_ban_expire_time.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version4(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        // first create all statements (syntax check)
+        let account_ban_expire_time = con
+            .prepare(include_str!(
+                "setup/sqlite/version4/delete/account_ban_expire_time.sql"
+            ))
+            .await?;
+        let account_ban_issuer = con
+            .prepare(include_str!(
+                "setup/sqlite/version4/delete/account_ban_issuer.sql"
+            ))
+            .await?;
+        let account_ban_reason = con
+            .prepare(include_str!(
+                "setup/sqlite/version4/delete/account_ban_reason.sql"
+            ))
+            .await?;
+
+        // afterwards actually alter the tables
+        account_ban_expire_time.query().execute(&mut *con).await?;
+        account_ban_issuer.query().execute(&mut *con).await?;
+        account_ban_reason.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version5(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        let account_events = con
+            .prepare(include_str!("setup/sqlite/version5/account_events.sql"))
+            .await?;
+        account_events.query().execute(&mut *con).await?;
+
+        // indices can only be prepared after their table exists
+        let account_events_account_id = con
+            .prepare(include_str!(
+                "setup/sqlite/version5/account_events_account_id.sql"
+            ))
+            .await?;
+        account_events_account_id.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version5(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        let account_events = con
+            .prepare(include_str!(
+                "setup/sqlite/version5/delete/account_events.sql"
+            ))
+            .await?;
+        account_events.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn setup_version6(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        let email_outbox = con
+            .prepare(include_str!("setup/sqlite/version6/email_outbox.sql"))
+            .await?;
+        email_outbox.query().execute(&mut *con).await?;
+
+        // indices can only be prepared after their table exists
+        let email_outbox_next_attempt = con
+            .prepare(include_str!(
+                "setup/sqlite/version6/email_outbox_next_attempt.sql"
+            ))
+            .await?;
+        email_outbox_next_attempt.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete_version6(
+        con: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<()> {
+        let email_outbox = con
+            .prepare(include_str!(
+                "setup/sqlite/version6/delete/email_outbox.sql"
+            ))
+            .await?;
+        email_outbox.query().execute(&mut *con).await?;
+
+        Ok(())
+    }
+
+    pub(super) async fn delete(con: &mut sqlx::sqlite::SqliteConnection) -> anyhow::Result<()> {
+        // tables of later versions reference the ones of the first version,
+        // so they are dropped first (and might not exist yet).
+        let _ = delete_version6(&mut *con).await;
+        let _ = delete_version5(&mut *con).await;
+        let _ = delete_version3(&mut *con).await;
+
+        // first create all statements (syntax check)
+        // delete in reverse order to creating
+        let session = con
+            .prepare(include_str!("setup/sqlite/delete/session.sql"))
+            .await?;
+        let credential_auth_tokens = con
+            .prepare(include_str!(
+                "setup/sqlite/delete/credential_auth_tokens.sql"
+            ))
+            .await?;
+        let account_tokens = con
+            .prepare(include_str!("setup/sqlite/delete/account_tokens.sql"))
+            .await?;
+        let credential_steam = con
+            .prepare(include_str!("setup/sqlite/delete/credential_steam.sql"))
+            .await?;
+        let credential_email = con
+            .prepare(include_str!("setup/sqlite/delete/credential_email.sql"))
+            .await?;
+        let account = con
+            .prepare(include_str!("setup/sqlite/delete/account.sql"))
+            .await?;
+        let certs = con
+            .prepare(include_str!("setup/sqlite/delete/certs.sql"))
+            .await?;
+
+        // afterwards actually drop tables
+        let session = session.query().execute(&mut *con).await;
+        let credential_auth_tokens = credential_auth_tokens.query().execute(&mut *con).await;
+        let account_tokens = account_tokens.query().execute(&mut *con).await;
+        let credential_steam = credential_steam.query().execute(&mut *con).await;
+        let credential_email = credential_email.query().execute(&mut *con).await;
+        let account = account.query().execute(&mut *con).await;
+        let certs = certs.query().execute(&mut *con).await;
+
+        let _ = set_version(&mut AnyConnection::Sqlite(&mut *con), VERSION_NAME, 0).await;
+
+        // handle errors at once
+        session
+            .and(credential_auth_tokens)
+            .and(account_tokens)
+            .and(credential_steam)
+            .and(credential_email)
+            .and(account)
+            .and(certs)?;
+
+        Ok(())
     }
 }
 
-pub async fn setup(pool: &AnyPool) -> anyhow::Result<()> {
-    tokio::fs::create_dir_all("config").await?;
+/// Other crates of the build might enable more databases (e.g. postgres),
+/// which the account server has no tables for.
+fn unsupported_database() -> anyhow::Error {
+    anyhow::anyhow!("the account server only supports MySQL & SQLite")
+}
+
+fn setup_version1<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::setup_version1(con).await,
+            AnyConnection::Sqlite(con) => sqlite::setup_version1(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
 
-    let mut pool_con = pool.acquire().await?;
-    let mut con = pool_con.acquire().await?;
+fn setup_version2<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::setup_version2(con).await,
+            AnyConnection::Sqlite(con) => sqlite::setup_version2(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
 
-    con.transaction(|mut con| {
-        Box::pin(async move {
-            let version = get_version(&mut con.con(), VERSION_NAME).await?;
-            if version < 1 {
-                setup_version1(&mut con.con()).await?;
-            }
+fn delete_version2<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::delete_version2(con).await,
+            AnyConnection::Sqlite(con) => sqlite::delete_version2(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
 
-            anyhow::Ok(())
-        })
+fn setup_version3<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::setup_version3(con).await,
+            AnyConnection::Sqlite(con) => sqlite::setup_version3(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
     })
-    .await
-}
-
-async fn delete_mysql(con: &mut sqlx::mysql::MySqlConnection) -> anyhow::Result<()> {
-    // first create all statements (syntax check)
-    // delete in reverse order to creating
-    let session = con
-        .prepare(include_str!("setup/mysql/delete/session.sql"))
-        .await?;
-    let credential_auth_tokens = con
-        .prepare(include_str!(
-            "setup/mysql/delete/credential_auth_tokens.sql"
-        ))
-        .await?;
-    let account_tokens = con
-        .prepare(include_str!("setup/mysql/delete/account_tokens.sql"))
-        .await?;
-    let credential_steam = con
-        .prepare(include_str!("setup/mysql/delete/credential_steam.sql"))
-        .await?;
-    let credential_email = con
-        .prepare(include_str!("setup/mysql/delete/credential_email.sql"))
-        .await?;
-    let account = con
-        .prepare(include_str!("setup/mysql/delete/account.sql"))
-        .await?;
-    let certs = con
-        .prepare(include_str!("setup/mysql/delete/certs.sql"))
-        .await?;
-
-    // afterwards actually drop tables
-    let session = session.query().execute(&mut *con).await;
-    let credential_auth_tokens = credential_auth_tokens.query().execute(&mut *con).await;
-    let account_tokens = account_tokens.query().execute(&mut *con).await;
-    let credential_steam = credential_steam.query().execute(&mut *con).await;
-    let credential_email = credential_email.query().execute(&mut *con).await;
-    let account = account.query().execute(&mut *con).await;
-    let certs = certs.query().execute(&mut *con).await;
-
-    let _ = set_version(&mut AnyConnection::MySql(&mut *con), VERSION_NAME, 0).await;
-
-    // handle errors at once
-    session
-        .and(credential_auth_tokens)
-        .and(account_tokens)
-        .and(credential_steam)
-        .and(credential_email)
-        .and(account)
-        .and(certs)?;
+}
+
+fn delete_version3<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::delete_version3(con).await,
+            AnyConnection::Sqlite(con) => sqlite::delete_version3(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
+
+fn setup_version4<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::setup_version4(con).await,
+            AnyConnection::Sqlite(con) => sqlite::setup_version4(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
+
+fn delete_version4<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::delete_version4(con).await,
+            AnyConnection::Sqlite(con) => sqlite::delete_version4(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
+
+fn setup_version5<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::setup_version5(con).await,
+            AnyConnection::Sqlite(con) => sqlite::setup_version5(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
+
+fn delete_version5<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::delete_version5(con).await,
+            AnyConnection::Sqlite(con) => sqlite::delete_version5(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
+
+fn setup_version6<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::setup_version6(con).await,
+            AnyConnection::Sqlite(con) => sqlite::setup_version6(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
+
+fn delete_version6<'a>(con: &'a mut AnyConnection<'_>) -> BoxFuture<'a, anyhow::Result<()>> {
+    Box::pin(async move {
+        match con {
+            AnyConnection::MySql(con) => mysql::delete_version6(con).await,
+            AnyConnection::Sqlite(con) => sqlite::delete_version6(con).await,
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported_database()),
+        }
+    })
+}
+
+/// All migrations of the account server tables, in order.
+pub fn migrations() -> Migrations {
+    Migrations::new(VERSION_NAME)
+        .step(setup_version1, None)
+        // session times & device label
+        .step(setup_version2, Some(delete_version2))
+        // account bans
+        .step(setup_version3, Some(delete_version3))
+        // ban reason, issuer & expiry
+        .step(setup_version4, Some(delete_version4))
+        // account events (audit log)
+        .step(setup_version5, Some(delete_version5))
+        // email outbox
+        .step(setup_version6, Some(delete_version6))
+}
+
+pub async fn setup(pool: &AnyPool) -> anyhow::Result<()> {
+    tokio::fs::create_dir_all("config").await?;
+
+    migrations().up(pool).await?;
 
     Ok(())
 }
@@ -119,8 +697,13 @@ pub async fn delete(pool: &AnyPool) -> anyhow::Result<()> {
     let con = con.acquire().await?;
     match con {
         AnyConnection::MySql(con) => {
-            let _ = delete_mysql(con).await;
+            let _ = mysql::delete(con).await;
+        }
+        AnyConnection::Sqlite(con) => {
+            let _ = sqlite::delete(con).await;
         }
+        #[allow(unreachable_patterns)]
+        _ => {}
     }
 
     let _ = tokio::fs::remove_file("signing_keys.json").await;
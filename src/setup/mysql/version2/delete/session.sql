@@ -0,0 +1,4 @@
+ALTER TABLE
+    user_session DROP COLUMN create_time,
+    DROP COLUMN last_sign_time,
+    DROP COLUMN device_label;
@@ -0,0 +1,8 @@
+ALTER TABLE
+    user_session
+ADD
+    COLUMN create_time DATETIME NOT NULL DEFAULT '1970-01-01 00:00:00',
+ADD
+    COLUMN last_sign_time DATETIME NOT NULL DEFAULT '1970-01-01 00:00:00',
+ADD
+    COLUMN device_label VARCHAR(64) NULL;
@@ -0,0 +1,6 @@
+-- the real creation time of existing sessions is unknown
+UPDATE
+    user_session
+SET
+    create_time = UTC_TIMESTAMP(),
+    last_sign_time = UTC_TIMESTAMP();
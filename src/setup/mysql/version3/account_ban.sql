@@ -0,0 +1,7 @@
+CREATE TABLE account_ban (
+    account_id BIGINT NOT NULL,
+    -- UTC timestamp! (UTC_TIMESTAMP())
+    create_time DATETIME NOT NULL,
+    FOREIGN KEY(account_id) REFERENCES account(id),
+    PRIMARY KEY(account_id)
+);
@@ -0,0 +1,8 @@
+ALTER TABLE
+    account_ban
+ADD
+    COLUMN reason VARCHAR(1024) NOT NULL DEFAULT '',
+ADD
+    COLUMN issuer VARCHAR(256) NOT NULL DEFAULT '',
+ADD
+    COLUMN expire_time DATETIME NULL;
@@ -0,0 +1,4 @@
+ALTER TABLE
+    account_ban DROP COLUMN reason,
+    DROP COLUMN issuer,
+    DROP COLUMN expire_time;
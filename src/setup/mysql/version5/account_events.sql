@@ -0,0 +1,21 @@
+CREATE TABLE account_events (
+    id BIGINT NOT NULL AUTO_INCREMENT,
+    -- no foreign key, the events should outlive the account
+    account_id BIGINT NOT NULL,
+    -- IMPORTANT: keep with in sync with the AccountEventKind enum in lib/ddnet-accounts-shared
+    event ENUM(
+        'login',
+        'linkcredential',
+        'unlinkcredential',
+        'logoutall',
+        'delete'
+    ) NOT NULL,
+    -- UTC timestamp! (UTC_TIMESTAMP())
+    create_time DATETIME NOT NULL,
+    client_ip VARCHAR(45) NOT NULL,
+    hw_id_hash BINARY(32) DEFAULT NULL,
+    -- IMPORTANT: keep with in sync with the AccountEventCredential enum in lib/ddnet-accounts-shared
+    credential_type ENUM('email', 'steam') DEFAULT NULL,
+    PRIMARY KEY(id),
+    INDEX(account_id, create_time)
+);
@@ -0,0 +1,15 @@
+CREATE TABLE email_outbox (
+    id BIGINT NOT NULL AUTO_INCREMENT,
+    recipient VARCHAR(255) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    html MEDIUMTEXT NOT NULL,
+    text MEDIUMTEXT NOT NULL,
+    -- the number of failed attempts to send this email
+    attempts INT NOT NULL DEFAULT 0,
+    -- UTC timestamp! (UTC_TIMESTAMP())
+    create_time DATETIME NOT NULL,
+    -- UTC timestamp! (UTC_TIMESTAMP())
+    next_attempt DATETIME NOT NULL,
+    PRIMARY KEY(id),
+    INDEX(next_attempt)
+);
@@ -0,0 +1,5 @@
+CREATE TABLE account (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- UTC timestamp! (datetime('now'))
+    create_time DATETIME NOT NULL
+);
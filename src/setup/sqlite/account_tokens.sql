@@ -0,0 +1,16 @@
+CREATE TABLE account_tokens (
+    account_id INTEGER NOT NULL,
+    token BLOB NOT NULL,
+    valid_until DATETIME NOT NULL,
+    -- IMPORTANT: keep with in sync with the AccountTokenType enum in src/types.rs
+    ty TEXT NOT NULL CHECK(
+        ty IN (
+            'logoutall',
+            'linkcredential',
+            'unlinkcredential',
+            'delete'
+        )
+    ),
+    FOREIGN KEY(account_id) REFERENCES account(id),
+    PRIMARY KEY(token)
+);
@@ -0,0 +1,6 @@
+CREATE TABLE certs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    cert_der BLOB,
+    -- UTC timestamp! (datetime('now'))
+    valid_until DATETIME NOT NULL
+);
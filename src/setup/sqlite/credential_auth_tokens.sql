@@ -0,0 +1,13 @@
+CREATE TABLE credential_auth_tokens (
+    token BLOB NOT NULL,
+    valid_until DATETIME NOT NULL,
+    -- IMPORTANT: keep with in sync with the TokenType enum in src/types.rs
+    ty TEXT NOT NULL CHECK(ty IN ('email', 'steam')),
+    -- IMPORTANT: keep with in sync with the AccountTokenType enum in src/types.rs
+    op TEXT NOT NULL CHECK(
+        op IN ('login', 'linkcredential', 'unlinkcredential')
+    ),
+    -- the email or steamid or similar depending on above type.
+    identifier VARCHAR(255) NOT NULL,
+    PRIMARY KEY(token)
+);
@@ -0,0 +1 @@
+CREATE INDEX credential_auth_tokens_identifier ON credential_auth_tokens (identifier);
@@ -0,0 +1,8 @@
+CREATE TABLE credential_email (
+    account_id INTEGER NOT NULL,
+    -- case insensitive like the default collation of mysql
+    email VARCHAR(255) NOT NULL COLLATE NOCASE,
+    valid_until DATETIME NOT NULL,
+    FOREIGN KEY(account_id) REFERENCES account(id),
+    PRIMARY KEY(email)
+);
@@ -0,0 +1,7 @@
+CREATE TABLE credential_steam (
+    account_id INTEGER NOT NULL,
+    steamid64 INTEGER NOT NULL,
+    valid_until DATETIME NOT NULL,
+    FOREIGN KEY(account_id) REFERENCES account(id),
+    PRIMARY KEY(steamid64)
+);
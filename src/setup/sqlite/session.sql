@@ -0,0 +1,7 @@
+CREATE TABLE user_session (
+    account_id INTEGER NOT NULL,
+    pub_key BLOB NOT NULL,
+    hw_id BLOB NOT NULL,
+    FOREIGN KEY(account_id) REFERENCES account(id),
+    UNIQUE(pub_key)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE
+    user_session DROP COLUMN create_time;
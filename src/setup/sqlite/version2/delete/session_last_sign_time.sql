@@ -0,0 +1,2 @@
+ALTER TABLE
+    user_session DROP COLUMN last_sign_time;
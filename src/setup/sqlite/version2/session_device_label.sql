@@ -0,0 +1,4 @@
+ALTER TABLE
+    user_session
+ADD
+    COLUMN device_label VARCHAR(64) NULL;
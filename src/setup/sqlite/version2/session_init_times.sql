@@ -0,0 +1,6 @@
+-- the real creation time of existing sessions is unknown
+UPDATE
+    user_session
+SET
+    create_time = datetime('now'),
+    last_sign_time = datetime('now');
@@ -0,0 +1,4 @@
+ALTER TABLE
+    user_session
+ADD
+    COLUMN last_sign_time DATETIME NOT NULL DEFAULT '1970-01-01 00:00:00';
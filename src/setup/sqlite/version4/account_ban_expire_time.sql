@@ -0,0 +1,4 @@
+ALTER TABLE
+    account_ban
+ADD
+    COLUMN expire_time DATETIME NULL;
@@ -0,0 +1,4 @@
+ALTER TABLE
+    account_ban
+ADD
+    COLUMN issuer VARCHAR(256) NOT NULL DEFAULT '';
@@ -0,0 +1,4 @@
+ALTER TABLE
+    account_ban
+ADD
+    COLUMN reason VARCHAR(1024) NOT NULL DEFAULT '';
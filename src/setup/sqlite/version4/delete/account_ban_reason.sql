@@ -0,0 +1,2 @@
+ALTER TABLE
+    account_ban DROP COLUMN reason;
@@ -0,0 +1,21 @@
+CREATE TABLE account_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- no foreign key, the events should outlive the account
+    account_id INTEGER NOT NULL,
+    -- IMPORTANT: keep with in sync with the AccountEventKind enum in lib/ddnet-accounts-shared
+    event TEXT NOT NULL CHECK(
+        event IN (
+            'login',
+            'linkcredential',
+            'unlinkcredential',
+            'logoutall',
+            'delete'
+        )
+    ),
+    -- UTC timestamp! (datetime('now'))
+    create_time DATETIME NOT NULL,
+    client_ip VARCHAR(45) NOT NULL,
+    hw_id_hash BLOB DEFAULT NULL,
+    -- IMPORTANT: keep with in sync with the AccountEventCredential enum in lib/ddnet-accounts-shared
+    credential_type TEXT DEFAULT NULL CHECK(credential_type IN ('email', 'steam'))
+);
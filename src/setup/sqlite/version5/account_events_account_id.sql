@@ -0,0 +1 @@
+CREATE INDEX account_events_account_id ON account_events (account_id, create_time);
@@ -0,0 +1,13 @@
+CREATE TABLE email_outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    recipient VARCHAR(255) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    html TEXT NOT NULL,
+    text TEXT NOT NULL,
+    -- the number of failed attempts to send this email
+    attempts INTEGER NOT NULL DEFAULT 0,
+    -- UTC timestamp! (datetime('now'))
+    create_time DATETIME NOT NULL,
+    -- UTC timestamp! (datetime('now'))
+    next_attempt DATETIME NOT NULL
+);
@@ -0,0 +1 @@
+CREATE INDEX email_outbox_next_attempt ON email_outbox (next_attempt);
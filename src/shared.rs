@@ -4,8 +4,10 @@ use chrono::TimeDelta;
 use parking_lot::RwLock;
 
 use crate::{
-    certs::PrivateKeys, db::DbConnectionShared, email::EmailShared, ip_limit::IpDenyList,
-    steam::SteamShared,
+    certs::PrivateKeys, db::DbConnectionShared, email::EmailShared, health::HealthCache,
+    ip_limit::IpDenyList, metrics::Metrics, rate_limit::RouteRateLimiters,
+    secret_verifier::SecretVerifier, shutdown::Shutdown, steam::SteamShared,
+    token_limit::TokenLimiter,
 };
 
 pub const CERT_MAX_AGE_DELTA: TimeDelta = TimeDelta::seconds(20 * 60);
@@ -23,8 +25,16 @@ pub struct Shared {
     /// All certificates that are valid for any certificate generated
     /// by any legit account server.
     pub cert_chain: Arc<RwLock<Arc<Vec<x509_cert::Certificate>>>>,
-    /// The email template for credential auth tokens
-    pub credential_auth_tokens_email: Arc<RwLock<Arc<String>>>,
-    /// The email template for account tokens
-    pub account_tokens_email: Arc<RwLock<Arc<String>>>,
+    /// Prometheus metrics, also shared with the email & steam helpers.
+    pub metrics: Arc<Metrics>,
+    /// Verifies the secret keys of the `*-secret` routes.
+    pub secret_verifier: RwLock<Arc<dyn SecretVerifier>>,
+    /// Limits the tokens issued per email & steamid.
+    pub token_limiter: TokenLimiter,
+    /// The ip rate limiters of all routes.
+    pub rate_limiters: RouteRateLimiters,
+    /// Stops the http server & all background tasks.
+    pub shutdown: Shutdown,
+    /// The last readiness report.
+    pub health: HealthCache,
 }
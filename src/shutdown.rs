@@ -0,0 +1,52 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::watch;
+
+/// How long in-flight requests may take to finish, after a shutdown was requested.
+pub const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Tells all parts of the account server to stop,
+/// e.g. the http server stops accepting new connections.
+#[derive(Debug, Clone)]
+pub struct Shutdown(Arc<watch::Sender<bool>>);
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self(Arc::new(watch::Sender::new(false)))
+    }
+}
+
+impl Shutdown {
+    pub fn trigger(&self) {
+        self.0.send_replace(true);
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Resolves once the shutdown was triggered,
+    /// immediately if it already was.
+    pub async fn wait(&self) {
+        let mut rx = self.0.subscribe();
+        let _ = rx.wait_for(|triggered| *triggered).await;
+    }
+}
+
+/// Resolves on ctrl+c or SIGTERM.
+pub async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        let mut terminate =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+                .expect("failed to install the SIGTERM handler");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
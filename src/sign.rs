@@ -3,16 +3,21 @@ pub mod queries;
 use std::{str::FromStr, sync::Arc, time::Duration};
 
 use axum::Json;
-use ddnet_account_sql::{any::AnyPool, query::Query};
+use ddnet_account_sql::{
+    any::{AnyConnection, AnyPool},
+    query::Query,
+};
 use ddnet_accounts_shared::{
     account_server::{
+        ban::AccountBan,
         cert_account_ext::{AccountCertData, AccountCertExt},
-        errors::{AccountServerRequestError, Empty},
+        errors::{AccountServerRequestError, RequestFailure},
         result::AccountServerReqResult,
-        sign::SignResponseSuccess,
+        sign::{SignError, SignResponseSuccess},
     },
     client::sign::SignRequest,
 };
+use ddnet_accounts_types::account_id::AccountId;
 use p256::ecdsa::DerSignature;
 use x509_cert::builder::Builder;
 use x509_cert::der::Encode;
@@ -21,31 +26,74 @@ use x509_cert::{
     time::Validity,
 };
 
-use crate::shared::{Shared, CERT_MAX_AGE_DELTA, CERT_MIN_AGE_DELTA};
+use crate::{
+    request_error::request_logic_error,
+    shared::{Shared, CERT_MAX_AGE_DELTA, CERT_MIN_AGE_DELTA},
+};
 
-use self::queries::AuthAttempt;
+use self::queries::{AccountBanQry, AuthAttempt, UpdateSignTime};
 
 pub async fn sign_request(
     shared: Arc<Shared>,
     pool: AnyPool,
     Json(data): Json<SignRequest>,
-) -> Json<AccountServerReqResult<SignResponseSuccess, Empty>> {
-    Json(
-        sign(shared, pool, data)
-            .await
-            .map_err(|err| AccountServerRequestError::Unexpected {
-                target: "sign".into(),
-                err: err.to_string(),
-                bt: err.backtrace().to_string(),
-            }),
-    )
+) -> Json<AccountServerReqResult<SignResponseSuccess, SignError>> {
+    Json(sign(shared, pool, data).await)
+}
+
+#[derive(Debug, Clone)]
+enum SignResponse {
+    /// Worked
+    Success(SignResponseSuccess),
+    /// The account is banned
+    Banned(AccountBan),
+}
+
+/// Returns the active ban of the account, if any.
+pub async fn account_ban(
+    shared: &Arc<Shared>,
+    account_id: &AccountId,
+    connection: &mut AnyConnection<'_>,
+) -> anyhow::Result<Option<AccountBan>> {
+    let qry = AccountBanQry { account_id };
+
+    let row = qry
+        .query(&shared.db.account_ban_statement)
+        .fetch_optional(connection)
+        .await?;
+
+    row.map(|row| {
+        let ban = AccountBanQry::row_data(&row)?;
+        anyhow::Ok(AccountBan {
+            reason: ban.reason,
+            expires: ban.expire_time,
+        })
+    })
+    .transpose()
 }
 
 pub async fn sign(
     shared: Arc<Shared>,
     pool: AnyPool,
     data: SignRequest,
-) -> anyhow::Result<SignResponseSuccess> {
+) -> AccountServerReqResult<SignResponseSuccess, SignError> {
+    let res = sign_impl(shared, pool, data)
+        .await
+        .map_err(|err| request_logic_error("sign", err))?;
+
+    match res {
+        SignResponse::Success(res) => Ok(res),
+        SignResponse::Banned(ban) => Err(AccountServerRequestError::LogicError(SignError::Banned(
+            ban,
+        ))),
+    }
+}
+
+async fn sign_impl(
+    shared: Arc<Shared>,
+    pool: AnyPool,
+    data: SignRequest,
+) -> anyhow::Result<SignResponse> {
     data.account_data
         .public_key
         .verify_strict(data.time_stamp.to_string().as_bytes(), &data.signature)?;
@@ -53,7 +101,7 @@ pub async fn sign(
     let delta = now.signed_duration_since(data.time_stamp);
     anyhow::ensure!(
         delta < CERT_MAX_AGE_DELTA && delta > CERT_MIN_AGE_DELTA,
-        "time stamp was not in a valid time frame."
+        RequestFailure::TimestampOutOfWindow
     );
 
     let mut connection = pool.acquire().await?;
@@ -62,10 +110,20 @@ pub async fn sign(
     let qry = AuthAttempt { data: &data };
     let row = qry
         .query(&shared.db.auth_attempt_statement)
-        .fetch_one(&mut connection)
-        .await?;
+        .fetch_optional(&mut connection)
+        .await?
+        .ok_or(SignError::SessionInvalid)?;
     let auth_data = AuthAttempt::row_data(&row)?;
 
+    if let Some(ban) = account_ban(&shared, &auth_data.account_id, &mut connection).await? {
+        return Ok(SignResponse::Banned(ban));
+    }
+
+    let qry = UpdateSignTime { data: &data };
+    qry.query(&shared.db.update_sign_time_statement)
+        .execute(&mut connection)
+        .await?;
+
     let serial_number = SerialNumber::from(42u32);
     let validity = Validity::from_now(Duration::new(60 * 60, 0))?;
     let profile = Profile::Root;
@@ -95,5 +153,7 @@ pub async fn sign(
     })?;
     let cert = builder.build::<DerSignature>()?.to_der()?;
 
-    Ok(SignResponseSuccess { cert_der: cert })
+    Ok(SignResponse::Success(SignResponseSuccess {
+        cert_der: cert,
+    }))
 }
@@ -0,0 +1,11 @@
+SELECT
+    account_ban.reason,
+    account_ban.expire_time
+FROM
+    account_ban
+WHERE
+    account_ban.account_id = ?
+    AND (
+        account_ban.expire_time IS NULL
+        OR account_ban.expire_time > UTC_TIMESTAMP()
+    );
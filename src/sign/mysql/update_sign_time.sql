@@ -0,0 +1,7 @@
+UPDATE
+    user_session
+SET
+    last_sign_time = UTC_TIMESTAMP()
+WHERE
+    user_session.pub_key = ?
+    AND user_session.hw_id = ?;
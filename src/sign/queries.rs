@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use ddnet_account_sql::query::Query;
 use ddnet_accounts_shared::client::sign::SignRequest;
 use ddnet_accounts_types::account_id::AccountId;
@@ -25,6 +26,11 @@ impl Query<AuthAttemptData> for AuthAttempt<'_> {
     ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
         Ok(connection.prepare(include_str!("mysql/auth.sql")).await?)
     }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection.prepare(include_str!("sqlite/auth.sql")).await?)
+    }
     fn query_mysql<'b>(
         &'b self,
         statement: &'b sqlx::mysql::MySqlStatement<'static>,
@@ -34,10 +40,126 @@ impl Query<AuthAttemptData> for AuthAttempt<'_> {
             .bind(self.data.account_data.public_key.as_bytes().as_slice())
             .bind(self.data.account_data.hw_id.as_slice())
     }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement
+            .query()
+            .bind(self.data.account_data.public_key.as_bytes().as_slice())
+            .bind(self.data.account_data.hw_id.as_slice())
+    }
     fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<AuthAttemptData> {
         Ok(AuthAttemptData {
             account_id: row.try_get("account_id")?,
             creation_date: row.try_get("create_time")?,
         })
     }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<AuthAttemptData> {
+        Ok(AuthAttemptData {
+            account_id: row.try_get("account_id")?,
+            creation_date: row.try_get("create_time")?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdateSignTime<'a> {
+    pub data: &'a SignRequest,
+}
+
+#[async_trait::async_trait]
+impl Query<()> for UpdateSignTime<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/update_sign_time.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/update_sign_time.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement
+            .query()
+            .bind(self.data.account_data.public_key.as_bytes().as_slice())
+            .bind(self.data.account_data.hw_id.as_slice())
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement
+            .query()
+            .bind(self.data.account_data.public_key.as_bytes().as_slice())
+            .bind(self.data.account_data.hw_id.as_slice())
+    }
+    fn row_data_mysql(_row: &sqlx::mysql::MySqlRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+    fn row_data_sqlite(_row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<()> {
+        Err(anyhow!("Row data is not supported"))
+    }
+}
+
+/// The active ban of an account, if any.
+#[derive(Debug)]
+pub struct AccountBanQry<'a> {
+    pub account_id: &'a AccountId,
+}
+
+#[derive(Debug)]
+pub struct AccountBanData {
+    pub reason: String,
+    pub expire_time: Option<DateTime<Utc>>,
+}
+
+#[async_trait::async_trait]
+impl Query<AccountBanData> for AccountBanQry<'_> {
+    async fn prepare_mysql(
+        connection: &mut sqlx::mysql::MySqlConnection,
+    ) -> anyhow::Result<sqlx::mysql::MySqlStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("mysql/account_ban.sql"))
+            .await?)
+    }
+    async fn prepare_sqlite(
+        connection: &mut sqlx::sqlite::SqliteConnection,
+    ) -> anyhow::Result<sqlx::sqlite::SqliteStatement<'static>> {
+        Ok(connection
+            .prepare(include_str!("sqlite/account_ban.sql"))
+            .await?)
+    }
+    fn query_mysql<'b>(
+        &'b self,
+        statement: &'b sqlx::mysql::MySqlStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+        statement.query().bind(self.account_id)
+    }
+    fn query_sqlite<'b>(
+        &'b self,
+        statement: &'b sqlx::sqlite::SqliteStatement<'static>,
+    ) -> sqlx::query::Query<'b, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'b>> {
+        statement.query().bind(self.account_id)
+    }
+    fn row_data_mysql(row: &sqlx::mysql::MySqlRow) -> anyhow::Result<AccountBanData> {
+        Ok(AccountBanData {
+            reason: row.try_get("reason")?,
+            expire_time: row.try_get("expire_time")?,
+        })
+    }
+    fn row_data_sqlite(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<AccountBanData> {
+        Ok(AccountBanData {
+            reason: row.try_get("reason")?,
+            expire_time: row.try_get("expire_time")?,
+        })
+    }
 }
@@ -0,0 +1,7 @@
+UPDATE
+    user_session
+SET
+    last_sign_time = datetime('now')
+WHERE
+    user_session.pub_key = ?
+    AND user_session.hw_id = ?;
@@ -1,8 +1,11 @@
 use std::{fmt::Debug, sync::Arc};
 
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::metrics::Metrics;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SteamUser {
     pub result: String,
@@ -38,16 +41,40 @@ impl SteamHook for SteamHookDummy {
     }
 }
 
+/// The parameters of the steam web api,
+/// they can be replaced while the account server is running.
+#[derive(Debug)]
+pub struct SteamParams {
+    steam_auth_url: String,
+    publisher_auth_key: String,
+    identity: Option<String>,
+    app_id: u32,
+}
+
+impl SteamParams {
+    pub fn new(
+        steam_auth_url: Url,
+        publisher_auth_key: &str,
+        identity: Option<&str>,
+        app_id: u32,
+    ) -> Self {
+        Self {
+            app_id,
+            publisher_auth_key: publisher_auth_key.to_string(),
+            identity: identity.map(|i| i.to_string()),
+            steam_auth_url: steam_auth_url.to_string(),
+        }
+    }
+}
+
 /// Shared steam helper
 #[derive(Debug)]
 pub struct SteamShared {
     http: reqwest::Client,
     steam_hook: Arc<dyn SteamHook>,
+    metrics: Arc<Metrics>,
 
-    steam_auth_url: String,
-    publisher_auth_key: String,
-    identity: Option<String>,
-    app_id: u32,
+    params: RwLock<Arc<SteamParams>>,
 }
 
 /// https://partner.steamgames.com/doc/webapi/ISteamUserAuth#AuthenticateUserTicket
@@ -55,25 +82,24 @@ pub const OFFICIAL_STEAM_AUTH_URL: &str =
     "https://partner.steam-api.com/ISteamUserAuth/AuthenticateUserTicket/v1/";
 
 impl SteamShared {
-    pub fn new(
-        steam_auth_url: Url,
-        publisher_auth_key: &str,
-        identity: Option<&str>,
-        app_id: u32,
-    ) -> anyhow::Result<Self> {
+    pub fn new(params: SteamParams) -> anyhow::Result<Self> {
         let http = reqwest::Client::new();
 
         Ok(Self {
             http,
             steam_hook: Arc::new(SteamHookDummy {}),
+            metrics: Default::default(),
 
-            app_id,
-            publisher_auth_key: publisher_auth_key.to_string(),
-            identity: identity.map(|i| i.to_string()),
-            steam_auth_url: steam_auth_url.to_string(),
+            params: RwLock::new(Arc::new(params)),
         })
     }
 
+    /// Replaces the parameters of the steam web api,
+    /// already running verifications still use the old ones.
+    pub fn set_params(&self, params: SteamParams) {
+        *self.params.write() = Arc::new(params);
+    }
+
     /// A hook that can see all sent steam token requests.
     /// Currently only useful for testing
     #[allow(dead_code)]
@@ -81,22 +107,46 @@ impl SteamShared {
         self.steam_hook = Arc::new(hook);
     }
 
+    /// Where the outcome of steam ticket verifications is counted.
+    pub fn set_metrics(&mut self, metrics: Arc<Metrics>) {
+        self.metrics = metrics;
+    }
+
+    /// Checks if the steam web api is reachable,
+    /// any response counts, since a request without ticket can't succeed anyway.
+    pub async fn test_connection(&self) -> anyhow::Result<()> {
+        let url = self.params.read().steam_auth_url.clone();
+        self.http.get(url).send().await?;
+        Ok(())
+    }
+
     pub async fn verify_steamid64(&self, steam_ticket: Vec<u8>) -> anyhow::Result<i64> {
+        let res = self.verify_steamid64_impl(steam_ticket).await;
+        Metrics::count_result(&self.metrics.steam_verifications, &res);
+        res
+    }
+
+    async fn verify_steamid64_impl(&self, steam_ticket: Vec<u8>) -> anyhow::Result<i64> {
         self.steam_hook.on_steam_code(&steam_ticket);
 
         let ticket = hex::encode(steam_ticket);
 
-        let url = self.identity.as_ref().map_or_else(
+        let params = self.params.read().clone();
+        let url = params.identity.as_ref().map_or_else(
             || {
                 format!(
                     "{}?key={}&appid={}&ticket={}",
-                    self.steam_auth_url, self.publisher_auth_key, self.app_id, ticket
+                    params.steam_auth_url, params.publisher_auth_key, params.app_id, ticket
                 )
             },
             |identity| {
                 format!(
                     "{}?key={}&appid={}&ticket={}&identity={}",
-                    self.steam_auth_url, self.publisher_auth_key, self.app_id, ticket, identity
+                    params.steam_auth_url,
+                    params.publisher_auth_key,
+                    params.app_id,
+                    ticket,
+                    identity
                 )
             },
         );
@@ -113,7 +163,7 @@ mod test {
     use axum::{extract::Query, response::IntoResponse, routing::get, Json, Router};
     use serde::Deserialize;
 
-    use crate::steam::{HttpResult, SteamShared};
+    use crate::steam::{HttpResult, SteamParams, SteamShared};
 
     #[tokio::test]
     async fn steam_test() {
@@ -150,12 +200,12 @@ mod test {
             .unwrap();
         tokio::spawn(async move { axum::serve(listener, app).await });
 
-        let steam = SteamShared::new(
+        let steam = SteamShared::new(SteamParams::new(
             "http://127.0.0.1:4433/".try_into().unwrap(),
             "the_secret_publisher_key",
             Some("account"),
             1337,
-        )
+        ))
         .unwrap();
 
         let steamid = steam.verify_steamid64(vec![]).await.unwrap();
@@ -0,0 +1,330 @@
+use std::net::Ipv4Addr;
+
+use ddnet_account_sql::query::Query;
+use ddnet_accounts_shared::{
+    account_server::{
+        ban::AccountBan,
+        delete::DeleteError,
+        errors::{AccountServerRequestError, RequestFailure},
+        login::LoginError,
+        otp::generate_otp,
+        result::AccountServerReqResult,
+        sign::SignError,
+    },
+    client::{
+        account_token::AccountTokenOperation, credential_auth_token::CredentialAuthTokenOperation,
+        delete::DeleteRequest, machine_id::machine_uid, sign::prepare_sign_request,
+    },
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    account_token::queries::AddAccountTokenEmail,
+    admin::{
+        prepare_admin_listener, prepare_admin_router, run_admin, AdminAccountInfoResponse,
+        AdminBanRequest, AdminDetails, AdminKey, AdminListen, AdminListener,
+    },
+    credential_auth_token::queries::AddCredentialAuthToken,
+    delete::delete,
+    login::login,
+    request_error::request_logic_error,
+    request_log::REQUEST_ID_HEADER,
+    sign::sign,
+    tests::types::SqliteTestServer,
+    types::TokenType,
+};
+
+async fn admin_request<T: DeserializeOwned>(req: reqwest::RequestBuilder) -> anyhow::Result<T> {
+    let res = req.bearer_auth("test-key").send().await?;
+    Ok(serde_json::from_slice(&res.bytes().await?)?)
+}
+
+/// Tests the admin api on top of a sqlite database
+#[tokio::test]
+async fn admin_api() {
+    let test = async move {
+        let test_server =
+            SqliteTestServer::new("test-account-server-admin.sqlite", Default::default()).await?;
+        let (pool, shared) = (test_server.pool.clone(), test_server.shared.clone());
+
+        let add_token = || {
+            let shared = shared.clone();
+            let pool = pool.clone();
+            async move {
+                let token = generate_otp();
+                let mut connection = pool.acquire().await?;
+                let mut con = connection.acquire().await?;
+                AddCredentialAuthToken {
+                    token: &token,
+                    ty: &TokenType::Email,
+                    identifier: "test@localhost",
+                    op: &CredentialAuthTokenOperation::Login,
+                }
+                .query(&shared.db.credential_auth_token_statement)
+                .execute(&mut con)
+                .await?;
+                anyhow::Ok(hex::encode(token))
+            }
+        };
+
+        // create an account
+        let (login_req, account_data) =
+            ddnet_accounts_shared::client::login::login(add_token().await?)?;
+        let account_id = login(
+            shared.clone(),
+            pool.clone(),
+            Ipv4Addr::LOCALHOST.into(),
+            login_req,
+        )
+        .await?;
+        let hw_id = machine_uid()?;
+        let sign_req =
+            || prepare_sign_request(hw_id, &account_data.private_key, account_data.public_key);
+
+        let details = AdminDetails {
+            listen: AdminListen::Port { port: 0 },
+            keys: vec![AdminKey {
+                name: "test".into(),
+                key: "test-key".into(),
+            }],
+        };
+        let listener = prepare_admin_listener(&details.listen).await?;
+        let AdminListener::Tcp(tcp_listener) = &listener else {
+            unreachable!("the admin api listens on a tcp port");
+        };
+        let addr = tcp_listener.local_addr()?;
+        let app = prepare_admin_router(&details, &shared, &pool);
+        let server = tokio::spawn(run_admin(listener, app, shared.shutdown.clone()));
+
+        let http = reqwest::Client::new();
+        let url = |path: &str| format!("http://{addr}{path}");
+        type InfoResult = AccountServerReqResult<Option<AdminAccountInfoResponse>, ()>;
+        type OpResult = AccountServerReqResult<(), ()>;
+
+        // requests without a valid key are rejected
+        let res = http
+            .get(url(&format!("/account/{account_id}")))
+            .send()
+            .await?;
+        assert_eq!(res.status(), reqwest::StatusCode::UNAUTHORIZED);
+        // the admin api has a request log too
+        assert!(res.headers().contains_key(REQUEST_ID_HEADER));
+        let res = http
+            .get(url(&format!("/account/{account_id}")))
+            .bearer_auth("wrong-key")
+            .send()
+            .await?;
+        assert_eq!(res.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let info: InfoResult =
+            admin_request(http.get(url("/account/email/test@localhost"))).await?;
+        let info = info.unwrap().unwrap();
+        assert_eq!(info.account_id, account_id);
+        assert_eq!(info.email.as_deref(), Some("test@localhost"));
+        assert!(info.ban.is_none());
+
+        let info: InfoResult = admin_request(http.get(url("/account/steam/1234"))).await?;
+        assert!(info.unwrap().is_none());
+
+        let ban = |ban: AdminBanRequest| {
+            admin_request::<OpResult>(
+                http.post(url(&format!("/account/{account_id}/ban")))
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(serde_json::to_vec(&ban).unwrap()),
+            )
+        };
+
+        // banned accounts can neither sign nor login
+        ban(AdminBanRequest {
+            banned: true,
+            reason: "cheating".into(),
+            expires: None,
+        })
+        .await?
+        .unwrap();
+        let info: InfoResult =
+            admin_request(http.get(url(&format!("/account/{account_id}")))).await?;
+        let info_ban = info.unwrap().unwrap().ban.unwrap();
+        assert_eq!(info_ban.reason, "cheating");
+        assert_eq!(info_ban.issuer, "test");
+
+        assert!(matches!(
+            sign(shared.clone(), pool.clone(), sign_req()).await,
+            Err(AccountServerRequestError::LogicError(SignError::Banned(AccountBan {
+                ref reason,
+                expires: None,
+            }))) if reason == "cheating"
+        ));
+        let (login_req, _) = ddnet_accounts_shared::client::login::login(add_token().await?)?;
+        assert!(matches!(
+            login(
+                shared.clone(),
+                pool.clone(),
+                Ipv4Addr::LOCALHOST.into(),
+                login_req
+            )
+            .await,
+            Err(AccountServerRequestError::LogicError(LoginError::Banned(_)))
+        ));
+
+        // expired bans are ignored
+        ban(AdminBanRequest {
+            banned: true,
+            reason: "cheating".into(),
+            expires: Some(chrono::Utc::now() - chrono::TimeDelta::hours(1)),
+        })
+        .await?
+        .unwrap();
+        sign(shared.clone(), pool.clone(), sign_req()).await?;
+
+        // unbanning
+        ban(AdminBanRequest {
+            banned: true,
+            reason: "cheating".into(),
+            expires: Some(chrono::Utc::now() + chrono::TimeDelta::hours(1)),
+        })
+        .await?
+        .unwrap();
+        assert!(sign(shared.clone(), pool.clone(), sign_req())
+            .await
+            .is_err());
+        ban(AdminBanRequest {
+            banned: false,
+            reason: Default::default(),
+            expires: None,
+        })
+        .await?
+        .unwrap();
+        sign(shared.clone(), pool.clone(), sign_req()).await?;
+
+        // deleting also removes the ban
+        ban(AdminBanRequest {
+            banned: true,
+            reason: Default::default(),
+            expires: None,
+        })
+        .await?
+        .unwrap();
+
+        let res: OpResult =
+            admin_request(http.post(url(&format!("/account/{account_id}/logout-all")))).await?;
+        res.unwrap();
+
+        let res: OpResult =
+            admin_request(http.post(url(&format!("/account/{account_id}/unlink/email")))).await?;
+        res.unwrap();
+
+        let res: OpResult =
+            admin_request(http.post(url(&format!("/account/{account_id}/delete")))).await?;
+        res.unwrap();
+        let info: InfoResult =
+            admin_request(http.get(url(&format!("/account/{account_id}")))).await?;
+        assert!(info.unwrap().is_none());
+
+        // banned accounts can't be deleted by their owner,
+        // otherwise they could escape the ban with a new account
+        let (login_req, _) = ddnet_accounts_shared::client::login::login(add_token().await?)?;
+        let account_id = login(
+            shared.clone(),
+            pool.clone(),
+            Ipv4Addr::LOCALHOST.into(),
+            login_req,
+        )
+        .await?;
+        let res: OpResult = admin_request(
+            http.post(url(&format!("/account/{account_id}/ban")))
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(serde_json::to_vec(&AdminBanRequest {
+                    banned: true,
+                    reason: "cheating".into(),
+                    expires: None,
+                })?),
+        )
+        .await?;
+        res.unwrap();
+        let add_account_token = |op: AccountTokenOperation| {
+            let shared = shared.clone();
+            let pool = pool.clone();
+            async move {
+                let token = generate_otp();
+                let mut connection = pool.acquire().await?;
+                let mut con = connection.acquire().await?;
+                AddAccountTokenEmail {
+                    token: &token,
+                    email: &"test@localhost".parse()?,
+                    ty: &op,
+                }
+                .query(&shared.db.account_token_email_statement)
+                .execute(&mut con)
+                .await?;
+                anyhow::Ok(token)
+            }
+        };
+        // tokens of other operations are rejected
+        let res = delete(
+            shared.clone(),
+            pool.clone(),
+            Ipv4Addr::LOCALHOST.into(),
+            DeleteRequest {
+                account_token: add_account_token(AccountTokenOperation::LogoutAll).await?,
+            },
+        )
+        .await
+        .map_err(|err| request_logic_error::<DeleteError>("delete", err));
+        assert!(matches!(
+            res,
+            Err(AccountServerRequestError::Failure(
+                RequestFailure::WrongTokenOp
+            ))
+        ));
+        let res = delete(
+            shared.clone(),
+            pool.clone(),
+            Ipv4Addr::LOCALHOST.into(),
+            DeleteRequest {
+                account_token: add_account_token(AccountTokenOperation::Delete).await?,
+            },
+        )
+        .await
+        .map_err(|err| request_logic_error::<DeleteError>("delete", err));
+        assert!(matches!(
+            res,
+            Err(AccountServerRequestError::LogicError(DeleteError::Banned(
+                AccountBan { ref reason, .. }
+            ))) if reason == "cheating"
+        ));
+        let info: InfoResult =
+            admin_request(http.get(url(&format!("/account/{account_id}")))).await?;
+        assert!(info.unwrap().unwrap().ban.is_some());
+
+        // expired bans don't prevent the deletion
+        let res: OpResult = admin_request(
+            http.post(url(&format!("/account/{account_id}/ban")))
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(serde_json::to_vec(&AdminBanRequest {
+                    banned: true,
+                    reason: "cheating".into(),
+                    expires: Some(chrono::Utc::now() - chrono::TimeDelta::hours(1)),
+                })?),
+        )
+        .await?;
+        res.unwrap();
+        delete(
+            shared.clone(),
+            pool.clone(),
+            Ipv4Addr::LOCALHOST.into(),
+            DeleteRequest {
+                account_token: add_account_token(AccountTokenOperation::Delete).await?,
+            },
+        )
+        .await?;
+        let info: InfoResult =
+            admin_request(http.get(url(&format!("/account/{account_id}")))).await?;
+        assert!(info.unwrap().is_none());
+
+        server.abort();
+
+        test_server.destroy().await
+    };
+    test.await.unwrap();
+}
@@ -0,0 +1,145 @@
+use std::{net::IpAddr, num::NonZeroU32, time::Duration};
+
+use ddnet_accounts_shared::account_server::{
+    errors::AccountServerRequestError, result::AccountServerReqResult,
+};
+use iprange::IpRange;
+use reqwest::header::HeaderMap;
+
+use crate::{
+    client_ip::ClientIpResolver,
+    ip_limit::IpDenyList,
+    tests::types::{SqliteTestOptions, SqliteTestServer},
+    HttpServerDetails, LimiterSettings, LimiterValues,
+};
+
+#[test]
+fn client_ip_resolver() {
+    let resolver = ClientIpResolver::new(&["10.0.0.0/8".parse().unwrap()]);
+    let ip = |ip: &str| ip.parse::<IpAddr>().unwrap();
+    let headers = |forwarded_for: &str| {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", forwarded_for.parse().unwrap());
+        headers
+    };
+
+    // untrusted peers can't spoof their ip
+    assert_eq!(
+        resolver.resolve(ip("1.1.1.1"), &headers("2.2.2.2")),
+        ip("1.1.1.1")
+    );
+    // the last untrusted entry is the client
+    assert_eq!(
+        resolver.resolve(ip("10.0.0.1"), &headers("3.3.3.3, 2.2.2.2, 10.0.0.2")),
+        ip("2.2.2.2")
+    );
+    assert_eq!(
+        resolver.resolve(ip("10.0.0.1"), &headers("[2001:db8::1]:1234")),
+        ip("2001:db8::1")
+    );
+    assert_eq!(
+        resolver.resolve(ip("::ffff:10.0.0.1"), &headers("2.2.2.2")),
+        ip("2.2.2.2")
+    );
+    // without header the proxy itself is the client
+    assert_eq!(
+        resolver.resolve(ip("10.0.0.1"), &HeaderMap::new()),
+        ip("10.0.0.1")
+    );
+}
+
+/// Tests that a server that only listens on localhost trusts
+/// the reverse proxy on the same machine by default
+#[test]
+fn trusted_proxies_default() {
+    let localhost = vec!["127.0.0.1/32".parse().unwrap(), "::1/128".parse().unwrap()];
+    assert_eq!(HttpServerDetails::default().trusted_proxies(), localhost);
+    let details = HttpServerDetails {
+        bind: vec!["::1".parse().unwrap()],
+        ..Default::default()
+    };
+    assert_eq!(details.trusted_proxies(), localhost);
+
+    let details = HttpServerDetails {
+        bind: vec!["::".parse().unwrap()],
+        ..Default::default()
+    };
+    assert!(details.trusted_proxies().is_empty());
+    let details = HttpServerDetails {
+        trusted_proxies: Some(Vec::new()),
+        ..Default::default()
+    };
+    assert!(details.trusted_proxies().is_empty());
+}
+
+/// Tests that the deny list & the rate limiters see the forwarded client ip
+#[tokio::test]
+async fn client_ip_forwarded() {
+    let test = async move {
+        let mut server = SqliteTestServer::new(
+            "test-account-server-client-ip.sqlite",
+            SqliteTestOptions {
+                http: HttpServerDetails {
+                    trusted_proxies: Some(vec!["127.0.0.1/32".parse()?]),
+                    ..Default::default()
+                },
+                limits: LimiterSettings {
+                    login: LimiterValues {
+                        time_until_another_attempt: Duration::from_secs(60 * 60),
+                        initial_request_count: NonZeroU32::new(1).unwrap(),
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .await?;
+        *server.shared.ip_ban_list.write() = IpDenyList {
+            ipv4: {
+                let mut ip = IpRange::new();
+                ip.add("192.0.2.1/32".parse()?);
+                ip
+            },
+            ipv6: IpRange::new(),
+        };
+        server.spawn(false);
+
+        let http = reqwest::Client::new();
+        let request = |path: &str, forwarded_for: &str| {
+            http.post(server.url(path))
+                .header("x-forwarded-for", forwarded_for)
+                .send()
+        };
+        let is_err = |res: &[u8], check: fn(&AccountServerRequestError<()>) -> bool| {
+            serde_json::from_slice::<AccountServerReqResult<(), ()>>(res)
+                .is_ok_and(|res| res.is_err_and(|err| check(&err)))
+        };
+
+        // the banned client is behind the trusted proxy
+        let res = request("/login", "192.0.2.1").await?.bytes().await?;
+        assert!(is_err(&res, |err| matches!(
+            err,
+            AccountServerRequestError::VpnBan(_)
+        )));
+
+        // every client has its own limit
+        let res = request("/login", "192.0.2.2").await?.bytes().await?;
+        assert!(!is_err(&res, |err| matches!(
+            err,
+            AccountServerRequestError::RateLimited(_)
+        )));
+        let res = request("/login", "192.0.2.2").await?.bytes().await?;
+        assert!(is_err(&res, |err| matches!(
+            err,
+            AccountServerRequestError::RateLimited(_)
+        )));
+        let res = request("/login", "192.0.2.3").await?.bytes().await?;
+        assert!(!is_err(&res, |err| matches!(
+            err,
+            AccountServerRequestError::RateLimited(_)
+        )));
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
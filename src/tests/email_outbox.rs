@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use ddnet_account_sql::query::Query;
+use lettre::{AsyncSmtpTransport, Tokio1Executor};
+
+use crate::{
+    email::transport::SmtpEmailTransport,
+    email_outbox::{
+        queries::{ClaimEmail, PendingEmails, RetryEmail},
+        queue_email, retry_delay, send_pending_emails,
+    },
+    email_templates::RenderedEmail,
+    tests::types::{SqliteTestOptions, SqliteTestServer},
+};
+
+/// Tests that failed emails stay queued & are retried later
+#[tokio::test]
+async fn email_outbox() {
+    let test = async move {
+        assert_eq!(retry_delay(1), Duration::from_secs(60));
+        assert_eq!(retry_delay(2), Duration::from_secs(120));
+        assert_eq!(retry_delay(100), Duration::from_secs(4 * 60 * 60));
+
+        let server = SqliteTestServer::new(
+            "test-account-server-email-outbox.sqlite",
+            SqliteTestOptions {
+                // nothing listens on this port, so sending always fails
+                email: (
+                    "test@localhost",
+                    SmtpEmailTransport(
+                        AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous("127.0.0.1")
+                            .port(1)
+                            .build(),
+                    ),
+                )
+                    .into(),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let (pool, shared) = (server.pool.clone(), server.shared.clone());
+
+        let pending = || {
+            let shared = shared.clone();
+            let pool = pool.clone();
+            async move {
+                let mut connection = pool.acquire().await?;
+                let mut connection = connection.acquire().await?;
+                PendingEmails {}
+                    .query(&shared.db.pending_emails_statement)
+                    .fetch_all(&mut connection)
+                    .await?
+                    .iter()
+                    .map(PendingEmails::row_data)
+                    .collect::<anyhow::Result<Vec<_>>>()
+            }
+        };
+
+        {
+            let mut connection = pool.acquire().await?;
+            let mut connection = connection.acquire().await?;
+            queue_email(
+                &shared,
+                &mut connection,
+                "test@localhost",
+                &RenderedEmail {
+                    subject: "It works".to_string(),
+                    html: "<p>It indeed works</p>".to_string(),
+                    text: "It indeed works".to_string(),
+                },
+            )
+            .await?;
+        }
+        let emails = pending().await?;
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].attempts, 0);
+        assert_eq!(emails[0].recipient, "test@localhost");
+
+        // the failed email is delayed, but not lost
+        send_pending_emails(&pool, &shared).await?;
+        assert!(pending().await?.is_empty());
+        {
+            let mut connection = pool.acquire().await?;
+            let mut connection = connection.acquire().await?;
+            RetryEmail {
+                id: emails[0].id,
+                delay_secs: -60,
+            }
+            .query(&shared.db.retry_email_statement)
+            .execute(&mut connection)
+            .await?;
+        }
+        let emails = pending().await?;
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].attempts, 2);
+
+        // only one account server can claim an email for sending
+        let claim = || {
+            let shared = shared.clone();
+            let pool = pool.clone();
+            let id = emails[0].id;
+            async move {
+                let mut connection = pool.acquire().await?;
+                let mut connection = connection.acquire().await?;
+                let res = ClaimEmail { id, lease_secs: 60 }
+                    .query(&shared.db.claim_email_statement)
+                    .execute(&mut connection)
+                    .await?;
+                anyhow::Ok(res.rows_affected() == 1)
+            }
+        };
+        assert!(claim().await?);
+        assert!(!claim().await?);
+        assert!(pending().await?.is_empty());
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
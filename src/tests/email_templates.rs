@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use ddnet_accounts_shared::client::{
+    account_token::AccountTokenOperation, credential_auth_token::CredentialAuthTokenOperation,
+};
+
+use crate::email_templates::{EmailKind, EmailTemplates};
+
+async fn write(dir: &Path, file: &str, content: &str) -> anyhow::Result<()> {
+    let path = dir.join(file);
+    tokio::fs::create_dir_all(path.parent().unwrap()).await?;
+    tokio::fs::write(path, content).await?;
+    Ok(())
+}
+
+/// Tests the per operation templates & the locale fallbacks
+#[tokio::test]
+async fn email_templates() {
+    let test = async move {
+        let dir = tempfile::tempdir()?;
+        let templates = dir.path().join(EmailTemplates::DIR);
+        write(
+            &templates,
+            "credential_auth_tokens/login.subject",
+            "Login\n",
+        )
+        .await?;
+        write(
+            &templates,
+            "de/credential_auth_tokens/login.subject",
+            "Anmeldung",
+        )
+        .await?;
+        write(
+            &templates,
+            "de/credential_auth_tokens/login.html",
+            "<p>Hallo %SUBJECT%,</p><pre>%CODE%</pre>",
+        )
+        .await?;
+        write(dir.path(), "account_tokens.html", "<p>Legacy %CODE%</p>").await?;
+
+        let templates = EmailTemplates::load(dir.path()).await;
+        let login = EmailKind::CredentialAuthToken(CredentialAuthTokenOperation::Login);
+        let vars = [("%SUBJECT%", "test"), ("%CODE%", "c0de")];
+
+        let mail = templates.render(login, None, &vars);
+        assert_eq!(mail.subject, "Login");
+        assert!(mail.html.contains("<pre>c0de</pre>"));
+        assert!(mail.text.contains("c0de") && !mail.text.contains('<'));
+
+        // the region falls back to the language, missing parts to the default language
+        let mail = templates.render(login, Some("de_AT"), &vars);
+        assert_eq!(mail.subject, "Anmeldung");
+        assert_eq!(mail.html, "<p>Hallo test,</p><pre>c0de</pre>");
+        assert!(mail.text.starts_with("Hello test,"));
+
+        let mail = templates.render(login, Some("fr"), &vars);
+        assert_eq!(mail.subject, "Login");
+
+        // other operations keep their own subject
+        let mail = templates.render(
+            EmailKind::CredentialAuthToken(CredentialAuthTokenOperation::LinkCredential),
+            Some("de"),
+            &vars,
+        );
+        assert_eq!(mail.subject, "DDNet Link E-mail To Account");
+
+        // templates of older versions are still used
+        let mail = templates.render(
+            EmailKind::AccountToken(AccountTokenOperation::Delete),
+            None,
+            &vars,
+        );
+        assert_eq!(mail.subject, "DDNet Delete Account");
+        assert_eq!(mail.html, "<p>Legacy c0de</p>");
+
+        anyhow::Ok(())
+    };
+    test.await.unwrap();
+}
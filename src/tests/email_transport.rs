@@ -0,0 +1,53 @@
+use crate::{email_templates::RenderedEmail, prepare_email, EmailDetails, EmailTransportDetails};
+
+/// Tests the selection of the email transport by the settings
+#[tokio::test]
+async fn email_transport() {
+    let test = async move {
+        let details = |json: &str| serde_json::from_str::<EmailDetails>(json);
+
+        // the settings of older versions stay valid
+        let smtp = details(
+            r#"{"relay": "emails.localhost", "relay_port": 465, "username": "account",
+                "password": "email-password", "email_from": "account@localhost"}"#,
+        )?;
+        assert!(matches!(smtp.transport, EmailTransportDetails::Smtp(_)));
+        let sendmail = details(
+            r#"{"sendmail_command": "/usr/sbin/sendmail", "email_from": "account@localhost"}"#,
+        )?;
+        assert!(matches!(
+            sendmail.transport,
+            EmailTransportDetails::Sendmail(_)
+        ));
+        let memory = details(r#"{"in_memory": true, "email_from": "account@localhost"}"#)?;
+        assert!(matches!(memory.transport, EmailTransportDetails::Memory(_)));
+        prepare_email(&memory).await?;
+
+        let dir = tempfile::tempdir()?;
+        let mail_dir = dir.path().join("mails");
+        let file = details(&format!(
+            r#"{{"mail_dir": {}, "email_from": "account@localhost"}}"#,
+            serde_json::to_string(&mail_dir)?
+        ))?;
+        assert!(matches!(file.transport, EmailTransportDetails::File(_)));
+        let email = prepare_email(&file).await?;
+        email
+            .send_email(
+                "test@localhost",
+                RenderedEmail {
+                    subject: "It works".to_string(),
+                    html: "<p>It indeed works</p>".to_string(),
+                    text: "It indeed works".to_string(),
+                },
+            )
+            .await?;
+        let mut entries = tokio::fs::read_dir(&mail_dir).await?;
+        let entry = entries.next_entry().await?.unwrap();
+        assert!(entry.file_name().to_string_lossy().ends_with(".eml"));
+        let content = tokio::fs::read_to_string(entry.path()).await?;
+        assert!(content.contains("Subject: It works"));
+
+        anyhow::Ok(())
+    };
+    test.await.unwrap();
+}
@@ -0,0 +1,71 @@
+use ddnet_accounts_shared::account_server::health::{HealthReport, HealthStatus};
+
+use crate::{
+    steam::{SteamParams, SteamShared},
+    tests::types::{SqliteTestOptions, SqliteTestServer},
+};
+
+/// Tests that an unreachable dependency marks the server as not ready,
+/// while it stays alive
+#[tokio::test]
+async fn health() {
+    let test = async move {
+        let mut server = SqliteTestServer::new(
+            "test-account-server-health.sqlite",
+            SqliteTestOptions {
+                // nothing listens on this port
+                steam: SteamShared::new(SteamParams::new(
+                    "http://127.0.0.1:1".try_into()?,
+                    "my_secret_pub_auth_key",
+                    Some("account"),
+                    123,
+                ))?,
+                ..Default::default()
+            },
+        )
+        .await?;
+        server.spawn(false);
+
+        let http = reqwest::Client::new();
+        let url = |path: &str| server.url(path);
+
+        let live = http.get(url("/health/live")).send().await?;
+        assert_eq!(live.status(), reqwest::StatusCode::OK);
+
+        // concurrent probes share the same checks
+        let (ready, other_ready) = tokio::join!(
+            http.get(url("/health/ready")).send(),
+            http.get(url("/health/ready")).send()
+        );
+        let (ready, other_ready) = (ready?, other_ready?);
+        assert_eq!(ready.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        let report: HealthReport = serde_json::from_slice(&ready.bytes().await?)?;
+        let other_report: HealthReport = serde_json::from_slice(&other_ready.bytes().await?)?;
+        assert_eq!(
+            serde_json::to_value(&report)?,
+            serde_json::to_value(&other_report)?
+        );
+        assert_eq!(report.status, HealthStatus::Degraded);
+        let status = |name: &str| {
+            report
+                .checks
+                .iter()
+                .find(|check| check.name == name)
+                .map(|check| check.status)
+        };
+        assert_eq!(status("database"), Some(HealthStatus::Ok));
+        assert_eq!(status("cert"), Some(HealthStatus::Ok));
+        assert_eq!(status("email"), Some(HealthStatus::Ok));
+        assert_eq!(status("steam"), Some(HealthStatus::Degraded));
+        // the actual error is only logged
+        let steam = report
+            .checks
+            .iter()
+            .find(|check| check.name == "steam")
+            .unwrap();
+        assert_eq!(steam.message.as_deref(), Some("Steam is not reachable."));
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
@@ -0,0 +1,50 @@
+use ddnet_account_client_http_fs::{
+    fs::Fs,
+    key_store::{EncryptedFileKeyStore, KeyStore, KeyStoreSecret, PlainKeyStore},
+};
+
+/// Tests that the encrypted key store does not write the keys in plain,
+/// only decrypts them with the right secret & migrates plain keys on request
+#[tokio::test]
+async fn key_store() {
+    let test = async move {
+        let secure_dir = tempfile::tempdir()?;
+        let fs = Fs::new(secure_dir.path().into()).await?;
+        let key = b"my secret session key".to_vec();
+
+        let store = EncryptedFileKeyStore::new(KeyStoreSecret::Passphrase("correct".into()));
+        store.write(&fs, "account.key", key.clone()).await?;
+        let file = tokio::fs::read(secure_dir.path().join("account.key")).await?;
+        assert!(!file.windows(key.len()).any(|part| part == key));
+        assert_eq!(store.read(&fs, "account.key").await?, key);
+
+        let wrong_store = EncryptedFileKeyStore::new(KeyStoreSecret::Passphrase("wrong".into()));
+        assert!(wrong_store.read(&fs, "account.key").await.is_err());
+
+        // a key file renamed by an attacker must not be accepted
+        tokio::fs::copy(
+            secure_dir.path().join("account.key"),
+            secure_dir.path().join("other.key"),
+        )
+        .await?;
+        assert!(store.read(&fs, "other.key").await.is_err());
+
+        // keys of older clients are only accepted by the migration
+        PlainKeyStore.write(&fs, "legacy.key", key.clone()).await?;
+        assert!(store.read(&fs, "legacy.key").await.is_err());
+        assert!(store.migrate_plain(&fs, "legacy.key").await?);
+        let file = tokio::fs::read(secure_dir.path().join("legacy.key")).await?;
+        assert_ne!(file, key);
+        assert_eq!(store.read(&fs, "legacy.key").await?, key);
+        // encrypted & missing keys are not migrated
+        assert!(!store.migrate_plain(&fs, "legacy.key").await?);
+        assert!(!store.migrate_plain(&fs, "missing.key").await?);
+        assert_eq!(store.read(&fs, "legacy.key").await?, key);
+
+        store.remove(&fs, "account.key").await?;
+        assert!(store.read(&fs, "account.key").await.is_err());
+
+        anyhow::Ok(())
+    };
+    test.await.unwrap();
+}
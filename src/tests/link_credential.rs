@@ -7,6 +7,7 @@ use ddnet_account_client::{
 };
 use ddnet_account_client_reqwest::client::ClientReqwestTokioFs;
 use ddnet_accounts_shared::{
+    account_server::{errors::AccountServerRequestError, link_credential::LinkCredentialError},
     client::{
         account_token::AccountTokenOperation, credential_auth_token::CredentialAuthTokenOperation,
     },
@@ -143,7 +144,9 @@ async fn link_credential_hardening() {
         .await;
         assert!(matches!(
             res,
-            Err(LinkCredentialResult::AccountServerRequstError(_))
+            Err(LinkCredentialResult::AccountServerRequstError(
+                AccountServerRequestError::LogicError(LinkCredentialError::AccountTokenInvalid)
+            ))
         ));
 
         // login with new email
@@ -254,7 +257,9 @@ async fn link_credential_hardening() {
         .await;
         assert!(matches!(
             res,
-            Err(LinkCredentialResult::AccountServerRequstError(_))
+            Err(LinkCredentialResult::AccountServerRequstError(
+                AccountServerRequestError::LogicError(LinkCredentialError::CredentialAlreadyLinked)
+            ))
         ));
 
         // try to link the original email against the steam account
@@ -282,7 +287,9 @@ async fn link_credential_hardening() {
         .await;
         assert!(matches!(
             res,
-            Err(LinkCredentialResult::AccountServerRequstError(_))
+            Err(LinkCredentialResult::AccountServerRequstError(
+                AccountServerRequestError::LogicError(LinkCredentialError::CredentialAlreadyLinked)
+            ))
         ));
 
         acc_server.destroy().await?;
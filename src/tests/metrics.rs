@@ -0,0 +1,34 @@
+use crate::{metrics::prepare_metrics_router, tests::types::SqliteTestServer, LimiterSettings};
+
+/// Tests the `/metrics` endpoint on top of a sqlite database
+#[tokio::test]
+async fn metrics() {
+    let test = async move {
+        let mut server =
+            SqliteTestServer::new("test-account-server-metrics.sqlite", Default::default()).await?;
+        server.app = std::mem::take(&mut server.app).merge(prepare_metrics_router(&server.shared));
+        server.spawn(false);
+
+        let http = reqwest::Client::new();
+        let url = |path: &str| server.url(path);
+
+        http.get(url("/ping")).send().await?.error_for_status()?;
+        // more login attempts than the limiter allows
+        let login_limit = LimiterSettings::default().login.initial_request_count.get();
+        for _ in 0..=login_limit {
+            http.post(url("/login")).send().await?;
+        }
+
+        let res = http.get(url("/metrics")).send().await?.error_for_status()?;
+        let metrics = res.text().await?;
+        assert!(
+            metrics.contains(r#"account_server_http_requests_total{route="/ping",status="200"} 1"#)
+        );
+        assert!(metrics.contains(r#"account_server_rate_limited_total{route="/login"} 1"#));
+        assert!(metrics.contains("account_server_http_request_duration_seconds_count"));
+        assert!(metrics.contains("account_server_signing_key_age_seconds"));
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
@@ -1,10 +1,27 @@
+pub mod admin;
+pub mod client_ip;
 pub mod credential_auth_token;
+pub mod email_outbox;
+pub mod email_templates;
+pub mod email_transport;
 pub mod full;
 pub mod game_server;
+pub mod health;
 pub mod ip_ban;
+pub mod key_store;
 pub mod link_credential;
 pub mod login;
+pub mod metrics;
 pub mod multi_url;
+pub mod notifications;
+pub mod profiles_export;
+pub mod request_log;
+pub mod secret_verifier;
+pub mod settings;
+pub mod shutdown;
 pub mod signing_certs;
+pub mod sqlite;
+pub mod tls;
+pub mod token_limit;
 pub mod types;
 pub mod unlink_credential;
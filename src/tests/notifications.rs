@@ -0,0 +1,180 @@
+use std::{net::IpAddr, sync::Arc};
+
+use ddnet_account_sql::query::Query;
+use ddnet_accounts_shared::{
+    account_server::otp::{generate_otp, Otp},
+    client::{
+        account_token::AccountTokenOperation, credential_auth_token::CredentialAuthTokenOperation,
+        link_credential::LinkCredentialRequest, logout_all::LogoutAllRequest,
+        unlink_credential::UnlinkCredentialRequest,
+    },
+};
+use parking_lot::Mutex;
+
+use crate::{
+    account_token::queries::AddAccountTokenEmail,
+    credential_auth_token::queries::AddCredentialAuthToken,
+    email::{AccountNotification, EmailHook},
+    email_templates::{EmailKind, EmailTemplates},
+    link_credential::link_credential,
+    login::login,
+    logout_all::logout_all,
+    tests::types::{SqliteTestOptions, SqliteTestServer},
+    types::TokenType,
+    unlink_credential::unlink_credential,
+};
+
+#[derive(Debug)]
+struct EmailReader {
+    mails: Arc<Mutex<Vec<(String, String)>>>,
+}
+impl EmailHook for EmailReader {
+    fn on_mail(&self, email_subject: &str, email_body: &str) {
+        self.mails
+            .lock()
+            .push((email_subject.to_string(), email_body.to_string()));
+    }
+}
+
+/// Tests the notification emails on sensitive account changes
+#[tokio::test]
+async fn notifications() {
+    let test = async move {
+        let mails: Arc<Mutex<Vec<(String, String)>>> = Default::default();
+        let mut options = SqliteTestOptions::default();
+        options.email.set_hook(EmailReader {
+            mails: mails.clone(),
+        });
+        let server =
+            SqliteTestServer::new("test-account-server-notifications.sqlite", options).await?;
+        let (pool, shared) = (server.pool.clone(), server.shared.clone());
+
+        let add_credential_auth_token =
+            |ty: TokenType, identifier: &'static str, op: CredentialAuthTokenOperation| {
+                let shared = shared.clone();
+                let pool = pool.clone();
+                async move {
+                    let token = generate_otp();
+                    let mut connection = pool.acquire().await?;
+                    let mut con = connection.acquire().await?;
+                    AddCredentialAuthToken {
+                        token: &token,
+                        ty: &ty,
+                        identifier,
+                        op: &op,
+                    }
+                    .query(&shared.db.credential_auth_token_statement)
+                    .execute(&mut con)
+                    .await?;
+                    anyhow::Ok(token)
+                }
+            };
+        let add_account_token = |op: AccountTokenOperation| {
+            let shared = shared.clone();
+            let pool = pool.clone();
+            async move {
+                let token: Otp = generate_otp();
+                let mut connection = pool.acquire().await?;
+                let mut con = connection.acquire().await?;
+                AddAccountTokenEmail {
+                    token: &token,
+                    email: &"test@localhost".parse()?,
+                    ty: &op,
+                }
+                .query(&shared.db.account_token_email_statement)
+                .execute(&mut con)
+                .await?;
+                anyhow::Ok(token)
+            }
+        };
+        let last_mail = || mails.lock().last().cloned().unwrap();
+
+        let client_ip: IpAddr = "192.0.2.1".parse()?;
+
+        // an account with an email
+        let (login_req, _) = ddnet_accounts_shared::client::login::login(hex::encode(
+            add_credential_auth_token(
+                TokenType::Email,
+                "test@localhost",
+                CredentialAuthTokenOperation::Login,
+            )
+            .await?,
+        ))?;
+        login(shared.clone(), pool.clone(), client_ip, login_req).await?;
+        assert!(mails.lock().is_empty());
+
+        // linking steam notifies the linked email
+        link_credential(
+            shared.clone(),
+            pool.clone(),
+            client_ip,
+            LinkCredentialRequest {
+                account_token: add_account_token(AccountTokenOperation::LinkCredential).await?,
+                credential_auth_token: add_credential_auth_token(
+                    TokenType::Steam,
+                    "1",
+                    CredentialAuthTokenOperation::LinkCredential,
+                )
+                .await?,
+            },
+        )
+        .await?;
+        let (subject, body) = last_mail();
+        assert_eq!(
+            subject,
+            EmailKind::Notification(AccountNotification::CredentialLinked).default_subject()
+        );
+        assert!(body.contains("steam") && body.contains("192.0.2.1"));
+
+        unlink_credential(
+            shared.clone(),
+            pool.clone(),
+            client_ip,
+            UnlinkCredentialRequest {
+                credential_auth_token: add_credential_auth_token(
+                    TokenType::Steam,
+                    "1",
+                    CredentialAuthTokenOperation::UnlinkCredential,
+                )
+                .await?,
+            },
+        )
+        .await?;
+        let (subject, _) = last_mail();
+        assert_eq!(
+            subject,
+            EmailKind::Notification(AccountNotification::CredentialUnlinked).default_subject()
+        );
+
+        // templates are replaceable
+        let dir = tempfile::tempdir()?;
+        let notifications_dir = dir.path().join(EmailTemplates::DIR).join("notifications");
+        tokio::fs::create_dir_all(&notifications_dir).await?;
+        tokio::fs::write(
+            notifications_dir.join("logout_all.html"),
+            "Logged out by %CLIENT_IP%",
+        )
+        .await?;
+        *shared.email.templates.write() = Arc::new(EmailTemplates::load(dir.path()).await);
+        logout_all(
+            shared.clone(),
+            pool.clone(),
+            client_ip,
+            LogoutAllRequest {
+                account_token: add_account_token(AccountTokenOperation::LogoutAll).await?,
+                ignore_session: None,
+            },
+        )
+        .await?;
+        let (subject, body) = last_mail();
+        assert_eq!(
+            subject,
+            EmailKind::Notification(AccountNotification::LogoutAll).default_subject()
+        );
+        assert_eq!(body, "Logged out by 192.0.2.1");
+        assert_eq!(mails.lock().len(), 3);
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
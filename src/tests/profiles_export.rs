@@ -0,0 +1,169 @@
+use std::{fmt::Debug, future::Future, ops::Deref, path::PathBuf, pin::Pin, sync::Arc};
+
+use ddnet_account_client_http_fs::{
+    client::ClientHttpTokioFs,
+    key_store::{EncryptedFileKeyStore, KeyStore, KeyStoreSecret},
+    profiles::{Profiles, ProfilesLoading},
+    profiles_export::ImportLogin,
+};
+use ddnet_account_client_reqwest::client::ClientReqwestTokioFs;
+use ddnet_account_sql::query::Query;
+use ddnet_accounts_shared::{
+    account_server::{account_info::CredentialType, otp::generate_otp},
+    client::credential_auth_token::CredentialAuthTokenOperation,
+};
+
+use crate::{
+    credential_auth_token::queries::AddCredentialAuthToken, tests::types::SqliteTestServer,
+    types::TokenType,
+};
+
+type FactoryResult = Pin<Box<dyn Future<Output = anyhow::Result<ClientHttpTokioFs>> + Sync + Send>>;
+
+struct Factory(Box<dyn Fn(PathBuf, Arc<dyn KeyStore>) -> FactoryResult + Sync + Send>);
+
+impl Debug for Factory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Factory").finish()
+    }
+}
+
+impl Deref for Factory {
+    type Target = dyn Fn(PathBuf, Arc<dyn KeyStore>) -> FactoryResult;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+/// Tests that exported profiles can be imported on another machine
+/// & keep their data after logging in again
+#[tokio::test]
+async fn profiles_export() {
+    let test = async move {
+        let mut server = SqliteTestServer::new(
+            "test-account-server-profiles-export.sqlite",
+            Default::default(),
+        )
+        .await?;
+        let (pool, shared) = (server.pool.clone(), server.shared.clone());
+        server.spawn(false);
+
+        let add_token = || {
+            let shared = shared.clone();
+            let pool = pool.clone();
+            async move {
+                let token = generate_otp();
+                let mut connection = pool.acquire().await?;
+                let mut con = connection.acquire().await?;
+                AddCredentialAuthToken {
+                    token: &token,
+                    ty: &TokenType::Email,
+                    identifier: "test@localhost",
+                    op: &CredentialAuthTokenOperation::Login,
+                }
+                .query(&shared.db.credential_auth_token_statement)
+                .execute(&mut con)
+                .await?;
+                anyhow::Ok(hex::encode(token))
+            }
+        };
+
+        let url = url::Url::parse(&server.url(""))?;
+        let factory = Arc::new(Factory(Box::new(move |path: PathBuf, key_store| {
+            let url = url.clone();
+            Box::pin(async move {
+                let client =
+                    ClientReqwestTokioFs::new_with_key_store(vec![url], &path, key_store).await?;
+                Arc::into_inner(client.client).ok_or_else(|| anyhow::anyhow!("client is shared"))
+            })
+        })));
+        // the machines use different secrets for their keys
+        let load_profiles = |path: PathBuf, secret: &str| {
+            let factory = factory.clone();
+            let key_store: Arc<dyn KeyStore> = Arc::new(EncryptedFileKeyStore::new(
+                KeyStoreSecret::Passphrase(secret.into()),
+            ));
+            async move {
+                anyhow::Ok(Profiles::new(
+                    ProfilesLoading::new(path, factory, key_store).await?,
+                ))
+            }
+        };
+
+        let old_dir = tempfile::tempdir()?;
+        let old_profiles = load_profiles(old_dir.path().into(), "old machine").await?;
+        let profile_name = old_profiles
+            .login_email("test@localhost".parse()?, add_token().await?)
+            .await?;
+        old_profiles
+            .set_profile_display_name(&profile_name, "my main".into())
+            .await;
+
+        let export = old_profiles.export_profiles("export".into()).await?;
+        assert!(export.failed.is_empty());
+        let archive = export.archive;
+        assert!(!String::from_utf8_lossy(&archive).contains("my main"));
+
+        let new_dir = tempfile::tempdir()?;
+        let new_profiles = load_profiles(new_dir.path().into(), "new machine").await?;
+        assert!(new_profiles
+            .import_profiles(&archive, "wrong".into())
+            .await
+            .is_err());
+        let imported = new_profiles
+            .import_profiles(&archive, "export".into())
+            .await?;
+        let [pending] = imported.as_slice() else {
+            panic!("expected a single pending import");
+        };
+        assert_eq!(pending.profile_name, profile_name);
+        assert!(matches!(
+            pending.profile.credentials.as_slice(),
+            [CredentialType::Email(_)]
+        ));
+        // no session is imported
+        assert!(new_profiles.profiles().0.is_empty());
+
+        // pending imports survive a restart
+        let new_profiles = load_profiles(new_dir.path().into(), "new machine").await?;
+        let pending = new_profiles.imported_profiles();
+        let [pending] = pending.as_slice() else {
+            panic!("expected a single pending import");
+        };
+
+        // the pending import tells which login restores the profile
+        let [ImportLogin::Email { partial_email }] = pending.logins.as_slice() else {
+            panic!("expected an email login");
+        };
+        assert!(partial_email.contains('@'));
+        new_profiles
+            .login_email("test@localhost".parse()?, add_token().await?)
+            .await?;
+        let (profiles, cur_profile) = new_profiles.profiles();
+        assert_eq!(cur_profile, profile_name);
+        assert_eq!(profiles.get(&profile_name).unwrap().name, "my main");
+        assert!(new_profiles.imported_profiles().is_empty());
+
+        shared.shutdown.trigger();
+        server.server.take().unwrap().await??;
+
+        // profiles whose credentials can't be fetched are reported,
+        // but still exported
+        let export = new_profiles.export_profiles("export".into()).await?;
+        assert!(export.failed.contains_key(&profile_name));
+        let other_dir = tempfile::tempdir()?;
+        let other_profiles = load_profiles(other_dir.path().into(), "other machine").await?;
+        let imported = other_profiles
+            .import_profiles(&export.archive, "export".into())
+            .await?;
+        let [pending] = imported.as_slice() else {
+            panic!("expected a single pending import");
+        };
+        assert_eq!(pending.profile.profile_data.name, "my main");
+        assert!(pending.logins.is_empty());
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Json, Router,
+};
+use ddnet_accounts_shared::account_server::{
+    errors::{AccountServerRequestError, Empty, RequestFailure},
+    result::AccountServerReqResult,
+};
+use tower::ServiceExt;
+
+use crate::{
+    request_error::{request_error, UNEXPECTED_ERROR},
+    request_log::{request_log_layer, RequestLog, REQUEST_ID_HEADER},
+};
+
+/// Tests that every response gets a request id
+/// & that the details of unexpected errors are only sent in debug mode
+#[tokio::test]
+async fn request_log() {
+    let test = async move {
+        let app = |debug_errors: bool| {
+            Router::new()
+                .route(
+                    "/fail",
+                    axum::routing::get(|| async {
+                        Json(AccountServerReqResult::<(), Empty>::Err(request_error(
+                            "test",
+                            anyhow!("because").context("failed"),
+                        )))
+                    }),
+                )
+                .route("/ping", axum::routing::get(|| async { Json("pong") }))
+                .layer(axum::middleware::from_fn_with_state(
+                    Arc::new(RequestLog::new(debug_errors)),
+                    request_log_layer,
+                ))
+        };
+        let get = |debug_errors: bool, path: &'static str| async move {
+            let res = app(debug_errors)
+                .oneshot(Request::get(path).body(Body::empty())?)
+                .await?;
+            assert_eq!(res.status(), StatusCode::OK);
+            let request_id = res
+                .headers()
+                .get(REQUEST_ID_HEADER)
+                .map(|id| id.to_str().unwrap().to_string())
+                .unwrap();
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await?;
+            anyhow::Ok((request_id, body))
+        };
+
+        let (id1, body) = get(false, "/ping").await?;
+        assert_eq!(serde_json::from_slice::<String>(&body)?, "pong");
+        let (id2, _) = get(false, "/ping").await?;
+        assert_ne!(id1, id2);
+
+        let (_, body) = get(false, "/fail").await?;
+        let res: AccountServerReqResult<(), Empty> = serde_json::from_slice(&body)?;
+        let Err(AccountServerRequestError::Unexpected {
+            err, bt, incident, ..
+        }) = res
+        else {
+            panic!("expected an unexpected error");
+        };
+        assert_eq!(err, UNEXPECTED_ERROR);
+        assert!(bt.is_empty());
+        assert!(incident.is_some());
+
+        let (_, body) = get(true, "/fail").await?;
+        let res: AccountServerReqResult<(), Empty> = serde_json::from_slice(&body)?;
+        let Err(AccountServerRequestError::Unexpected { err, .. }) = res else {
+            panic!("expected an unexpected error");
+        };
+        assert_eq!(err, "failed: because");
+
+        // routes without request log never send the details
+        let res = Router::new()
+            .route(
+                "/fail",
+                axum::routing::get(|| async {
+                    Json(AccountServerReqResult::<(), Empty>::Err(request_error(
+                        "test",
+                        anyhow!("because").context("failed"),
+                    )))
+                }),
+            )
+            .oneshot(Request::get("/fail").body(Body::empty())?)
+            .await?;
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await?;
+        let res: AccountServerReqResult<(), Empty> = serde_json::from_slice(&body)?;
+        let Err(AccountServerRequestError::Unexpected { err, bt, .. }) = res else {
+            panic!("expected an unexpected error");
+        };
+        assert_eq!(err, UNEXPECTED_ERROR);
+        assert!(bt.is_empty());
+
+        anyhow::Ok(())
+    };
+    test.await.unwrap();
+}
+
+/// Tests that known failures are typed, even if they got a context
+#[test]
+fn request_error_classification() {
+    let res: AccountServerRequestError<Empty> = request_error(
+        "test",
+        anyhow!(RequestFailure::TimestampOutOfWindow).context("signing failed"),
+    );
+    assert!(matches!(
+        res,
+        AccountServerRequestError::Failure(RequestFailure::TimestampOutOfWindow)
+    ));
+
+    let res: AccountServerRequestError<Empty> = request_error("test", anyhow!("db is down"));
+    assert!(matches!(
+        res,
+        AccountServerRequestError::Unexpected {
+            incident: Some(_),
+            ..
+        }
+    ));
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use axum::{extract::Form, routing::get, routing::post, Json, Router};
+use ddnet_accounts_shared::{
+    account_server::{
+        credential_auth_token::CredentialAuthTokenError, errors::AccountServerRequestError,
+    },
+    client::credential_auth_token::{
+        CredentialAuthTokenOperation, CredentialAuthTokenSteamRequest, SecretKey,
+    },
+};
+use serde::Deserialize;
+
+use crate::{
+    credential_auth_token::credential_auth_token_steam,
+    secret_verifier::{HttpSecretVerifier, SecretVerifierDetails, SecretVerifierStub},
+    steam::{HttpResult, SteamParams, SteamShared, SteamUser, TicketAuthResponse},
+    tests::types::{SqliteTestOptions, SqliteTestServer},
+};
+
+const VALID_KEY: SecretKey = [1; 32];
+
+#[derive(Debug, Deserialize)]
+struct SiteVerifyForm {
+    secret: String,
+    response: String,
+}
+
+/// Tests the secret key verification of the `*-secret` routes
+#[tokio::test]
+async fn secret_verifier() {
+    let test = async move {
+        // fake steam & siteverify apis
+        let app = Router::new()
+            .route(
+                "/steam",
+                get(|| async {
+                    Json(HttpResult {
+                        response: TicketAuthResponse {
+                            params: SteamUser {
+                                result: "Ok".to_string(),
+                                steam_id: "1".to_string(),
+                                owner_steam_id: "1".to_string(),
+                                vac_banned: false,
+                                publisher_banned: false,
+                            },
+                        },
+                    })
+                }),
+            )
+            .route(
+                "/siteverify",
+                post(|Form(form): Form<SiteVerifyForm>| async move {
+                    let success =
+                        form.secret == "verify-secret" && form.response == hex::encode(VALID_KEY);
+                    Json(serde_json::json!({
+                        "success": success,
+                        "error-codes": if success { vec![] } else { vec!["invalid-input-response"] },
+                    }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let fake_apis_addr = listener.local_addr()?;
+        let fake_apis = tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let test_server = SqliteTestServer::new(
+            "test-account-server-secret.sqlite",
+            SqliteTestOptions {
+                steam: SteamShared::new(SteamParams::new(
+                    url::Url::parse(&format!("http://{fake_apis_addr}/steam"))?,
+                    "my_secret_pub_auth_key",
+                    Some("account"),
+                    123,
+                ))?,
+                ..Default::default()
+            },
+        )
+        .await?;
+        let (pool, shared) = (test_server.pool.clone(), test_server.shared.clone());
+
+        let token_steam = |requires_secret: bool, secret_key: Option<SecretKey>| {
+            credential_auth_token_steam(
+                shared.clone(),
+                pool.clone(),
+                requires_secret,
+                Json(CredentialAuthTokenSteamRequest {
+                    steam_ticket: vec![],
+                    secret_key,
+                    op: CredentialAuthTokenOperation::Login,
+                }),
+            )
+        };
+
+        // without a configured verifier no secret key is accepted
+        assert!(matches!(
+            token_steam(true, Some(VALID_KEY)).await.0,
+            Err(AccountServerRequestError::Other(_))
+        ));
+        token_steam(false, None).await.0?;
+
+        let challenge_url: url::Url = "https://accounts.localhost/challenge".try_into()?;
+        *shared.secret_verifier.write() = Arc::new(SecretVerifierStub {
+            valid_keys: vec![VALID_KEY],
+            challenge_url: Some(challenge_url.clone()),
+        });
+
+        assert!(matches!(
+            token_steam(false, None).await.0,
+            Err(AccountServerRequestError::LogicError(
+                CredentialAuthTokenError::WebValidationProcessNeeded { ref url }
+            )) if *url == challenge_url
+        ));
+        assert!(matches!(
+            token_steam(true, None).await.0,
+            Err(AccountServerRequestError::Other(_))
+        ));
+        assert!(matches!(
+            token_steam(true, Some([2; 32])).await.0,
+            Err(AccountServerRequestError::Other(_))
+        ));
+        token_steam(true, Some(VALID_KEY)).await.0?;
+
+        // http api
+        *shared.secret_verifier.write() =
+            Arc::new(HttpSecretVerifier::new(SecretVerifierDetails {
+                verify_url: url::Url::parse(&format!("http://{fake_apis_addr}/siteverify"))?,
+                secret: "verify-secret".into(),
+                challenge_url,
+                require_challenge: false,
+            }));
+        token_steam(false, None).await.0?;
+        assert!(matches!(
+            token_steam(true, Some([2; 32])).await.0,
+            Err(AccountServerRequestError::Other(_))
+        ));
+        token_steam(true, Some(VALID_KEY)).await.0?;
+
+        fake_apis.abort();
+
+        test_server.destroy().await
+    };
+    test.await.unwrap();
+}
@@ -0,0 +1,288 @@
+use std::{num::NonZeroU32, time::Duration};
+
+use axum::Json;
+use ddnet_accounts_shared::{
+    account_server::errors::AccountServerRequestError,
+    client::credential_auth_token::{
+        CredentialAuthTokenEmailRequest, CredentialAuthTokenOperation,
+    },
+};
+
+use crate::{
+    certs::{generate_key_and_cert, PrivateKeys},
+    check_config::check_config,
+    credential_auth_token::credential_auth_token_email,
+    prepare_email, prepare_steam,
+    settings::{apply_settings, watch_settings, SettingsSources},
+    tests::types::{SqliteTestOptions, SqliteTestServer},
+    DbDetails, Details, EmailDetails, EmailTransportDetails, LimiterValues, MemoryEmailDetails,
+    SqliteDetails, SteamDetails,
+};
+
+/// Tests that changed settings are applied to the running server,
+/// except for the ones that require a restart
+#[tokio::test]
+async fn settings_reload() {
+    const DB_FILE: &str = "test-account-server-settings.sqlite";
+    let test = async move {
+        let details = Details {
+            db: DbDetails::Sqlite(SqliteDetails {
+                path: DB_FILE.into(),
+            }),
+            http: Default::default(),
+            email: EmailDetails {
+                transport: EmailTransportDetails::Memory(MemoryEmailDetails { in_memory: true }),
+                email_from: "test@localhost".into(),
+            },
+            steam: SteamDetails {
+                auth_url: Some("http://127.0.0.1:3344".try_into()?),
+                publisher_auth_key: "my_secret_pub_auth_key".into(),
+                app_id: 123,
+                identify: None,
+            },
+            limitter: Default::default(),
+            admin: None,
+            metrics: Default::default(),
+            secret_verifier: None,
+        };
+
+        let mut email = prepare_email(&details.email).await?;
+        email.set_test_mode(true);
+        let server = SqliteTestServer::new(
+            DB_FILE,
+            SqliteTestOptions {
+                email,
+                steam: prepare_steam(&details.steam)?,
+                http: details.http.clone(),
+                limits: details.limitter.clone(),
+            },
+        )
+        .await?;
+        let (pool, shared) = (server.pool.clone(), server.shared.clone());
+
+        let credential_auth_token = || {
+            let shared = shared.clone();
+            let pool = pool.clone();
+            async move {
+                credential_auth_token_email(
+                    shared,
+                    pool,
+                    false,
+                    Json(CredentialAuthTokenEmailRequest {
+                        email: "test@localhost".parse().unwrap(),
+                        secret_key: None,
+                        op: CredentialAuthTokenOperation::Login,
+                        locale: None,
+                    }),
+                )
+                .await
+                .0
+            }
+        };
+        assert!(credential_auth_token().await.is_ok());
+        assert!(credential_auth_token().await.is_ok());
+
+        let mut new = details.clone();
+        new.limitter.email_tokens = LimiterValues {
+            time_until_another_attempt: Duration::from_secs(60 * 60),
+            initial_request_count: NonZeroU32::new(1).unwrap(),
+        };
+        new.email.email_from = "accounts@localhost".into();
+        // requires a restart
+        new.http.port = 4447;
+        new.db = DbDetails::Sqlite(SqliteDetails {
+            path: "other.sqlite".into(),
+        });
+        let applied = apply_settings(&shared, &details, new.clone()).await;
+        assert_eq!(applied.limitter, new.limitter);
+        assert_eq!(applied.email, new.email);
+        assert_eq!(applied.http, details.http);
+        assert_eq!(applied.db, details.db);
+
+        // the new limits start fresh
+        assert!(credential_auth_token().await.is_ok());
+        assert!(matches!(
+            credential_auth_token().await,
+            Err(AccountServerRequestError::RateLimited(_))
+        ));
+
+        // invalid settings are not applied
+        let mut invalid = applied.clone();
+        invalid.email.email_from = "not an email".into();
+        invalid.limitter.login.time_until_another_attempt = Duration::ZERO;
+        let res = apply_settings(&shared, &applied, invalid).await;
+        assert_eq!(res, applied);
+
+        // the watcher stops with the server
+        let watcher = tokio::spawn(watch_settings(
+            shared.clone(),
+            SettingsSources::new(Some("account-server-settings-reload.toml".into()), vec![]),
+            applied,
+        ));
+        shared.shutdown.trigger();
+        tokio::time::timeout(Duration::from_secs(2), watcher).await??;
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
+
+/// Tests that the environment overrides the settings file
+/// & the command line overrides both
+#[tokio::test]
+async fn settings_layers() {
+    const FILE: &str = "test-account-server-settings.toml";
+    let test = async move {
+        tokio::fs::write(
+            FILE,
+            r#"
+[db]
+host = "localhost"
+port = 3306
+database = "ddnet_accounts"
+username = "user"
+password = ""
+ca_cert_path = "/etc/mysql/ssl/ca-cert.pem"
+
+[http]
+port = 443
+
+[email]
+relay = "emails.localhost"
+relay_port = 465
+username = "account"
+password = ""
+email_from = "account@localhost"
+
+[steam]
+publisher_auth_key = ""
+app_id = 123
+
+[limitter]
+"#,
+        )
+        .await?;
+
+        let env = |vars: &[(&str, &str)]| {
+            vars.iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect::<Vec<_>>()
+        };
+        let sources = SettingsSources::new(Some(FILE.into()), vec!["http.port=8443".into()]);
+        let details = sources
+            .load_with_env(env(&[
+                ("ACCOUNT_DB_PASSWORD", "1234"),
+                ("ACCOUNT_EMAIL_PASSWORD", "email-password"),
+                ("ACCOUNT_EMAIL__EMAIL_FROM", "accounts@localhost"),
+                ("ACCOUNT_STEAM_PUBLISHER_AUTH_KEY", "publisher-key"),
+                ("ACCOUNT_HTTP_PORT", "8080"),
+                ("OTHER_DB_PASSWORD", "ignored"),
+            ]))
+            .await?;
+        let DbDetails::MySql(db) = &details.db else {
+            panic!("expected a mysql database");
+        };
+        // stays a string, even if it looks like a number
+        assert_eq!(db.password, "1234");
+        let EmailTransportDetails::Smtp(smtp) = &details.email.transport else {
+            panic!("expected a smtp transport");
+        };
+        assert_eq!(smtp.password, "email-password");
+        assert_eq!(details.email.email_from, "accounts@localhost");
+        assert_eq!(details.steam.publisher_auth_key, "publisher-key");
+        assert_eq!(details.http.port, 8443);
+
+        // settings that are missing in the file are created
+        for name in ["ACCOUNT_STEAM_IDENTIFY", "ACCOUNT_STEAM__IDENTIFY"] {
+            let details = SettingsSources::new(Some(FILE.into()), vec![])
+                .load_with_env(env(&[(name, "account")]))
+                .await?;
+            assert_eq!(details.steam.identify.as_deref(), Some("account"));
+        }
+
+        // unknown settings are only reported, e.g. variables of other programs
+        let details = SettingsSources::new(Some(FILE.into()), vec![])
+            .load_with_env(env(&[
+                ("ACCOUNT_DB_PASWORD", "1234"),
+                ("ACCOUNT_ID", "1"),
+                ("ACCOUNT_HTTP_PORT", "8080"),
+            ]))
+            .await?;
+        assert_eq!(details.http.port, 8080);
+
+        // invalid values are reported
+        assert!(SettingsSources::new(Some(FILE.into()), vec![])
+            .load_with_env(env(&[("ACCOUNT_EMAIL_EMAIL_FROM", "not an email")]))
+            .await
+            .is_err());
+        assert!(SettingsSources::new(
+            Some(FILE.into()),
+            vec!["limitter.login.time_until_another_attempt.secs=0".into()]
+        )
+        .load_with_env(vec![])
+        .await
+        .is_err());
+        assert!(
+            SettingsSources::new(Some(FILE.into()), vec!["http.port".into()])
+                .load_with_env(vec![])
+                .await
+                .is_err()
+        );
+
+        anyhow::Ok(())
+    };
+    let res = test.await;
+    let _ = tokio::fs::remove_file(FILE).await;
+    res.unwrap();
+}
+
+/// Tests that keys that don't belong to their certificate are found
+#[test]
+fn signing_keys_check() {
+    let (current_key, current_cert) = generate_key_and_cert(true).unwrap();
+    let (next_key, next_cert) = generate_key_and_cert(false).unwrap();
+    let keys = PrivateKeys {
+        current_key,
+        current_cert,
+        next_key,
+        next_cert,
+    };
+    assert!(keys.check().is_ok());
+
+    let swapped = PrivateKeys {
+        current_key: keys.next_key.clone(),
+        next_key: keys.current_key.clone(),
+        ..keys
+    };
+    assert!(swapped.check().is_err());
+}
+
+/// Tests that checking the settings does not create a sqlite database
+#[tokio::test]
+async fn check_config_sqlite() {
+    const DB_FILE: &str = "test-account-server-check-config.sqlite";
+    let _ = tokio::fs::remove_file(DB_FILE).await;
+
+    let details = Details {
+        db: DbDetails::Sqlite(SqliteDetails {
+            path: DB_FILE.into(),
+        }),
+        http: Default::default(),
+        email: EmailDetails {
+            transport: EmailTransportDetails::Memory(MemoryEmailDetails { in_memory: true }),
+            email_from: "test@localhost".into(),
+        },
+        steam: SteamDetails {
+            auth_url: Some("http://127.0.0.1:3344".try_into().unwrap()),
+            publisher_auth_key: "my_secret_pub_auth_key".into(),
+            app_id: 123,
+            identify: None,
+        },
+        limitter: Default::default(),
+        admin: None,
+        metrics: Default::default(),
+        secret_verifier: None,
+    };
+    assert!(!check_config(&details).await);
+    assert!(!std::path::Path::new(DB_FILE).exists());
+}
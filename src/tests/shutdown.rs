@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use crate::tests::types::SqliteTestServer;
+
+/// Tests that a shutdown finishes in-flight requests,
+/// but does not accept new connections
+#[tokio::test]
+async fn shutdown() {
+    let test = async move {
+        let mut server =
+            SqliteTestServer::new("test-account-server-shutdown.sqlite", Default::default())
+                .await?;
+        // a request that is still in-flight during the shutdown
+        server.app = std::mem::take(&mut server.app).route(
+            "/slow",
+            axum::routing::get(|| async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                "done"
+            }),
+        );
+        server.spawn(true);
+        let handle = server.server.take().unwrap();
+
+        let http = reqwest::Client::new();
+        let url = |path: &str| server.url(path);
+
+        http.get(url("/ping")).send().await?.error_for_status()?;
+
+        let slow = tokio::spawn(http.get(url("/slow")).send());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        server.shared.shutdown.trigger();
+
+        let res = slow.await??.error_for_status()?;
+        assert_eq!(res.text().await?, "done");
+
+        // the server & all background tasks stopped
+        tokio::time::timeout(Duration::from_secs(5), handle).await???;
+        assert!(reqwest::Client::new()
+            .get(url("/ping"))
+            .send()
+            .await
+            .is_err());
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
@@ -0,0 +1,219 @@
+use std::net::IpAddr;
+
+use ddnet_account_sql::query::Query;
+use ddnet_accounts_shared::{
+    account_server::{
+        account_events::{hw_id_hash, AccountEventCredential, AccountEventKind},
+        account_info::AccountInfoError,
+        errors::AccountServerRequestError,
+        otp::generate_otp,
+        sessions::SessionRevokeError,
+        sign::SignError,
+    },
+    client::{
+        account_events::prepare_account_events_request,
+        account_info::prepare_account_info_request,
+        credential_auth_token::CredentialAuthTokenOperation,
+        machine_id::machine_uid,
+        sessions::{prepare_session_revoke_request, prepare_sessions_request},
+        sign::prepare_sign_request,
+    },
+};
+
+use crate::{
+    account_events::account_events,
+    account_info::account_info,
+    credential_auth_token::queries::AddCredentialAuthToken,
+    login::login,
+    sessions::{session_revoke, sessions},
+    setup,
+    sign::sign,
+    tests::types::SqliteTestServer,
+    types::TokenType,
+};
+
+/// Tests the sqlite backend without a running http server
+#[tokio::test]
+async fn sqlite() {
+    let test = async move {
+        let server =
+            SqliteTestServer::new("test-account-server.sqlite", Default::default()).await?;
+        let (pool, shared) = (server.pool.clone(), server.shared.clone());
+
+        let add_token = |email: &'static str| {
+            let shared = shared.clone();
+            let pool = pool.clone();
+            async move {
+                let token = generate_otp();
+                let mut connection = pool.acquire().await?;
+                let mut con = connection.acquire().await?;
+                AddCredentialAuthToken {
+                    token: &token,
+                    ty: &TokenType::Email,
+                    identifier: email,
+                    op: &CredentialAuthTokenOperation::Login,
+                }
+                .query(&shared.db.credential_auth_token_statement)
+                .execute(&mut con)
+                .await?;
+                anyhow::Ok(hex::encode(token))
+            }
+        };
+
+        let client_ip: IpAddr = "192.0.2.1".parse()?;
+
+        // login creates the account
+        let (login_req, account_data) =
+            ddnet_accounts_shared::client::login::login(add_token("test@localhost").await?)?;
+        let account_id = login(shared.clone(), pool.clone(), client_ip, login_req).await?;
+
+        // emails are case insensitive, so this is the same account
+        let (login_req, _) =
+            ddnet_accounts_shared::client::login::login(add_token("TEST@localhost").await?)?;
+        assert_eq!(
+            login(shared.clone(), pool.clone(), client_ip, login_req).await?,
+            account_id
+        );
+
+        // tokens can only be used once
+        let token = add_token("test@localhost").await?;
+        let (login_req, _) = ddnet_accounts_shared::client::login::login(token.clone())?;
+        login(shared.clone(), pool.clone(), client_ip, login_req).await?;
+        let (login_req, _) = ddnet_accounts_shared::client::login::login(token)?;
+        assert!(login(shared.clone(), pool.clone(), client_ip, login_req)
+            .await
+            .is_err());
+
+        // sign the session of the first login
+        let hw_id = machine_uid()?;
+        sign(
+            shared.clone(),
+            pool.clone(),
+            prepare_sign_request(hw_id, &account_data.private_key, account_data.public_key),
+        )
+        .await?;
+
+        let info = account_info(
+            shared.clone(),
+            pool.clone(),
+            prepare_account_info_request(hw_id, &account_data.private_key, account_data.public_key),
+        )
+        .await?;
+        assert_eq!(info.account_id, account_id);
+        assert_eq!(info.credentials.len(), 1);
+
+        // a session that never logged in is reported typed
+        let (_, unknown_session) =
+            ddnet_accounts_shared::client::login::login(add_token("test@localhost").await?)?;
+        let err = account_info(
+            shared.clone(),
+            pool.clone(),
+            prepare_account_info_request(
+                hw_id,
+                &unknown_session.private_key,
+                unknown_session.public_key,
+            ),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<AccountInfoError>(),
+            Some(&AccountInfoError::SessionInvalid)
+        );
+        assert!(matches!(
+            sign(
+                shared.clone(),
+                pool.clone(),
+                prepare_sign_request(
+                    hw_id,
+                    &unknown_session.private_key,
+                    unknown_session.public_key
+                ),
+            )
+            .await,
+            Err(AccountServerRequestError::LogicError(
+                SignError::SessionInvalid
+            ))
+        ));
+
+        // all three logins created a session
+        let list = sessions(
+            shared.clone(),
+            pool.clone(),
+            prepare_sessions_request(hw_id, &account_data.private_key, account_data.public_key),
+        )
+        .await?;
+        assert_eq!(list.sessions.len(), 3);
+        assert_eq!(list.sessions.iter().filter(|s| s.is_current).count(), 1);
+        let other = list
+            .sessions
+            .iter()
+            .find(|s| !s.is_current)
+            .unwrap()
+            .fingerprint;
+
+        session_revoke(
+            shared.clone(),
+            pool.clone(),
+            prepare_session_revoke_request(
+                other,
+                hw_id,
+                &account_data.private_key,
+                account_data.public_key,
+            ),
+        )
+        .await?;
+        let list = sessions(
+            shared.clone(),
+            pool.clone(),
+            prepare_sessions_request(hw_id, &account_data.private_key, account_data.public_key),
+        )
+        .await?;
+        assert_eq!(list.sessions.len(), 2);
+        assert!(list.sessions.iter().all(|s| s.fingerprint != other));
+
+        // every login is part of the account events
+        let events = account_events(
+            shared.clone(),
+            pool.clone(),
+            prepare_account_events_request(
+                hw_id,
+                &account_data.private_key,
+                account_data.public_key,
+            ),
+        )
+        .await?;
+        assert_eq!(events.events.len(), 3);
+        assert!(events.events.iter().all(|event| {
+            event.kind == AccountEventKind::Login
+                && event.client_ip == client_ip
+                && event.hw_id_hash == Some(hw_id_hash(&hw_id))
+                && event.credential == Some(AccountEventCredential::Email)
+        }));
+
+        // revoking an unknown session fails, e.g. if it was revoked already
+        let err = session_revoke(
+            shared.clone(),
+            pool.clone(),
+            prepare_session_revoke_request(
+                other,
+                hw_id,
+                &account_data.private_key,
+                account_data.public_key,
+            ),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SessionRevokeError>(),
+            Some(&SessionRevokeError::SessionNotFound)
+        );
+
+        // the session tables can be migrated down & up again
+        setup::migrations().down(&pool, 1).await?;
+        setup::setup(&pool).await?;
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
@@ -0,0 +1,98 @@
+use std::{path::Path, time::Duration};
+
+use crate::{
+    http_listener::TlsDetails,
+    tests::types::{SqliteTestOptions, SqliteTestServer},
+    HttpServerDetails,
+};
+
+/// Writes a new self signed certificate, returns the certificate in PEM format.
+async fn write_cert(dir: &Path) -> anyhow::Result<String> {
+    let cert = rcgen::generate_simple_self_signed(vec![
+        "localhost".to_string(),
+        "127.0.0.1".to_string(),
+        "::1".to_string(),
+    ])?;
+    // like recommended for all watched files, replace them by moving
+    for (file, content) in [
+        ("key.pem", cert.key_pair.serialize_pem()),
+        ("cert.pem", cert.cert.pem()),
+    ] {
+        let tmp = dir.join(format!("{file}.tmp"));
+        tokio::fs::write(&tmp, content).await?;
+        tokio::fs::rename(tmp, dir.join(file)).await?;
+    }
+    Ok(cert.cert.pem())
+}
+
+async fn ping(cert: &str, url: &str) -> anyhow::Result<String> {
+    let http = reqwest::Client::builder()
+        .add_root_certificate(reqwest::Certificate::from_pem(cert.as_bytes())?)
+        .build()?;
+    Ok(http
+        .get(format!("{url}/ping"))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?)
+}
+
+/// Tests IPv4 & IPv6 listeners with tls & certificate reloading
+#[tokio::test]
+async fn tls() {
+    let test = async move {
+        let dir = tempfile::tempdir()?;
+        let cert = write_cert(dir.path()).await?;
+
+        let mut server = SqliteTestServer::new(
+            "test-account-server-tls.sqlite",
+            SqliteTestOptions {
+                http: HttpServerDetails {
+                    bind: vec!["127.0.0.1".parse()?, "::1".parse()?],
+                    tls: Some(TlsDetails {
+                        cert_path: dir.path().join("cert.pem"),
+                        key_path: dir.path().join("key.pem"),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .await?;
+        // every address has its own free port
+        let [ipv4, ipv6] = server.addrs[..] else {
+            unreachable!("two addresses are bound");
+        };
+        server.spawn(false);
+
+        assert_eq!(
+            ping(&cert, &format!("https://localhost:{}", ipv4.port())).await?,
+            "\"pong\""
+        );
+        assert_eq!(ping(&cert, &format!("https://{ipv6}")).await?, "\"pong\"");
+
+        // the new certificate is used without a restart
+        let new_cert = write_cert(dir.path()).await?;
+        let mut reloaded = false;
+        for _ in 0..50 {
+            if ping(&new_cert, &format!("https://{ipv4}")).await.is_ok() {
+                reloaded = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        assert!(reloaded);
+        assert!(ping(&cert, &format!("https://{ipv4}")).await.is_err());
+
+        // a client that never starts the handshake doesn't delay the shutdown
+        let _stalled = tokio::net::TcpStream::connect(ipv4).await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let handle = server.server.take().unwrap();
+        server.shared.shutdown.trigger();
+        tokio::time::timeout(Duration::from_secs(2), handle).await???;
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
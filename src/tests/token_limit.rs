@@ -0,0 +1,90 @@
+use std::{num::NonZeroU32, time::Duration};
+
+use axum::Json;
+use ddnet_accounts_shared::{
+    account_server::errors::AccountServerRequestError,
+    client::{
+        account_token::{AccountTokenEmailRequest, AccountTokenOperation},
+        credential_auth_token::{CredentialAuthTokenEmailRequest, CredentialAuthTokenOperation},
+    },
+};
+
+use crate::{
+    account_token::account_token_email,
+    credential_auth_token::credential_auth_token_email,
+    tests::types::{SqliteTestOptions, SqliteTestServer},
+    LimiterSettings, LimiterValues,
+};
+
+/// Tests that the tokens per email are limited, independent of the ip
+#[tokio::test]
+async fn token_limit() {
+    let test = async move {
+        let mut options = SqliteTestOptions {
+            limits: LimiterSettings {
+                email_tokens: LimiterValues {
+                    time_until_another_attempt: Duration::from_secs(60 * 60),
+                    initial_request_count: NonZeroU32::new(2).unwrap(),
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        options.email.set_test_mode(true);
+        let server =
+            SqliteTestServer::new("test-account-server-token-limit.sqlite", options).await?;
+        let (pool, shared) = (server.pool.clone(), server.shared.clone());
+
+        let credential_auth_token = |email: &str| {
+            let shared = shared.clone();
+            let pool = pool.clone();
+            let email = email.parse().unwrap();
+            async move {
+                credential_auth_token_email(
+                    shared,
+                    pool,
+                    false,
+                    Json(CredentialAuthTokenEmailRequest {
+                        email,
+                        secret_key: None,
+                        op: CredentialAuthTokenOperation::Login,
+                        locale: None,
+                    }),
+                )
+                .await
+                .0
+            }
+        };
+
+        assert!(credential_auth_token("test@localhost").await.is_ok());
+        // the same inbox
+        assert!(credential_auth_token("Test+spam@localhost").await.is_ok());
+        assert!(matches!(
+            credential_auth_token("test@localhost").await,
+            Err(AccountServerRequestError::RateLimited(_))
+        ));
+        // the limit is shared with the account tokens
+        let res = account_token_email(
+            shared.clone(),
+            pool.clone(),
+            false,
+            Json(AccountTokenEmailRequest {
+                email: "test@localhost".parse()?,
+                secret_key: None,
+                op: AccountTokenOperation::Delete,
+                locale: None,
+            }),
+        )
+        .await
+        .0;
+        assert!(matches!(
+            res,
+            Err(AccountServerRequestError::RateLimited(_))
+        ));
+        // other emails are not affected
+        assert!(credential_auth_token("test2@localhost").await.is_ok());
+
+        server.destroy().await
+    };
+    test.await.unwrap();
+}
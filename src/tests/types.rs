@@ -1,28 +1,30 @@
-use std::{num::NonZeroU32, sync::Arc, time::Duration};
+use std::{net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
 
 use axum::{extract::Query, response::IntoResponse, routing::get, Router};
 use ddnet_account_sql::any::AnyPool;
-use lettre::SmtpTransport;
 use parking_lot::Mutex;
 use serde::Deserialize;
 use tokio::{net::TcpSocket, task::JoinHandle};
 
 use crate::{
-    email::{EmailHook, EmailShared},
+    email::{transport::MemoryEmailTransport, EmailHook, EmailShared},
+    email_templates::EmailKind,
+    http_listener::HttpListener,
     prepare_db, prepare_http, prepare_statements, run, setup,
     shared::Shared,
-    steam::{self, SteamHook, SteamShared},
+    steam::{self, SteamHook, SteamParams, SteamShared},
+    DbDetails, HttpServerDetails, LimiterSettings, SqliteDetails,
 };
 
 pub async fn test_setup() -> anyhow::Result<AnyPool> {
-    prepare_db(&crate::DbDetails {
+    prepare_db(&crate::DbDetails::MySql(crate::MySqlDetails {
         host: "localhost".into(),
         port: 3306,
         database: "ddnet_account_test".into(),
         username: "ddnet-account-test".into(),
         password: "test".into(),
         ca_cert_path: "/etc/mysql/ssl/ca-cert.pem".into(),
-    })
+    }))
     .await
 }
 
@@ -48,8 +50,7 @@ impl TestAccServer {
         setup::setup(&pool).await?;
 
         let db = prepare_statements(&pool).await?;
-        let mut email: EmailShared =
-            ("test@localhost", SmtpTransport::unencrypted_localhost()).into();
+        let mut email: EmailShared = ("test@localhost", MemoryEmailTransport::default()).into();
         email.set_test_mode(email_test_mode);
         #[derive(Debug)]
         struct EmailReader {
@@ -58,6 +59,13 @@ impl TestAccServer {
         }
         impl EmailHook for EmailReader {
             fn on_mail(&self, email_subject: &str, email_body: &str) {
+                // notifications don't contain any token
+                if EmailKind::ALL.iter().any(|kind| {
+                    matches!(kind, EmailKind::Notification(_))
+                        && kind.default_subject() == email_subject
+                }) {
+                    return;
+                }
                 if [
                     "DDNet Logout All Sessions",
                     "DDNet Link Credential",
@@ -84,12 +92,12 @@ impl TestAccServer {
             account_token: account_token.clone(),
         });
 
-        let mut steam = SteamShared::new(
+        let mut steam = SteamShared::new(SteamParams::new(
             "http://127.0.0.1:3344".try_into()?,
             "my_secret_pub_auth_key",
             Some("account"),
             123,
-        )?;
+        ))?;
         #[derive(Debug)]
         struct SteamReader {}
         impl SteamHook for SteamReader {
@@ -184,10 +192,33 @@ impl TestAccServer {
                     time_until_another_attempt: Duration::from_nanos(1),
                     initial_request_count: NonZeroU32::new(u32::MAX).unwrap(),
                 },
+                sessions: crate::LimiterValues {
+                    time_until_another_attempt: Duration::from_nanos(1),
+                    initial_request_count: NonZeroU32::new(u32::MAX).unwrap(),
+                },
+                session_revoke: crate::LimiterValues {
+                    time_until_another_attempt: Duration::from_nanos(1),
+                    initial_request_count: NonZeroU32::new(u32::MAX).unwrap(),
+                },
+                account_events: crate::LimiterValues {
+                    time_until_another_attempt: Duration::from_nanos(1),
+                    initial_request_count: NonZeroU32::new(u32::MAX).unwrap(),
+                },
+                email_tokens: crate::LimiterValues {
+                    time_until_another_attempt: Duration::from_nanos(1),
+                    initial_request_count: NonZeroU32::new(u32::MAX).unwrap(),
+                },
+                steam_tokens: crate::LimiterValues {
+                    time_until_another_attempt: Duration::from_nanos(1),
+                    initial_request_count: NonZeroU32::new(u32::MAX).unwrap(),
+                },
             }
         };
         let (listener, app, shared) = prepare_http(
-            &crate::HttpServerDetails { port: 4433 },
+            &crate::HttpServerDetails {
+                port: 4433,
+                ..Default::default()
+            },
             db,
             email,
             steam,
@@ -220,6 +251,125 @@ impl TestAccServer {
     }
 }
 
+/// Removes the sqlite database & the files of its write-ahead log.
+pub async fn remove_sqlite_files(db_file: &str) {
+    for file in [
+        db_file.to_string(),
+        format!("{db_file}-wal"),
+        format!("{db_file}-shm"),
+    ] {
+        let _ = tokio::fs::remove_file(file).await;
+    }
+}
+
+/// The parts of a [`SqliteTestServer`] that tests customize.
+pub struct SqliteTestOptions {
+    pub email: EmailShared,
+    pub steam: SteamShared,
+    /// The port is ignored, a free port is used instead.
+    pub http: HttpServerDetails,
+    pub limits: LimiterSettings,
+}
+
+impl Default for SqliteTestOptions {
+    fn default() -> Self {
+        Self {
+            email: ("test@localhost", MemoryEmailTransport::default()).into(),
+            steam: SteamShared::new(SteamParams::new(
+                "http://127.0.0.1:3344".try_into().unwrap(),
+                "my_secret_pub_auth_key",
+                Some("account"),
+                123,
+            ))
+            .unwrap(),
+            http: Default::default(),
+            limits: Default::default(),
+        }
+    }
+}
+
+/// An account server on top of a sqlite database,
+/// so the test does not need a MySQL server.
+pub struct SqliteTestServer {
+    db_file: &'static str,
+    pub(crate) pool: AnyPool,
+    pub(crate) shared: Arc<Shared>,
+    /// The addresses the http server listens on.
+    pub(crate) addrs: Vec<SocketAddr>,
+    /// Can be extended before [`SqliteTestServer::spawn`].
+    pub(crate) app: Router,
+    listener: Option<HttpListener>,
+    pub(crate) server: Option<JoinHandle<anyhow::Result<()>>>,
+}
+
+impl SqliteTestServer {
+    pub(crate) async fn new(
+        db_file: &'static str,
+        options: SqliteTestOptions,
+    ) -> anyhow::Result<Self> {
+        // ignore old test runs
+        remove_sqlite_files(db_file).await;
+
+        let pool = prepare_db(&DbDetails::Sqlite(SqliteDetails {
+            path: db_file.into(),
+        }))
+        .await?;
+
+        setup::setup(&pool).await?;
+
+        let db = prepare_statements(&pool).await?;
+        let (listener, app, shared) = prepare_http(
+            &HttpServerDetails {
+                port: 0,
+                ..options.http
+            },
+            db,
+            options.email,
+            options.steam,
+            &pool,
+            &options.limits,
+        )
+        .await?;
+
+        Ok(Self {
+            db_file,
+            pool,
+            shared,
+            addrs: listener.local_addrs()?,
+            app,
+            listener: Some(listener),
+            server: None,
+        })
+    }
+
+    /// The url of `path` on the first address of the http server.
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("http://{}{path}", self.addrs[0])
+    }
+
+    /// Runs the http server, see [`run`].
+    pub(crate) fn spawn(&mut self, handle_updates: bool) {
+        let listener = self.listener.take().expect("the server only runs once");
+        let app = std::mem::take(&mut self.app);
+        let pool = self.pool.clone();
+        let shared = self.shared.clone();
+        self.server = Some(tokio::spawn(async move {
+            run(listener, app, pool, shared, handle_updates).await
+        }));
+    }
+
+    pub(crate) async fn destroy(self) -> anyhow::Result<()> {
+        if let Some(server) = self.server {
+            server.abort();
+            let _ = server.await;
+        }
+
+        setup::delete(&self.pool).await?;
+        remove_sqlite_files(self.db_file).await;
+        anyhow::Ok(())
+    }
+}
+
 pub struct TestGameServer {
     pool: AnyPool,
     pub(crate) game_server_data: Arc<ddnet_account_game_server::shared::Shared>,
@@ -6,6 +6,7 @@ use ddnet_account_client::{
 };
 use ddnet_account_client_reqwest::client::ClientReqwestTokioFs;
 use ddnet_accounts_shared::{
+    account_server::{errors::AccountServerRequestError, unlink_credential::UnlinkCredentialError},
     client::{
         account_token::AccountTokenOperation, credential_auth_token::CredentialAuthTokenOperation,
     },
@@ -71,7 +72,9 @@ async fn unlink_credential_hardening() {
         .await;
         assert!(matches!(
             res,
-            Err(UnlinkCredentialResult::AccountServerRequstError(_))
+            Err(UnlinkCredentialResult::AccountServerRequstError(
+                AccountServerRequestError::LogicError(UnlinkCredentialError::LastCredential)
+            ))
         ));
 
         // link steam to the account
@@ -150,7 +153,9 @@ async fn unlink_credential_hardening() {
         .await;
         assert!(matches!(
             res,
-            Err(UnlinkCredentialResult::AccountServerRequstError(_))
+            Err(UnlinkCredentialResult::AccountServerRequstError(
+                AccountServerRequestError::LogicError(UnlinkCredentialError::LastCredential)
+            ))
         ));
 
         // link an email to the account again
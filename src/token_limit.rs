@@ -0,0 +1,72 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::{rate_limit::KeyedLimiter, LimiterSettings};
+
+/// A token was requested too often for the same credential.
+#[derive(Debug)]
+pub struct TokenRateLimited {
+    wait: Duration,
+}
+
+impl Display for TokenRateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Too many tokens were requested for this credential! Wait for {}s",
+            self.wait.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for TokenRateLimited {}
+
+/// Limits the tokens issued per email & per steamid,
+/// so a single inbox can't be flooded by rotating ips.
+#[derive(Debug)]
+pub struct TokenLimiter {
+    email: KeyedLimiter<String>,
+    steam: KeyedLimiter<i64>,
+}
+
+/// `Name+Tag@Example.org` -> `name@example.org`, since all of these end
+/// up in the same inbox for most providers.
+fn normalize_email(email: &email_address::EmailAddress) -> String {
+    let local_part = email.local_part().to_lowercase();
+    let local_part = local_part
+        .split_once('+')
+        .map(|(local_part, _)| local_part)
+        .unwrap_or(&local_part);
+    format!("{local_part}@{}", email.domain().to_lowercase())
+}
+
+impl TokenLimiter {
+    pub fn new(settings: &LimiterSettings) -> anyhow::Result<Self> {
+        Ok(Self {
+            email: KeyedLimiter::new(&settings.email_tokens)?,
+            steam: KeyedLimiter::new(&settings.steam_tokens)?,
+        })
+    }
+
+    pub fn update(&self, settings: &LimiterSettings) -> anyhow::Result<()> {
+        self.email.update(&settings.email_tokens)?;
+        self.steam.update(&settings.steam_tokens)
+    }
+
+    pub fn check_email(&self, email: &email_address::EmailAddress) -> Result<(), TokenRateLimited> {
+        self.email
+            .check(&normalize_email(email))
+            .map_err(|wait| TokenRateLimited { wait })
+    }
+
+    pub fn check_steam(&self, steamid64: i64) -> Result<(), TokenRateLimited> {
+        self.steam
+            .check(&steamid64)
+            .map_err(|wait| TokenRateLimited { wait })
+    }
+
+    /// Forgets credentials that would not be limited anymore anyway.
+    pub fn retain_recent(&self) {
+        self.email.retain_recent();
+        self.steam.retain_recent();
+    }
+}
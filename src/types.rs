@@ -1,5 +1,8 @@
-use ddnet_accounts_shared::client::{
-    account_token::AccountTokenOperation, credential_auth_token::CredentialAuthTokenOperation,
+use ddnet_accounts_shared::{
+    account_server::account_events::AccountEventCredential,
+    client::{
+        account_token::AccountTokenOperation, credential_auth_token::CredentialAuthTokenOperation,
+    },
 };
 use serde::{Deserialize, Serialize};
 use strum::{EnumString, IntoStaticStr};
@@ -20,3 +23,12 @@ pub type AccountTokenType = AccountTokenOperation;
 // IMPORTANT: keep this in sync with the ty enum in src/setup/mysql/credential_auth_tokens.sql
 /// The type of token that was created.
 pub type CredentialAuthTokenType = CredentialAuthTokenOperation;
+
+impl From<TokenType> for AccountEventCredential {
+    fn from(ty: TokenType) -> Self {
+        match ty {
+            TokenType::Email => Self::Email,
+            TokenType::Steam => Self::Steam,
+        }
+    }
+}
@@ -1,20 +1,27 @@
 pub mod queries;
 
-use std::{str::FromStr, sync::Arc};
+use std::{net::IpAddr, str::FromStr, sync::Arc};
 
-use axum::Json;
+use axum::{Extension, Json};
 use ddnet_account_sql::{any::AnyPool, query::Query};
 use ddnet_accounts_shared::{
     account_server::{
-        errors::{AccountServerRequestError, Empty},
-        result::AccountServerReqResult,
+        account_events::AccountEventKind, errors::RequestFailure, result::AccountServerReqResult,
+        unlink_credential::UnlinkCredentialError,
     },
     client::unlink_credential::UnlinkCredentialRequest,
 };
 use queries::{UnlinkCredentialByEmail, UnlinkCredentialBySteam};
 
 use crate::{
-    login::get_and_invalidate_credential_auth_token,
+    account_events::add_account_event,
+    client_ip::ClientIp,
+    email::{linked_email, send_notification, AccountNotification},
+    login::{
+        get_and_invalidate_credential_auth_token,
+        queries::{AccountIdFromEmail, AccountIdFromSteam},
+    },
+    request_error::request_logic_error,
     shared::Shared,
     types::{CredentialAuthTokenType, TokenType},
 };
@@ -22,27 +29,29 @@ use crate::{
 pub async fn unlink_credential_request(
     shared: Arc<Shared>,
     pool: AnyPool,
+    Extension(ClientIp(client_ip)): Extension<ClientIp>,
     Json(data): Json<UnlinkCredentialRequest>,
-) -> Json<AccountServerReqResult<(), Empty>> {
-    Json(unlink_credential(shared, pool, data).await.map_err(|err| {
-        AccountServerRequestError::Unexpected {
-            target: "unlink_credential".into(),
-            err: err.to_string(),
-            bt: err.backtrace().to_string(),
-        }
-    }))
+) -> Json<AccountServerReqResult<(), UnlinkCredentialError>> {
+    Json(
+        unlink_credential(shared, pool, client_ip, data)
+            .await
+            .map_err(|err| request_logic_error("unlink_credential", err)),
+    )
 }
 
 pub async fn unlink_credential(
     shared: Arc<Shared>,
     pool: AnyPool,
+    client_ip: IpAddr,
     data: UnlinkCredentialRequest,
 ) -> anyhow::Result<()> {
     let mut connection = pool.acquire().await?;
     let mut connection = connection.acquire().await?;
 
-    connection
+    let shared_clone = shared.clone();
+    let (notify, ty) = connection
         .transaction(|mut connection| {
+            let shared = shared_clone;
             Box::pin(async move {
                 let token_data = get_and_invalidate_credential_auth_token(
                     &shared,
@@ -50,48 +59,103 @@ pub async fn unlink_credential(
                     &mut connection.con(),
                 )
                 .await?
-                .ok_or_else(|| anyhow::anyhow!("Credential auth token is invalid/expired."))?;
+                .ok_or(UnlinkCredentialError::TokenInvalid)?;
                 anyhow::ensure!(
                     token_data.op == CredentialAuthTokenType::UnlinkCredential,
-                    "Credential auth token was not for unlinking \
-                    the current credential from its account"
+                    RequestFailure::WrongTokenOp
                 );
 
-                let affected_rows = match token_data.ty {
+                let (account_id, affected_rows) = match token_data.ty {
                     TokenType::Email => {
                         let email = email_address::EmailAddress::from_str(&token_data.identifier)?;
+                        // the account the email belongs to, for the account events
+                        let qry = AccountIdFromEmail { email: &email };
+                        let account_id = qry
+                            .query(&shared.db.account_id_from_email_qry_statement)
+                            .fetch_optional(&mut connection.con())
+                            .await?
+                            .map(|row| AccountIdFromEmail::row_data(&row))
+                            .transpose()?
+                            .map(|data| data.account_id);
+
                         // remove the current email, if exists.
                         let qry = UnlinkCredentialByEmail { email: &email };
 
-                        qry.query(&shared.db.unlink_credential_by_email_statement)
+                        let affected_rows = qry
+                            .query(&shared.db.unlink_credential_by_email_statement)
                             .execute(&mut connection.con())
                             .await?
-                            .rows_affected()
+                            .rows_affected();
+                        (account_id, affected_rows)
                     }
                     TokenType::Steam => {
                         let steamid64: i64 = token_data.identifier.parse()?;
+                        // the account the steam id belongs to, for the account events
+                        let qry = AccountIdFromSteam {
+                            steamid64: &steamid64,
+                        };
+                        let account_id = qry
+                            .query(&shared.db.account_id_from_steam_qry_statement)
+                            .fetch_optional(&mut connection.con())
+                            .await?
+                            .map(|row| AccountIdFromSteam::row_data(&row))
+                            .transpose()?
+                            .map(|data| data.account_id);
+
                         // remove the current steam, if exists.
                         let qry = UnlinkCredentialBySteam {
                             steamid64: &steamid64,
                         };
 
-                        qry.query(&shared.db.unlink_credential_by_steam_statement)
+                        let affected_rows = qry
+                            .query(&shared.db.unlink_credential_by_steam_statement)
                             .execute(&mut connection.con())
                             .await?
-                            .rows_affected()
+                            .rows_affected();
+                        (account_id, affected_rows)
                     }
                 };
 
-                anyhow::ensure!(
-                    affected_rows > 0,
-                    "No credential was unlinked. \
-                    There has to be at least one credential per account."
-                );
+                let Some(account_id) = account_id else {
+                    return Err(UnlinkCredentialError::CredentialNotLinked.into());
+                };
+                // there has to be at least one credential per account
+                anyhow::ensure!(affected_rows > 0, UnlinkCredentialError::LastCredential);
 
-                anyhow::Ok(())
+                let mut notify = linked_email(&shared, &mut connection.con(), &account_id).await?;
+                add_account_event(
+                    &shared,
+                    &mut connection.con(),
+                    &account_id,
+                    AccountEventKind::UnlinkCredential,
+                    client_ip,
+                    None,
+                    Some(token_data.ty.into()),
+                )
+                .await?;
+                // the unlinked email itself is notified too
+                if matches!(token_data.ty, TokenType::Email) {
+                    notify = Some(email_address::EmailAddress::from_str(
+                        &token_data.identifier,
+                    )?);
+                }
+
+                anyhow::Ok((notify, token_data.ty))
             })
         })
         .await?;
 
+    if let Some(to) = notify {
+        send_notification(
+            &shared,
+            &mut connection,
+            &to,
+            AccountNotification::CredentialUnlinked,
+            Some(ty),
+            client_ip,
+        )
+        .await;
+    }
+
     Ok(())
 }
@@ -1,13 +1,22 @@
 use std::{sync::Arc, time::Duration};
 
 use ddnet_account_sql::{any::AnyPool, query::Query};
-use queries::{CleanupAccountTokens, CleanupCerts, CleanupCredentialAuthTokens};
+use queries::{
+    CleanupAccountEvents, CleanupAccountTokens, CleanupCerts, CleanupCredentialAuthTokens,
+};
 
-use crate::{email::EmailShared, email_limit, ip_limit, shared::Shared};
+use crate::{
+    email::EmailShared, email_limit, email_templates::EmailTemplates, file_watcher::FileWatcher,
+    ip_limit, shared::Shared,
+};
 
 pub mod queries;
 
 pub async fn update_impl(pool: &AnyPool, shared: &Arc<Shared>) {
+    // forget credentials & ips that are not rate limited anymore
+    shared.token_limiter.retain_recent();
+    shared.rate_limiters.retain_recent();
+
     if let Ok(mut connection) = pool.acquire().await {
         if let Ok(mut connection) = connection.acquire().await {
             // cleanup credential auth tokens
@@ -27,25 +36,45 @@ pub async fn update_impl(pool: &AnyPool, shared: &Arc<Shared>) {
                 .query(&shared.db.cleanup_certs_statement)
                 .execute(&mut connection)
                 .await;
+
+            // cleanup old account events
+            let _ = CleanupAccountEvents {}
+                .query(&shared.db.cleanup_account_events_statement)
+                .execute(&mut connection)
+                .await;
         }
     }
 }
 
-pub async fn update(pool: AnyPool, shared: Arc<Shared>) -> ! {
+pub async fn update(pool: AnyPool, shared: Arc<Shared>) {
     loop {
         update_impl(&pool, &shared).await;
 
         // only do the update once per hour
-        tokio::time::sleep(Duration::from_secs(60 * 60 * 24)).await;
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(60 * 60 * 24)) => {}
+            _ = shared.shutdown.wait() => return,
+        }
+    }
+}
+
+/// Reloads all email templates, whenever one of them changes.
+async fn watch_email_templates(shared: Arc<Shared>, mut watcher: FileWatcher) {
+    while watcher.wait_for_change().await.is_ok() {
+        let templates = EmailShared::load_templates().await;
+        *shared.email.templates.write() = Arc::new(templates);
     }
 }
 
+/// Reloads the files on change, until the shutdown.
 pub async fn handle_watchers(shared: Arc<Shared>) {
+    let shutdown = shared.shutdown.clone();
     let shared_email_deny = shared.clone();
     let shared_email_allow = shared.clone();
+    let shared_email_templates = shared.clone();
     let shared_email_account_tokens = shared.clone();
     let shared_email_credential_auth_tokens = shared.clone();
-    let res = tokio::try_join!(
+    let mut watchers = [
         tokio::spawn(async move {
             let mut ip_ban = ip_limit::IpDenyList::watcher();
             loop {
@@ -79,37 +108,30 @@ pub async fn handle_watchers(shared: Arc<Shared>) {
                 }
             }
         }),
-        tokio::spawn(async move {
-            let mut email_account_tokens = EmailShared::watcher("account_tokens.html");
-            loop {
-                if email_account_tokens.wait_for_change().await.is_ok() {
-                    if let Ok(mail) = EmailShared::load_email_template("account_tokens.html").await
-                    {
-                        *shared_email_account_tokens.account_tokens_email.write() = Arc::new(mail);
-                    }
-                } else {
-                    break;
-                }
+        tokio::spawn(watch_email_templates(
+            shared_email_templates,
+            EmailShared::watcher(EmailTemplates::DIR),
+        )),
+        // the templates of older versions
+        tokio::spawn(watch_email_templates(
+            shared_email_account_tokens,
+            EmailShared::watcher("account_tokens.html"),
+        )),
+        tokio::spawn(watch_email_templates(
+            shared_email_credential_auth_tokens,
+            EmailShared::watcher("credential_auth_tokens.html"),
+        )),
+    ];
+    tokio::select! {
+        res = futures::future::try_join_all(&mut watchers) => {
+            if let Err(err) = res {
+                log::error!("{err}");
             }
-        }),
-        tokio::spawn(async move {
-            let mut email_credential_auth = EmailShared::watcher("credential_auth_tokens.html");
-            loop {
-                if email_credential_auth.wait_for_change().await.is_ok() {
-                    if let Ok(mail) =
-                        EmailShared::load_email_template("credential_auth_tokens.html").await
-                    {
-                        *shared_email_credential_auth_tokens
-                            .credential_auth_tokens_email
-                            .write() = Arc::new(mail);
-                    }
-                } else {
-                    break;
-                }
-            }
-        }),
-    );
-    if let Err(err) = res {
-        log::error!("{err}");
+        }
+        _ = shutdown.wait() => {}
+    }
+    // the watchers only wait for changes, so they can simply be stopped
+    for watcher in watchers {
+        watcher.abort();
     }
 }
@@ -0,0 +1,5 @@
+DELETE FROM
+    account_events
+WHERE
+    -- only the recent events are relevant to investigate suspicious activity
+    account_events.create_time <= DATE_SUB(UTC_TIMESTAMP(), INTERVAL 90 DAY);
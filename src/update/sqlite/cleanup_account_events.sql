@@ -0,0 +1,5 @@
+DELETE FROM
+    account_events
+WHERE
+    -- only the recent events are relevant to investigate suspicious activity
+    account_events.create_time <= datetime('now', '-90 days');
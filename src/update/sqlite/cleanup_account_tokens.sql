@@ -0,0 +1,4 @@
+DELETE FROM
+    account_tokens
+WHERE
+    account_tokens.valid_until <= datetime('now');
@@ -0,0 +1,5 @@
+DELETE FROM
+    certs
+WHERE
+    -- two days earlier, the corresponding keys shouldn't be in use anymore anyway
+    certs.valid_until <= datetime('now', '+2 days');
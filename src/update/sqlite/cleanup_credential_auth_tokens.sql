@@ -0,0 +1,4 @@
+DELETE FROM
+    credential_auth_tokens
+WHERE
+    credential_auth_tokens.valid_until <= datetime('now');